@@ -0,0 +1,64 @@
+import { describe, expect, it } from "bun:test";
+import type { BookModel } from "@epub-parser";
+import { parseReaderArgs, resolveChapterTarget } from "@reader-app/args";
+
+const book: BookModel = {
+  id: "b",
+  metadata: {},
+  spine: ["c1", "c2", "c3"].map(idref => ({ idref, linear: true })),
+  manifest: Object.fromEntries(
+    ["c1", "c2", "c3"].map(id => [id, { id, href: `content/${id}.xhtml`, mediaType: "application/xhtml+xml" }]),
+  ),
+  toc: [],
+  resources: { basePath: "", items: {}, getContent: async () => null },
+};
+
+describe("reader arguments", () => {
+  it("reads flags, options and the book path in any order", () => {
+    expect(parseReaderArgs(["--profile", "kid", "book.epub", "--chapter", "5", "--ipc"])).toEqual({
+      validate: false,
+      dumpJson: false,
+      noText: false,
+      ipc: true,
+      profile: "kid",
+      epubPath: "book.epub",
+      target: { chapter: 5 },
+    });
+    expect(parseReaderArgs(["book.epub", "--href", "content/ch5.xhtml"]).target).toEqual({
+      href: "content/ch5.xhtml",
+    });
+    expect(parseReaderArgs([]).target).toBeUndefined();
+  });
+
+  it("rejects missing values, non-numeric chapters, unknown options and both targets at once", () => {
+    expect(() => parseReaderArgs(["book.epub", "--chapter"])).toThrow("--chapter needs a value");
+    expect(() => parseReaderArgs(["--profile", "--ipc"])).toThrow("--profile needs a value");
+    expect(() => parseReaderArgs(["book.epub", "--chapter", "five"])).toThrow("chapter number");
+    expect(() => parseReaderArgs(["--chapters", "5"])).toThrow("Unknown option");
+    expect(() => parseReaderArgs(["--chapter", "1", "--href", "a.xhtml"])).toThrow("not both");
+  });
+});
+
+describe("chapter target", () => {
+  it("opens 1-based chapter numbers and clamps the ones outside the book with a warning", () => {
+    const warnings: string[] = [];
+    const warn = (message: string) => warnings.push(message);
+
+    expect(resolveChapterTarget(book, { chapter: 2 }, warn)).toBe(1);
+    expect(warnings).toEqual([]);
+    expect(resolveChapterTarget(book, { chapter: 9 }, warn)).toBe(2);
+    expect(resolveChapterTarget(book, { chapter: 0 }, warn)).toBe(0);
+    expect(warnings).toEqual([
+      "--chapter 9 is outside 1..3; opening chapter 3",
+      "--chapter 0 is outside 1..3; opening chapter 1",
+    ]);
+  });
+
+  it("finds the spine document an href names and keeps the saved position for unknown ones", () => {
+    const warnings: string[] = [];
+
+    expect(resolveChapterTarget(book, { href: "content/c3.xhtml#top" }, message => warnings.push(message))).toBe(2);
+    expect(resolveChapterTarget(book, { href: "content/gone.xhtml" }, message => warnings.push(message))).toBeNull();
+    expect(warnings).toEqual(["No chapter matches --href content/gone.xhtml; opening at the saved position"]);
+  });
+});
@@ -0,0 +1,107 @@
+import { describe, expect, it } from "bun:test";
+import type { BookModel } from "@epub-parser";
+import { InMemoryStateStore } from "@state-store";
+import { applyIpcCommand, parseIpcCommand, runIpc, snapshotSession, type ReaderSession } from "@reader-app/ipc";
+
+function fakeBook(id: string, chapters: number): BookModel {
+  return {
+    id,
+    metadata: { title: `Book ${id}` },
+    spine: Array.from({ length: chapters }, (_item, index) => ({ idref: `c${index}`, linear: true })),
+    manifest: {},
+    toc: [],
+    resources: { basePath: "", items: {}, getContent: async () => null },
+  };
+}
+
+async function* linesOf(...lines: string[]) {
+  yield* lines;
+}
+
+describe("ipc commands", () => {
+  it("parses the supported commands", () => {
+    expect(parseIpcCommand('{"cmd":"next_chapter"}')).toEqual({ cmd: "next_chapter" });
+    expect(parseIpcCommand('{"cmd":"jump","index":5}')).toEqual({ cmd: "jump", index: 5 });
+    expect(parseIpcCommand('{"cmd":"open","path":"a.epub"}')).toEqual({ cmd: "open", path: "a.epub" });
+  });
+
+  it("rejects malformed commands", () => {
+    expect(() => parseIpcCommand("{nope")).toThrow("Not valid JSON");
+    expect(() => parseIpcCommand("[1]")).toThrow("JSON objects");
+    expect(() => parseIpcCommand('{"cmd":"jump","index":"5"}')).toThrow("integer");
+    expect(() => parseIpcCommand('{"cmd":"open"}')).toThrow("path");
+    expect(() => parseIpcCommand('{"cmd":"explode"}')).toThrow("Unknown command");
+  });
+
+  it("maps commands onto the session and saves progress", async () => {
+    const store = new InMemoryStateStore();
+    const deps = { openBook: async (path: string) => fakeBook(path, 3), store };
+    let session: ReaderSession = await applyIpcCommand({ book: null, chapter: 0 }, { cmd: "open", path: "a" }, deps);
+
+    session = await applyIpcCommand(session, { cmd: "next_chapter" }, deps);
+    session = await applyIpcCommand(session, { cmd: "next_chapter" }, deps);
+    session = await applyIpcCommand(session, { cmd: "next_chapter" }, deps);
+    expect(session.chapter).toBe(2);
+    session = await applyIpcCommand(session, { cmd: "jump", index: 1 }, deps);
+    expect(await store.loadProgress("a")).toEqual({ spineIndex: 1, offset: 0 });
+
+    const reopened = await applyIpcCommand({ book: null, chapter: 0 }, { cmd: "open", path: "a" }, deps);
+    expect(reopened.chapter).toBe(1);
+  });
+
+  it("skips linear=\"no\" chapters when stepping but jumps to them by href", async () => {
+    const book: BookModel = {
+      ...fakeBook("n", 3),
+      spine: [
+        { idref: "c0", linear: true },
+        { idref: "answers", linear: false },
+        { idref: "c2", linear: true },
+      ],
+      manifest: {
+        c0: { id: "c0", href: "OEBPS/c0.xhtml", mediaType: "application/xhtml+xml" },
+        answers: { id: "answers", href: "OEBPS/answers.xhtml", mediaType: "application/xhtml+xml" },
+        c2: { id: "c2", href: "OEBPS/c2.xhtml", mediaType: "application/xhtml+xml" },
+      },
+      resources: { basePath: "OEBPS", items: {}, getContent: async () => null },
+    };
+    const deps = { openBook: async () => book };
+    let session: ReaderSession = { book, path: "n", chapter: 0 };
+
+    session = await applyIpcCommand(session, { cmd: "next_chapter" }, deps);
+    expect(session.chapter).toBe(2);
+    session = await applyIpcCommand(session, { cmd: "previous_chapter" }, deps);
+    expect(session.chapter).toBe(0);
+
+    expect(parseIpcCommand('{"cmd":"jump_to_chapter_href","href":"answers.xhtml#q2"}')).toEqual({
+      cmd: "jump_to_chapter_href",
+      href: "answers.xhtml#q2",
+    });
+    session = await applyIpcCommand(session, { cmd: "jump_to_chapter_href", href: "answers.xhtml#q2" }, deps);
+    expect(session.chapter).toBe(1);
+    session = await applyIpcCommand(session, { cmd: "jump_to_chapter_href", href: "OEBPS/c2.xhtml" }, deps);
+    expect(session.chapter).toBe(2);
+    await expect(
+      applyIpcCommand(session, { cmd: "jump_to_chapter_href", href: "gone.xhtml" }, deps),
+    ).rejects.toMatchObject({ code: "IPC_UNKNOWN_HREF" });
+    expect(snapshotSession(session).chapterCount).toBe(2);
+  });
+
+  it("answers every line and keeps going after errors", async () => {
+    const output: string[] = [];
+    const deps = { openBook: async (path: string) => fakeBook(path, 2) };
+    const session = await runIpc(
+      linesOf('{"cmd":"next_chapter"}', "garbage", '{"cmd":"open","path":"b"}', "", '{"cmd":"jump","index":9}'),
+      line => output.push(line),
+      { book: null, chapter: 0 },
+      deps,
+    );
+
+    expect(output.map(line => JSON.parse(line))).toEqual([
+      { ok: false, code: "IPC_NO_BOOK", error: "No book is open" },
+      { ok: false, code: "IPC_MALFORMED", error: "Not valid JSON: garbage" },
+      { ok: true, state: { path: "b", bookId: "b", title: "Book b", chapter: 0, chapterCount: 2 } },
+      { ok: false, code: "IPC_OUT_OF_RANGE", error: "Chapter 9 is outside 0..1" },
+    ]);
+    expect(session.chapter).toBe(0);
+  });
+});
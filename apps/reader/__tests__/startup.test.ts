@@ -0,0 +1,44 @@
+import { describe, expect, it } from "bun:test";
+import { mkdtempSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { createLocalStorageBackend, InMemoryStateStore, SettingsStore } from "@state-store";
+import { chooseStartupBook, CLI_SETTINGS_KEY, DEFAULT_CLI_SETTINGS, JsonFileStorage } from "@reader-app/startup";
+
+const onDisk = new Set(["/books/last.epub", "/books/given.epub"]);
+const exists = (path: string) => onDisk.has(path);
+const lastOpen = { ...DEFAULT_CLI_SETTINGS, lastOpenPath: "/books/last.epub" };
+
+describe("startup book", () => {
+  it("prefers a path from the command line", () => {
+    expect(chooseStartupBook("/books/given.epub", lastOpen, exists)).toEqual({
+      path: "/books/given.epub",
+      source: "cli",
+    });
+  });
+
+  it("falls back to the book open at the last exit", () => {
+    expect(chooseStartupBook(undefined, lastOpen, exists)).toEqual({ path: "/books/last.epub", source: "lastOpen" });
+  });
+
+  it("opens nothing when there is no last book, it is gone, or reopening is off", () => {
+    expect(chooseStartupBook(undefined, DEFAULT_CLI_SETTINGS, exists)).toBeNull();
+    expect(chooseStartupBook(undefined, { ...lastOpen, lastOpenPath: "/books/deleted.epub" }, exists)).toBeNull();
+    expect(chooseStartupBook(undefined, { ...lastOpen, reopenLastBook: false }, exists)).toBeNull();
+  });
+
+  it("keeps the last book and its progress in the settings file across launches", async () => {
+    const path = join(mkdtempSync(join(tmpdir(), "bkai-cli-")), "nested", "reader.json");
+    // Each launch starts from fresh objects reading the same file.
+    const settings = () =>
+      new SettingsStore({ key: CLI_SETTINGS_KEY, defaults: DEFAULT_CLI_SETTINGS, storage: new JsonFileStorage(path) });
+    const store = () =>
+      new InMemoryStateStore({ backend: createLocalStorageBackend({ storage: new JsonFileStorage(path) }) });
+
+    settings().save({ ...DEFAULT_CLI_SETTINGS, lastOpenPath: "/books/last.epub" });
+    await store().saveProgress("book-1", { spineIndex: 4, offset: 2 });
+
+    expect(settings().load().lastOpenPath).toBe("/books/last.epub");
+    expect(await store().loadProgress("book-1")).toEqual({ spineIndex: 4, offset: 2 });
+  });
+});
@@ -0,0 +1,89 @@
+import { AppError } from "@core-platform";
+import type { BookModel } from "@epub-parser";
+import { findSpineIndex } from "./ipc";
+
+/** Where to open the book: a 1-based chapter number or a spine document's href. */
+export type ChapterTarget = { chapter: number } | { href: string };
+
+export interface ReaderArgs {
+  validate: boolean;
+  dumpJson: boolean;
+  /** `--no-text`: leave chapter text out of `--dump-json`. */
+  noText: boolean;
+  ipc: boolean;
+  profile?: string;
+  epubPath?: string;
+  target?: ChapterTarget;
+}
+
+const FLAGS = new Set(["--validate", "--dump-json", "--no-text", "--ipc"]);
+const VALUE_OPTIONS = new Set(["--profile", "--chapter", "--href"]);
+
+function argumentError(message: string): AppError {
+  return new AppError(message, { code: "CLI_BAD_ARGUMENT", source: "cli", userMessage: "命令行参数不正确" });
+}
+
+/** Reads the reader's command line (without the runtime and script); malformed arguments throw. */
+export function parseReaderArgs(args: string[]): ReaderArgs {
+  const flags = new Set<string>();
+  const values = new Map<string, string>();
+  let epubPath: string | undefined;
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index]!;
+    if (VALUE_OPTIONS.has(arg)) {
+      const value = args[index + 1];
+      if (value === undefined || value.startsWith("--")) throw argumentError(`${arg} needs a value`);
+      values.set(arg, value);
+      index += 1;
+    } else if (FLAGS.has(arg)) {
+      flags.add(arg);
+    } else if (arg.startsWith("--")) {
+      throw argumentError(`Unknown option: ${arg}`);
+    } else {
+      epubPath ??= arg;
+    }
+  }
+
+  const chapter = values.get("--chapter");
+  const href = values.get("--href");
+  if (chapter !== undefined && href !== undefined) throw argumentError("Use either --chapter or --href, not both");
+  if (chapter !== undefined && !/^-?\d+$/.test(chapter.trim())) {
+    throw argumentError(`--chapter needs a chapter number, not ${chapter}`);
+  }
+  const target: ChapterTarget | undefined =
+    chapter !== undefined ? { chapter: Number(chapter) } : href !== undefined ? { href } : undefined;
+
+  return {
+    validate: flags.has("--validate"),
+    dumpJson: flags.has("--dump-json"),
+    noText: flags.has("--no-text"),
+    ipc: flags.has("--ipc"),
+    profile: values.get("--profile"),
+    epubPath,
+    target,
+  };
+}
+
+/**
+ * The spine index `target` opens in `book`. Chapter numbers outside the book
+ * are clamped to its first or last chapter; an href no spine document matches
+ * gives null, so the saved position is kept. Either case is reported to `warn`.
+ */
+export function resolveChapterTarget(
+  book: BookModel,
+  target: ChapterTarget,
+  warn: (message: string) => void = message => console.error(message),
+): number | null {
+  const last = book.spine.length - 1;
+  if (last < 0) return null;
+  if ("href" in target) {
+    const index = findSpineIndex(book, target.href);
+    if (index < 0) warn(`No chapter matches --href ${target.href}; opening at the saved position`);
+    return index < 0 ? null : index;
+  }
+  const index = Math.min(Math.max(target.chapter - 1, 0), last);
+  if (index !== target.chapter - 1) {
+    warn(`--chapter ${target.chapter} is outside 1..${last + 1}; opening chapter ${index + 1}`);
+  }
+  return index;
+}
@@ -0,0 +1,171 @@
+import { AppError, isAppError } from "@core-platform";
+import type { BookModel } from "@epub-parser";
+import type { InMemoryStateStore } from "@state-store";
+
+export type IpcCommand =
+  | { cmd: "state" }
+  | { cmd: "next_chapter" }
+  | { cmd: "previous_chapter" }
+  | { cmd: "jump"; index: number }
+  | { cmd: "jump_to_chapter_href"; href: string }
+  | { cmd: "open"; path: string };
+
+export interface ReaderSession {
+  book: BookModel | null;
+  path?: string;
+  chapter: number;
+}
+
+export interface ReaderSnapshot {
+  path: string | null;
+  bookId: string | null;
+  title: string | null;
+  chapter: number;
+  /** Chapters in the reading order; `linear="no"` spine items are not counted. */
+  chapterCount: number;
+}
+
+export type IpcReply = { ok: true; state: ReaderSnapshot } | { ok: false; code: string; error: string };
+
+export interface IpcDependencies {
+  openBook: (path: string) => Promise<BookModel>;
+  /** Progress is saved after every move and restored when a book is opened. */
+  store?: InMemoryStateStore;
+}
+
+function ipcError(code: string, message: string): AppError {
+  return new AppError(message, { code, source: "ipc", userMessage: "无法执行控制命令" });
+}
+
+/** Parses one line of the `--ipc` protocol, e.g. `{"cmd":"jump","index":5}`. */
+export function parseIpcCommand(line: string): IpcCommand {
+  let input: unknown;
+  try {
+    input = JSON.parse(line);
+  } catch {
+    throw ipcError("IPC_MALFORMED", `Not valid JSON: ${line.trim().slice(0, 80)}`);
+  }
+  if (!input || typeof input !== "object" || Array.isArray(input)) {
+    throw ipcError("IPC_MALFORMED", "Commands must be JSON objects");
+  }
+
+  const { cmd, index, path, href } = input as Record<string, unknown>;
+  switch (cmd) {
+    case "state":
+    case "next_chapter":
+    case "previous_chapter":
+      return { cmd };
+    case "jump":
+      if (typeof index !== "number" || !Number.isInteger(index)) {
+        throw ipcError("IPC_BAD_ARGUMENT", '"jump" needs an integer "index"');
+      }
+      return { cmd, index };
+    case "jump_to_chapter_href":
+      if (typeof href !== "string" || !href) {
+        throw ipcError("IPC_BAD_ARGUMENT", '"jump_to_chapter_href" needs an "href"');
+      }
+      return { cmd, href };
+    case "open":
+      if (typeof path !== "string" || !path) {
+        throw ipcError("IPC_BAD_ARGUMENT", '"open" needs a "path"');
+      }
+      return { cmd, path };
+    default:
+      throw ipcError("IPC_UNKNOWN_COMMAND", `Unknown command: ${String(cmd)}`);
+  }
+}
+
+export function snapshotSession(session: ReaderSession): ReaderSnapshot {
+  return {
+    path: session.path ?? null,
+    bookId: session.book?.id ?? null,
+    title: session.book?.metadata.title ?? null,
+    chapter: session.chapter,
+    chapterCount: session.book?.spine.filter(item => item.linear).length ?? 0,
+  };
+}
+
+/** The next spine index in reading order, skipping non-linear items; `chapter` itself at either end. */
+function stepChapter(book: BookModel, chapter: number, direction: 1 | -1): number {
+  for (let next = chapter + direction; next >= 0 && next < book.spine.length; next += direction) {
+    if (book.spine[next]!.linear) return next;
+  }
+  return chapter;
+}
+
+/** Spine index of the document `href` names, as an archive path or relative to the package; -1 if none. */
+export function findSpineIndex(book: BookModel, href: string): number {
+  const path = href.split("#")[0]!.replace(/^\/+/, "");
+  const base = book.resources.basePath.replace(/\/+$/, "");
+  return book.spine.findIndex(item => {
+    const target = book.manifest[item.idref]?.href;
+    return !!target && (target === path || (!!base && target === `${base}/${path}`));
+  });
+}
+
+async function moveTo(session: ReaderSession, chapter: number, deps: IpcDependencies): Promise<ReaderSession> {
+  const book = session.book;
+  if (!book) throw ipcError("IPC_NO_BOOK", "No book is open");
+  if (chapter < 0 || chapter >= book.spine.length) {
+    throw ipcError("IPC_OUT_OF_RANGE", `Chapter ${chapter} is outside 0..${book.spine.length - 1}`);
+  }
+  await deps.store?.saveProgress(book.id, { spineIndex: chapter, offset: 0 });
+  return { ...session, chapter };
+}
+
+/** Applies a command and returns the new session; invalid moves throw without changing it. */
+export async function applyIpcCommand(
+  session: ReaderSession,
+  command: IpcCommand,
+  deps: IpcDependencies,
+): Promise<ReaderSession> {
+  const book = session.book;
+  switch (command.cmd) {
+    case "state":
+      return session;
+    case "next_chapter":
+      return moveTo(session, book ? stepChapter(book, session.chapter, 1) : session.chapter, deps);
+    case "previous_chapter":
+      return moveTo(session, book ? stepChapter(book, session.chapter, -1) : session.chapter, deps);
+    case "jump":
+      return moveTo(session, command.index, deps);
+    case "jump_to_chapter_href": {
+      const index = book ? findSpineIndex(book, command.href) : 0;
+      if (index < 0) throw ipcError("IPC_UNKNOWN_HREF", `No spine document matches ${command.href}`);
+      return moveTo(session, index, deps);
+    }
+    case "open": {
+      const book = await deps.openBook(command.path);
+      const saved = await deps.store?.loadProgress(book.id);
+      const chapter = Math.min(Math.max(saved?.spineIndex ?? 0, 0), Math.max(book.spine.length - 1, 0));
+      return { book, path: command.path, chapter };
+    }
+  }
+}
+
+/**
+ * Reads newline-delimited JSON commands and answers each with one JSON line:
+ * the state snapshot after the command, or an error that leaves it unchanged.
+ */
+export async function runIpc(
+  lines: AsyncIterable<string>,
+  write: (line: string) => void,
+  initial: ReaderSession,
+  deps: IpcDependencies,
+): Promise<ReaderSession> {
+  let session = initial;
+  for await (const line of lines) {
+    if (!line.trim()) continue;
+    let reply: IpcReply;
+    try {
+      session = await applyIpcCommand(session, parseIpcCommand(line), deps);
+      reply = { ok: true, state: snapshotSession(session) };
+    } catch (error) {
+      reply = isAppError(error)
+        ? { ok: false, code: error.code ?? "IPC_FAILED", error: error.message }
+        : { ok: false, code: "IPC_FAILED", error: error instanceof Error ? error.message : String(error) };
+    }
+    write(JSON.stringify(reply));
+  }
+  return session;
+}
@@ -1,36 +1,144 @@
-import { openEpub } from "@epub-parser";
-import { InMemoryStateStore } from "@state-store";
+import { dumpBook, openEpub, validateEpub } from "@epub-parser";
+import {
+  createLocalStorageBackend,
+  InMemoryStateStore,
+  normalizeProfileName,
+  profileKey,
+  SettingsStore,
+  STATE_STORAGE_KEY,
+} from "@state-store";
+import { type ChapterTarget, parseReaderArgs, type ReaderArgs, resolveChapterTarget } from "./args";
+import { applyIpcCommand, runIpc, type ReaderSession } from "./ipc";
+import {
+  chooseStartupBook,
+  CLI_SETTINGS_KEY,
+  DEFAULT_CLI_SETTINGS,
+  defaultSettingsPath,
+  JsonFileStorage,
+} from "./startup";
 
 export interface ReaderBootstrapOptions {
   epubPath: string;
+  /** Reader profile whose progress and settings to use. */
+  profile?: string;
+  /** Progress is restored from it; defaults to an empty in-memory store. */
+  store?: InMemoryStateStore;
+  /** Chapter to open instead of the saved position, e.g. from `--chapter` or `--href`. */
+  target?: ChapterTarget;
 }
 
 export async function bootstrapReader(options: ReaderBootstrapOptions) {
-  const store = new InMemoryStateStore();
+  const store = options.store ?? new InMemoryStateStore();
   const book = await openEpub(options.epubPath);
+  const saved = (await store.loadProgress(book.id)) ?? { spineIndex: 0, offset: 0 };
+  const targetIndex = options.target ? resolveChapterTarget(book, options.target) : null;
+  const progress = targetIndex === null ? saved : { spineIndex: targetIndex, offset: 0 };
 
-  await store.saveProgress(book.id, { spineIndex: 0, offset: 0 });
+  await store.saveProgress(book.id, progress);
 
   return {
     book,
     store,
+    progress,
+    profile: normalizeProfileName(options.profile),
   };
 }
 
-if (import.meta.main) {
-  const epubPath = Bun.argv[2];
-  if (!epubPath) {
-    console.error("Usage: bun apps/reader/src/main.ts <path-to-epub>");
-    process.exit(1);
+function exitWithUsage(): never {
+  console.error(
+    "Usage: bun apps/reader/src/main.ts [--validate | --dump-json [--no-text] | --ipc] [--profile NAME]\n" +
+      "         [--chapter N | --href PATH] [<epub>]\n" +
+      "Without a path, the book open at the last exit is reopened.",
+  );
+  process.exit(1);
+}
+
+function readArgs(): ReaderArgs {
+  try {
+    return parseReaderArgs(Bun.argv.slice(2));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    exitWithUsage();
   }
+}
 
-  bootstrapReader({ epubPath })
-    .then(({ book }) => {
-      console.log(`Loaded book: ${book.metadata.title ?? "Unknown Title"}`);
-      console.log(`Chapters in spine: ${book.spine.length}`);
-    })
-    .catch(error => {
-      console.error("Failed to open EPUB:", error);
-      process.exit(1);
-    });
+if (import.meta.main) {
+  const { validate, dumpJson, noText, ipc, profile, epubPath, target } = readArgs();
+
+  // Settings and progress live in one JSON file, kept apart per profile like the browser's.
+  const profileName = normalizeProfileName(profile);
+  const storage = new JsonFileStorage(defaultSettingsPath());
+  const settings = new SettingsStore({
+    key: profileKey(CLI_SETTINGS_KEY, profileName),
+    defaults: DEFAULT_CLI_SETTINGS,
+    storage,
+  });
+  const store = new InMemoryStateStore({
+    backend: createLocalStorageBackend({ storageKey: profileKey(STATE_STORAGE_KEY, profileName), storage }),
+  });
+  const rememberOpenBook = (path: string | undefined) =>
+    settings.save({ ...settings.load(), lastOpenPath: path ?? "" });
+  // Validating and dumping always need a path; only reading falls back to the last book.
+  const reading = !validate && !dumpJson;
+  const startup = reading ? chooseStartupBook(epubPath, settings.load()) : null;
+  if (startup?.source === "lastOpen") console.error(`Reopening ${startup.path}`);
+  const bookPath = reading ? startup?.path : epubPath;
+
+  if (ipc) {
+    // Commands arrive one JSON object per line on stdin; every reply is one JSON line on stdout.
+    const deps = { openBook: (path: string) => openEpub(path), store };
+    const empty: ReaderSession = { book: null, chapter: 0 };
+    (bookPath ? applyIpcCommand(empty, { cmd: "open", path: bookPath }, deps) : Promise.resolve(empty))
+      .then(session => {
+        const index = session.book && target ? resolveChapterTarget(session.book, target) : null;
+        return index === null ? session : applyIpcCommand(session, { cmd: "jump", index }, deps);
+      })
+      .then(session => runIpc(console, line => console.log(line), session, deps))
+      .then(session => rememberOpenBook(session.path))
+      .catch(error => {
+        console.error("Failed to open EPUB:", error);
+        process.exit(1);
+      });
+  } else if (!bookPath) {
+    exitWithUsage();
+  } else if (dumpJson) {
+    openEpub(bookPath)
+      .then(book => dumpBook(book, { includeText: !noText }))
+      .then(dump => {
+        console.log(JSON.stringify(dump, null, 2));
+      })
+      .catch(error => {
+        console.error("Failed to dump EPUB:", error);
+        process.exit(1);
+      });
+  } else if (validate) {
+    validateEpub(bookPath)
+      .then(report => {
+        if (report.warnings.length === 0) {
+          console.log("No problems found.");
+          return;
+        }
+        console.log(`${report.warnings.length} warning(s):`);
+        for (const warning of report.warnings) {
+          console.log(`  [${warning.code}] ${warning.message}`);
+        }
+      })
+      .catch(error => {
+        console.error("Failed to validate EPUB:", error);
+        process.exit(1);
+      });
+  } else {
+    bootstrapReader({ epubPath: bookPath, profile, store, target })
+      .then(({ book, profile, progress }) => {
+        rememberOpenBook(bookPath);
+        console.log(`Profile: ${profile}`);
+        console.log(`Loaded book: ${book.metadata.title ?? "Unknown Title"}`);
+        console.log(`Chapters in spine: ${book.spine.length}`);
+        console.log(`Resuming at chapter ${progress.spineIndex + 1}`);
+      })
+      .catch(error => {
+        console.error("Failed to open EPUB:", error);
+        process.exit(1);
+      });
+  }
 }
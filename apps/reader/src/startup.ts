@@ -0,0 +1,72 @@
+import { existsSync, mkdirSync, readFileSync, writeFileSync } from "node:fs";
+import { homedir } from "node:os";
+import { dirname, join } from "node:path";
+import type { SettingsStorage } from "@state-store";
+
+export const CLI_SETTINGS_KEY = "bkai.cli";
+
+export interface CliSettings {
+  /** Reopen the book that was open at exit when no path is given. */
+  reopenLastBook: boolean;
+  /** The book open when the reader last exited; empty when none was. */
+  lastOpenPath: string;
+}
+
+export const DEFAULT_CLI_SETTINGS: CliSettings = {
+  reopenLastBook: true,
+  lastOpenPath: "",
+};
+
+export type StartupBook = { path: string; source: "cli" | "lastOpen" } | null;
+
+/**
+ * The book to open at launch: the command-line path whenever one is given,
+ * else the book open at the last exit if it still exists, else none.
+ */
+export function chooseStartupBook(
+  cliPath: string | undefined,
+  settings: CliSettings,
+  exists: (path: string) => boolean = existsSync,
+): StartupBook {
+  if (cliPath) return { path: cliPath, source: "cli" };
+  const last = settings.lastOpenPath;
+  if (settings.reopenLastBook && last && exists(last)) return { path: last, source: "lastOpen" };
+  return null;
+}
+
+/** `$XDG_CONFIG_HOME/bkai/reader.json`, falling back to `~/.config`. */
+export function defaultSettingsPath(): string {
+  return join(process.env.XDG_CONFIG_HOME || join(homedir(), ".config"), "bkai", "reader.json");
+}
+
+/**
+ * `SettingsStorage` over one JSON file holding every key, so the CLI can use
+ * the same settings and progress stores as the browser. Writes go straight to
+ * disk; an unreadable file reads as empty.
+ */
+export class JsonFileStorage implements SettingsStorage {
+  private readonly path: string;
+
+  constructor(path: string) {
+    this.path = path;
+  }
+
+  getItem(key: string): string | null {
+    return this.read()[key] ?? null;
+  }
+
+  setItem(key: string, value: string): void {
+    const items = { ...this.read(), [key]: value };
+    mkdirSync(dirname(this.path), { recursive: true });
+    writeFileSync(this.path, JSON.stringify(items, null, 2));
+  }
+
+  private read(): Record<string, string> {
+    try {
+      const parsed: unknown = JSON.parse(readFileSync(this.path, "utf8"));
+      return parsed && typeof parsed === "object" && !Array.isArray(parsed) ? (parsed as Record<string, string>) : {};
+    } catch {
+      return {};
+    }
+  }
+}
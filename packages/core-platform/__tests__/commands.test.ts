@@ -0,0 +1,48 @@
+import { describe, expect, it } from "bun:test";
+import { CommandRegistry, filterCommands, fuzzyScore, type Command } from "@core-platform";
+
+const command = (id: string, title: string, keywords?: string[]): Command => ({ id, title, keywords, run: () => {} });
+
+describe("fuzzyScore", () => {
+  it("matches characters in order and rejects anything else", () => {
+    expect(fuzzyScore("gtc", "Go To Chapter")).not.toBeNull();
+    expect(fuzzyScore("ctg", "Go To Chapter")).toBeNull();
+  });
+});
+
+describe("filterCommands", () => {
+  it("ranks word-start matches first", () => {
+    const commands = [
+      command("theme", "Toggle Theme Colors"),
+      command("chapter", "Go To Chapter"),
+      command("export", "Export EPUB"),
+    ];
+
+    expect(filterCommands(commands, "gtc").map(item => item.id)).toEqual(["chapter", "theme"]);
+  });
+
+  it("matches keywords as well as titles", () => {
+    const commands = [command("search", "搜索全文", ["search"]), command("export", "导出精简 EPUB", ["export"])];
+
+    expect(filterCommands(commands, "srch").map(item => item.id)).toEqual(["search"]);
+    expect(filterCommands(commands, "导出").map(item => item.id)).toEqual(["export"]);
+  });
+
+  it("keeps registration order for an empty query", () => {
+    const commands = [command("b", "Beta"), command("a", "Alpha")];
+    expect(filterCommands(commands, " ")).toBe(commands);
+  });
+});
+
+describe("CommandRegistry", () => {
+  it("runs registered commands and forgets them once disposed", async () => {
+    const registry = new CommandRegistry();
+    let runs = 0;
+    const dispose = registry.register({ id: "count", title: "Count", run: () => void runs++ });
+
+    expect(await registry.run("count")).toBe(true);
+    dispose();
+    expect(await registry.run("count")).toBe(false);
+    expect(runs).toBe(1);
+  });
+});
@@ -0,0 +1,48 @@
+import { describe, expect, it } from "bun:test";
+import { classifyClipboardText, parentDirectory, siblingBookPath } from "@core-platform";
+
+const folder = [
+  "Shelf/Series 10.epub",
+  "Shelf/notes.txt",
+  "Shelf/Series 2.epub",
+  "Shelf/Series 1.epub",
+  "Shelf/Extras/Series 3.epub",
+];
+
+describe("sibling books", () => {
+  it("steps through the epubs of the same directory in name order", () => {
+    expect(siblingBookPath(folder, "Shelf/Series 1.epub", 1)).toBe("Shelf/Series 2.epub");
+    expect(siblingBookPath(folder, "Shelf/Series 2.epub", 1)).toBe("Shelf/Series 10.epub");
+    expect(siblingBookPath(folder, "Shelf/Series 10.epub", -2)).toBe("Shelf/Series 1.epub");
+  });
+
+  it("returns null past the first or last book and for unknown files", () => {
+    expect(siblingBookPath(folder, "Shelf/Series 1.epub", -1)).toBeNull();
+    expect(siblingBookPath(folder, "Shelf/Series 10.epub", 1)).toBeNull();
+    expect(siblingBookPath(folder, "Shelf/Extras/Series 3.epub", 1)).toBeNull();
+    expect(siblingBookPath(folder, "Elsewhere.epub", 1)).toBeNull();
+    expect(parentDirectory("Book.epub")).toBe("");
+  });
+});
+
+describe("clipboard book locations", () => {
+  it("recognizes web links and local epub paths", () => {
+    expect(classifyClipboardText(" https://example.com/books/Moby%20Dick.epub\n")).toEqual({
+      kind: "url",
+      url: "https://example.com/books/Moby%20Dick.epub",
+    });
+    expect(classifyClipboardText('"/home/me/Dune.EPUB"')).toEqual({ kind: "path", path: "/home/me/Dune.EPUB" });
+    expect(classifyClipboardText("C:\\Books\\Dune.epub")).toEqual({ kind: "path", path: "C:\\Books\\Dune.epub" });
+    expect(classifyClipboardText("file:///C:/Books/%E4%B8%89%E4%BD%93.epub")).toEqual({
+      kind: "path",
+      path: "C:/Books/三体.epub",
+    });
+  });
+
+  it("ignores text that is not a book location", () => {
+    const garbage = ["", "hello world", "notes.txt", "ftp://example.com/a.epub", "file:///tmp/a.pdf", "a.epub\nb.epub"];
+    for (const text of garbage) {
+      expect(classifyClipboardText(text)).toEqual({ kind: "none" });
+    }
+  });
+});
@@ -0,0 +1,49 @@
+import { describe, expect, it } from "bun:test";
+import { createKeyMap, matchKeyAction, parseKeyBinding } from "@core-platform";
+
+const press = (key: string, modifiers: { shift?: boolean; ctrl?: boolean } = {}) => ({
+  key,
+  shiftKey: modifiers.shift ?? false,
+  ctrlKey: modifiers.ctrl ?? false,
+  altKey: false,
+  metaKey: false,
+});
+
+describe("createKeyMap", () => {
+  it("uses custom bindings and keeps defaults for unspecified actions", () => {
+    const { keyMap, warnings } = createKeyMap({ previousChapter: "h", nextChapter: ["l", "Ctrl+n"] });
+
+    expect(warnings).toEqual([]);
+    expect(keyMap.previousChapter).toEqual([{ key: "h", shift: false, ctrl: false, alt: false, meta: false }]);
+    expect(keyMap.nextChapter).toEqual([
+      { key: "l", shift: false, ctrl: false, alt: false, meta: false },
+      { key: "n", shift: false, ctrl: true, alt: false, meta: false },
+    ]);
+    expect(keyMap.pageUp).toEqual([{ key: " ", shift: true, ctrl: false, alt: false, meta: false }]);
+
+    expect(matchKeyAction(keyMap, press("h"))).toBe("previousChapter");
+    expect(matchKeyAction(keyMap, press("n", { ctrl: true }))).toBe("nextChapter");
+    expect(matchKeyAction(keyMap, press("ArrowLeft"))).toBeNull();
+    expect(matchKeyAction(keyMap, press(" ", { shift: true }))).toBe("pageUp");
+  });
+
+  it("warns about invalid keys and unknown actions", () => {
+    const { keyMap, warnings } = createKeyMap({ nextChapter: "Hyper+ll", jump: "j" });
+
+    expect(parseKeyBinding("Shift+")).toBeNull();
+    expect(warnings).toEqual([
+      'Invalid key "Hyper+ll" for action "nextChapter"',
+      'Unknown key map action "jump"',
+    ]);
+    expect(keyMap.nextChapter[0]?.key).toBe("ArrowRight");
+  });
+
+  it("binds the auto-scroll speed keys with and without Shift", () => {
+    const { keyMap } = createKeyMap();
+
+    expect(parseKeyBinding("Shift+Plus")).toEqual({ key: "+", shift: true, ctrl: false, alt: false, meta: false });
+    expect(matchKeyAction(keyMap, press("+", { shift: true }))).toBe("autoScrollFaster");
+    expect(matchKeyAction(keyMap, press("+"))).toBe("autoScrollFaster");
+    expect(matchKeyAction(keyMap, press("-"))).toBe("autoScrollSlower");
+  });
+});
@@ -0,0 +1,43 @@
+import { describe, expect, it } from "bun:test";
+import { joinTextRuns, NARROW_NO_BREAK_SPACE, spaceBetween, spacingRulesFor } from "@core-platform";
+
+const smart = spacingRulesFor("smart", "en");
+const french = spacingRulesFor("smart", "fr-CA");
+
+describe("spacing between text runs", () => {
+  it("keeps the source's space between words and adds none where there was none", () => {
+    expect(joinTextRuns("one", "two", true, smart)).toBe("one two");
+    expect(joinTextRuns("one", "two", false, smart)).toBe("onetwo");
+  });
+
+  it("drops spaces around em dashes", () => {
+    expect(joinTextRuns("wait", "—no", true, smart)).toBe("wait—no");
+    expect(joinTextRuns("wait—", "no", true, smart)).toBe("wait—no");
+  });
+
+  it("drops spaces inside opening and closing quotes and brackets", () => {
+    expect(joinTextRuns("said “", "Hello", true, smart)).toBe("said “Hello");
+    expect(joinTextRuns("Hello", "” she said", true, smart)).toBe("Hello” she said");
+    expect(joinTextRuns("(", "see", true, smart)).toBe("(see");
+    expect(joinTextRuns("below", ")", true, smart)).toBe("below)");
+  });
+
+  it("drops spaces before closing punctuation outside French", () => {
+    expect(joinTextRuns("Chapter", ": Start", true, smart)).toBe("Chapter: Start");
+    expect(joinTextRuns("Stop", "!", true, smart)).toBe("Stop!");
+  });
+
+  it("puts a narrow no-break space before high punctuation and inside guillemets in French", () => {
+    expect(spaceBetween("Attention", ":", false, french)).toBe(NARROW_NO_BREAK_SPACE);
+    expect(joinTextRuns("Bonjour", "!", true, french)).toBe(`Bonjour${NARROW_NO_BREAK_SPACE}!`);
+    expect(joinTextRuns("«", "Oui", true, french)).toBe(`«${NARROW_NO_BREAK_SPACE}Oui`);
+    expect(joinTextRuns("Oui", "»", false, french)).toBe(`Oui${NARROW_NO_BREAK_SPACE}»`);
+    expect(joinTextRuns("Fin", ".", true, french)).toBe("Fin.");
+  });
+
+  it("follows the source exactly at the source level", () => {
+    const source = spacingRulesFor("source", "fr");
+    expect(joinTextRuns("Bonjour", "!", true, source)).toBe("Bonjour !");
+    expect(joinTextRuns("wait", "—no", false, source)).toBe("wait—no");
+  });
+});
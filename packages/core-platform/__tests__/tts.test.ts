@@ -0,0 +1,70 @@
+import { describe, expect, it } from "bun:test";
+import { ReadAloudController, type ReadAloudPosition, type TtsEngine } from "@core-platform";
+
+class MockEngine implements TtsEngine {
+  spoken: string[] = [];
+  stopped = 0;
+  private pending: Array<() => void> = [];
+
+  speak(text: string) {
+    this.spoken.push(text);
+    return new Promise<void>(resolve => this.pending.push(resolve));
+  }
+
+  pause() {}
+  resume() {}
+
+  stop() {
+    this.stopped += 1;
+    this.finishCurrent();
+  }
+
+  finishCurrent() {
+    this.pending.shift()?.();
+  }
+}
+
+const source = {
+  chapterCount: 2,
+  paragraphs: (chapterIndex: number) => (chapterIndex === 0 ? ["One.", "Two."] : ["Three."]),
+};
+
+const tick = () => new Promise(resolve => setTimeout(resolve, 0));
+
+describe("ReadAloudController", () => {
+  it("reads paragraphs in order and continues into the next chapter", async () => {
+    const engine = new MockEngine();
+    const positions: Array<ReadAloudPosition | null> = [];
+    const controller = new ReadAloudController(engine, source, { onPosition: position => positions.push(position) });
+
+    const done = controller.start(0);
+    for (let i = 0; i < 3; i += 1) {
+      await tick();
+      engine.finishCurrent();
+    }
+    await done;
+
+    expect(engine.spoken).toEqual(["One.", "Two.", "Three."]);
+    expect(positions.map(position => position && [position.chapterIndex, position.paragraphIndex])).toEqual([
+      [0, 0],
+      [0, 1],
+      [1, 0],
+      null,
+    ]);
+    expect(controller.state).toBe("idle");
+  });
+
+  it("stops immediately without speaking further paragraphs", async () => {
+    const engine = new MockEngine();
+    const controller = new ReadAloudController(engine, source);
+
+    const done = controller.start(0);
+    await tick();
+    controller.stop();
+    await done;
+
+    expect(engine.spoken).toEqual(["One."]);
+    expect(engine.stopped).toBe(1);
+    expect(controller.state).toBe("idle");
+  });
+});
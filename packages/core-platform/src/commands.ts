@@ -0,0 +1,87 @@
+export interface Command {
+  /** Stable id; registering the same id again replaces the earlier command. */
+  id: string;
+  title: string;
+  /** Extra words the palette should match, e.g. English names for a Chinese title. */
+  keywords?: string[];
+  run: () => void | Promise<void>;
+}
+
+/**
+ * Named commands the palette can run. Features register their own commands and
+ * drop them with the returned disposer, so the palette never needs to know
+ * about them.
+ */
+export class CommandRegistry {
+  private readonly commands = new Map<string, Command>();
+
+  register(...commands: Command[]): () => void {
+    for (const command of commands) {
+      this.commands.set(command.id, command);
+    }
+    return () => {
+      for (const command of commands) {
+        if (this.commands.get(command.id) === command) {
+          this.commands.delete(command.id);
+        }
+      }
+    };
+  }
+
+  list(): Command[] {
+    return [...this.commands.values()];
+  }
+
+  async run(id: string): Promise<boolean> {
+    const command = this.commands.get(id);
+    if (!command) return false;
+    await command.run();
+    return true;
+  }
+}
+
+const WORD_BOUNDARY = /[\s\-_:：/·]/;
+
+/**
+ * Scores `text` against a fuzzy `query` whose characters must appear in order.
+ * Matches at word starts and runs of consecutive characters score higher, so
+ * "gtc" ranks "Go To Chapter" above "Toggle Theme Colors". Returns null when
+ * the query does not match.
+ */
+export function fuzzyScore(query: string, text: string): number | null {
+  const needle = [...query.toLowerCase().replace(/\s+/g, "")];
+  const haystack = [...text.toLowerCase()];
+  if (needle.length === 0) return 0;
+
+  let score = 0;
+  let searchFrom = 0;
+  let previousMatch = -2;
+  for (const char of needle) {
+    const index = haystack.indexOf(char, searchFrom);
+    if (index < 0) return null;
+    const atWordStart = index === 0 || WORD_BOUNDARY.test(haystack[index - 1]!);
+    score += 1;
+    if (atWordStart) score += 8;
+    if (index === previousMatch + 1) score += 4;
+    // Characters skipped over cost a little, so tighter matches win ties.
+    score -= Math.min(index - searchFrom, 4) * 0.25;
+    previousMatch = index;
+    searchFrom = index + 1;
+  }
+  return score;
+}
+
+/** Commands matching `query`, best first; an empty query keeps registration order. */
+export function filterCommands(commands: Command[], query: string): Command[] {
+  if (!query.trim()) return commands;
+  return commands
+    .map((command, order) => {
+      const scores = [command.title, ...(command.keywords ?? [])]
+        .map(text => fuzzyScore(query, text))
+        .filter((value): value is number => value !== null);
+      return { command, order, score: scores.length > 0 ? Math.max(...scores) : null };
+    })
+    .filter((entry): entry is { command: Command; order: number; score: number } => entry.score !== null)
+    .sort((a, b) => b.score - a.score || a.order - b.order)
+    .map(entry => entry.command);
+}
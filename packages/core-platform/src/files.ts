@@ -0,0 +1,56 @@
+const BOOK_EXTENSION = /\.epub$/i;
+const pathCollator = new Intl.Collator(undefined, { numeric: true });
+
+/** Orders file paths the way a file manager does, so "Book 2" sorts before "Book 10". */
+export function comparePaths(a: string, b: string): number {
+  return pathCollator.compare(a, b);
+}
+
+/** Directory part of a `/`-separated path; "" for a bare file name. */
+export function parentDirectory(path: string): string {
+  const slash = path.lastIndexOf("/");
+  return slash === -1 ? "" : path.slice(0, slash);
+}
+
+/**
+ * The book `delta` steps away from `current` among the `.epub` files in the
+ * same directory, in name order. Null when `current` is not listed or the step
+ * would go past the first or last book.
+ */
+export function siblingBookPath(paths: string[], current: string, delta: number): string | null {
+  const directory = parentDirectory(current);
+  const books = paths
+    .filter(path => BOOK_EXTENSION.test(path) && parentDirectory(path) === directory)
+    .sort(comparePaths);
+  const index = books.indexOf(current);
+  if (index === -1) return null;
+  return books[index + delta] ?? null;
+}
+
+export type ClipboardBookLocation =
+  | { kind: "url"; url: string }
+  | { kind: "path"; path: string }
+  | { kind: "none" };
+
+/**
+ * Decides whether pasted text points at a book: an `http(s)` URL, or a local
+ * `.epub` path given directly or as a `file:` URL. Surrounding quotes, as
+ * added by "copy as path" in file managers, are ignored.
+ */
+export function classifyClipboardText(text: string): ClipboardBookLocation {
+  const value = text.trim().replace(/^(["'])(.*)\1$/s, "$2").trim();
+  if (!value || /[\r\n]/.test(value)) return { kind: "none" };
+
+  if (!/^[a-z][a-z\d+.-]+:\/\//i.test(value)) {
+    return BOOK_EXTENSION.test(value) ? { kind: "path", path: value } : { kind: "none" };
+  }
+  try {
+    const url = new URL(value);
+    if (url.protocol === "http:" || url.protocol === "https:") return { kind: "url", url: url.href };
+    // `file:///C:/Books/a.epub` becomes `C:/Books/a.epub` rather than `/C:/Books/a.epub`.
+    const path = decodeURIComponent(url.pathname).replace(/^\/([a-z]:\/)/i, "$1");
+    return url.protocol === "file:" && BOOK_EXTENSION.test(path) ? { kind: "path", path } : { kind: "none" };
+  } catch {
+    return { kind: "none" };
+  }
+}
@@ -1,2 +1,7 @@
+export * from "./commands";
 export * from "./errors";
 export * from "./events";
+export * from "./files";
+export * from "./keymap";
+export * from "./spacing";
+export * from "./tts";
@@ -0,0 +1,202 @@
+export type KeyAction =
+  | "previousChapter"
+  | "nextChapter"
+  | "scrollUp"
+  | "scrollDown"
+  | "pageUp"
+  | "pageDown"
+  | "togglePlainText"
+  | "copyBibtex"
+  | "toggleSpeech"
+  | "copyChapterHtml"
+  | "toggleAutoScroll"
+  | "autoScrollFaster"
+  | "autoScrollSlower"
+  | "nextHeading"
+  | "previousHeading"
+  | "openCommandPalette"
+  | "toggleDebugOutlines"
+  | "reloadBook"
+  | "copyQuote"
+  | "nextBook"
+  | "previousBook"
+  | "pasteOpen"
+  | "focusNextLink"
+  | "focusPreviousLink"
+  | "clipSelection";
+
+export interface KeyBinding {
+  key: string;
+  shift: boolean;
+  ctrl: boolean;
+  alt: boolean;
+  meta: boolean;
+}
+
+export type KeyMap = Record<KeyAction, KeyBinding[]>;
+
+/** User-facing config: each action maps to one key string or a list, e.g. `"Shift+Space"`. */
+export type KeyMapConfig = Partial<Record<string, string | string[]>>;
+
+export interface KeyEventLike {
+  key: string;
+  shiftKey: boolean;
+  ctrlKey: boolean;
+  altKey: boolean;
+  metaKey: boolean;
+}
+
+export const KEY_ACTIONS: KeyAction[] = [
+  "previousChapter",
+  "nextChapter",
+  "scrollUp",
+  "scrollDown",
+  "pageUp",
+  "pageDown",
+  "togglePlainText",
+  "copyBibtex",
+  "toggleSpeech",
+  "copyChapterHtml",
+  "toggleAutoScroll",
+  "autoScrollFaster",
+  "autoScrollSlower",
+  "nextHeading",
+  "previousHeading",
+  "openCommandPalette",
+  "toggleDebugOutlines",
+  "reloadBook",
+  "copyQuote",
+  "nextBook",
+  "previousBook",
+  "pasteOpen",
+  "focusNextLink",
+  "focusPreviousLink",
+  "clipSelection",
+];
+
+export const DEFAULT_KEY_MAP_CONFIG: Record<KeyAction, string[]> = {
+  previousChapter: ["ArrowLeft"],
+  nextChapter: ["ArrowRight"],
+  scrollUp: ["ArrowUp"],
+  scrollDown: ["ArrowDown"],
+  pageUp: ["Shift+Space"],
+  pageDown: ["Space"],
+  togglePlainText: ["Ctrl+Shift+P"],
+  copyBibtex: ["Ctrl+Shift+B"],
+  toggleSpeech: ["Ctrl+Shift+S"],
+  copyChapterHtml: ["Ctrl+Shift+H"],
+  toggleAutoScroll: ["Ctrl+Shift+A"],
+  autoScrollFaster: ["Plus", "Shift+Plus", "="],
+  autoScrollSlower: ["Minus"],
+  nextHeading: ["n"],
+  previousHeading: ["p"],
+  openCommandPalette: ["Ctrl+P"],
+  toggleDebugOutlines: ["Ctrl+Alt+D"],
+  reloadBook: ["Ctrl+R"],
+  copyQuote: ["Ctrl+Shift+X"],
+  nextBook: ["Alt+PageDown"],
+  previousBook: ["Alt+PageUp"],
+  pasteOpen: ["Ctrl+Shift+V"],
+  focusNextLink: ["Tab"],
+  focusPreviousLink: ["Shift+Tab"],
+  clipSelection: ["Ctrl+Shift+K"],
+};
+
+const MODIFIERS = new Set(["shift", "ctrl", "alt", "meta"]);
+const NAMED_KEYS: Record<string, string> = {
+  space: " ",
+  left: "ArrowLeft",
+  right: "ArrowRight",
+  up: "ArrowUp",
+  down: "ArrowDown",
+  arrowleft: "ArrowLeft",
+  arrowright: "ArrowRight",
+  arrowup: "ArrowUp",
+  arrowdown: "ArrowDown",
+  pageup: "PageUp",
+  pagedown: "PageDown",
+  home: "Home",
+  end: "End",
+  enter: "Enter",
+  escape: "Escape",
+  esc: "Escape",
+  tab: "Tab",
+  backspace: "Backspace",
+  plus: "+",
+  minus: "-",
+};
+
+/** Parses `"Ctrl+Shift+K"`-style strings; returns null for anything that is not a single key. */
+export function parseKeyBinding(input: string): KeyBinding | null {
+  const parts = input.split("+").map(part => part.trim());
+  const binding: KeyBinding = { key: "", shift: false, ctrl: false, alt: false, meta: false };
+
+  for (const [index, part] of parts.entries()) {
+    const lower = part.toLowerCase();
+    if (index < parts.length - 1) {
+      if (!MODIFIERS.has(lower)) return null;
+      binding[lower as "shift" | "ctrl" | "alt" | "meta"] = true;
+      continue;
+    }
+
+    if (NAMED_KEYS[lower]) {
+      binding.key = NAMED_KEYS[lower];
+    } else if ([...part].length === 1) {
+      binding.key = part.toLowerCase();
+    } else if (/^F([1-9]|1[0-2])$/i.test(part)) {
+      binding.key = part.toUpperCase();
+    } else {
+      return null;
+    }
+  }
+
+  return binding.key ? binding : null;
+}
+
+/**
+ * Builds a key map from user config. Unspecified actions keep their defaults;
+ * unknown actions and unparseable key strings are reported as warnings and skipped.
+ */
+export function createKeyMap(config: KeyMapConfig = {}): { keyMap: KeyMap; warnings: string[] } {
+  const warnings: string[] = [];
+  const keyMap = {} as KeyMap;
+
+  for (const action of KEY_ACTIONS) {
+    const entry = config[action];
+    const keys = entry === undefined ? DEFAULT_KEY_MAP_CONFIG[action] : Array.isArray(entry) ? entry : [entry];
+    const bindings: KeyBinding[] = [];
+    for (const key of keys) {
+      const binding = typeof key === "string" ? parseKeyBinding(key) : null;
+      if (binding) {
+        bindings.push(binding);
+      } else {
+        warnings.push(`Invalid key "${String(key)}" for action "${action}"`);
+      }
+    }
+    keyMap[action] = bindings.length > 0 ? bindings : DEFAULT_KEY_MAP_CONFIG[action].map(key => parseKeyBinding(key)!);
+  }
+
+  for (const action of Object.keys(config)) {
+    if (!KEY_ACTIONS.includes(action as KeyAction)) {
+      warnings.push(`Unknown key map action "${action}"`);
+    }
+  }
+
+  return { keyMap, warnings };
+}
+
+export function matchKeyAction(keyMap: KeyMap, event: KeyEventLike): KeyAction | null {
+  const key = event.key.length === 1 ? event.key.toLowerCase() : event.key;
+  for (const action of KEY_ACTIONS) {
+    const matched = keyMap[action].some(
+      binding =>
+        binding.key === key &&
+        binding.shift === event.shiftKey &&
+        binding.ctrl === event.ctrlKey &&
+        binding.alt === event.altKey &&
+        binding.meta === event.metaKey,
+    );
+    if (matched) return action;
+  }
+  return null;
+}
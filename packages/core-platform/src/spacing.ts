@@ -0,0 +1,71 @@
+/**
+ * How the space between two runs of text is chosen where markup split them,
+ * e.g. `Chapter <em>One</em>: Start` once the `<em>` is gone:
+ * - `source`: a space wherever the source had whitespace at the seam.
+ * - `smart`: the same, minus spaces before closing punctuation, inside
+ *   brackets and quotes and around em dashes; French text gets a narrow
+ *   no-break space before `: ; ! ?` and inside « ».
+ */
+export type SpacingLevel = "source" | "smart";
+
+export interface SpacingRules {
+  /** No space before `, . ; : ! ?` and the like. */
+  punctuation: boolean;
+  /** No space just inside `( [ {`. */
+  brackets: boolean;
+  /** No space just inside curly quotes. */
+  quotes: boolean;
+  /** No space around em dashes. */
+  dashes: boolean;
+  /** French typography; takes precedence over `punctuation` and `quotes`. */
+  french: boolean;
+}
+
+export const NARROW_NO_BREAK_SPACE = "\u202f";
+
+const CLOSING_PUNCTUATION = new Set([",", ".", ";", ":", "!", "?", "\u2026", "\u3001", "\u3002", "\uff0c"]);
+const FRENCH_SPACED_PUNCTUATION = new Set([";", ":", "!", "?"]);
+const OPENING_BRACKETS = new Set(["(", "[", "{"]);
+const CLOSING_BRACKETS = new Set([")", "]", "}"]);
+// “ ‘ « ‹ „ and ” ’ » ›
+const OPENING_QUOTES = new Set(["\u201c", "\u2018", "\u00ab", "\u2039", "\u201e"]);
+const CLOSING_QUOTES = new Set(["\u201d", "\u2019", "\u00bb", "\u203a"]);
+const OPENING_GUILLEMET = "\u00ab";
+const CLOSING_GUILLEMET = "\u00bb";
+const EM_DASH = "\u2014";
+
+/** The rules for `level` in text of `language` (a BCP 47 tag); French rules apply to any `fr` tag. */
+export function spacingRulesFor(level: SpacingLevel, language?: string): SpacingRules {
+  const smart = level === "smart";
+  return {
+    punctuation: smart,
+    brackets: smart,
+    quotes: smart,
+    dashes: smart,
+    french: smart && /^fr(?:-|$)/i.test(language ?? ""),
+  };
+}
+
+/**
+ * What goes between `before` and `after` where they meet: "", " " or a narrow
+ * no-break space. `spaced` tells whether the source had whitespace there;
+ * both runs are expected to be trimmed at the seam.
+ */
+export function spaceBetween(before: string, after: string, spaced: boolean, rules: SpacingRules): string {
+  const last = before.at(-1);
+  const first = after[0];
+  if (!last || !first) return "";
+  const frenchSeam =
+    FRENCH_SPACED_PUNCTUATION.has(first) || first === CLOSING_GUILLEMET || last === OPENING_GUILLEMET;
+  if (rules.french && frenchSeam) return NARROW_NO_BREAK_SPACE;
+  if (rules.punctuation && CLOSING_PUNCTUATION.has(first)) return "";
+  if (rules.brackets && (OPENING_BRACKETS.has(last) || CLOSING_BRACKETS.has(first))) return "";
+  if (rules.quotes && (OPENING_QUOTES.has(last) || CLOSING_QUOTES.has(first))) return "";
+  if (rules.dashes && (last === EM_DASH || first === EM_DASH)) return "";
+  return spaced ? " " : "";
+}
+
+/** `before` and `after` joined by `spaceBetween`. */
+export function joinTextRuns(before: string, after: string, spaced: boolean, rules: SpacingRules): string {
+  return `${before}${spaceBetween(before, after, spaced, rules)}${after}`;
+}
@@ -0,0 +1,115 @@
+export interface TtsEngine {
+  /** Resolves once the text has been spoken or speech was stopped. */
+  speak(text: string): Promise<void>;
+  pause(): void;
+  resume(): void;
+  stop(): void;
+}
+
+export interface ReadAloudSource {
+  chapterCount: number;
+  paragraphs(chapterIndex: number): string[];
+}
+
+export interface ReadAloudPosition {
+  chapterIndex: number;
+  paragraphIndex: number;
+  text: string;
+}
+
+export type ReadAloudState = "idle" | "speaking" | "paused";
+
+export interface ReadAloudCallbacks {
+  /** Called before each paragraph is spoken, and with null once reading ends or stops. */
+  onPosition?: (position: ReadAloudPosition | null) => void;
+  onStateChange?: (state: ReadAloudState) => void;
+}
+
+/**
+ * Reads a book paragraph by paragraph through a pluggable engine, moving on to
+ * the next chapter when one is finished.
+ */
+export class ReadAloudController {
+  private runId = 0;
+  private currentState: ReadAloudState = "idle";
+
+  constructor(
+    private readonly engine: TtsEngine,
+    private readonly source: ReadAloudSource,
+    private readonly callbacks: ReadAloudCallbacks = {},
+  ) {}
+
+  get state(): ReadAloudState {
+    return this.currentState;
+  }
+
+  async start(chapterIndex: number, paragraphIndex = 0) {
+    this.stop();
+    const runId = ++this.runId;
+    this.setState("speaking");
+
+    for (let chapter = chapterIndex; chapter < this.source.chapterCount; chapter += 1) {
+      const paragraphs = this.source.paragraphs(chapter);
+      for (let paragraph = chapter === chapterIndex ? paragraphIndex : 0; paragraph < paragraphs.length; paragraph += 1) {
+        const text = paragraphs[paragraph]!;
+        this.callbacks.onPosition?.({ chapterIndex: chapter, paragraphIndex: paragraph, text });
+        await this.engine.speak(text);
+        if (runId !== this.runId) return;
+      }
+    }
+
+    this.finish();
+  }
+
+  pause() {
+    if (this.currentState !== "speaking") return;
+    this.engine.pause();
+    this.setState("paused");
+  }
+
+  resume() {
+    if (this.currentState !== "paused") return;
+    this.engine.resume();
+    this.setState("speaking");
+  }
+
+  stop() {
+    if (this.currentState === "idle") return;
+    this.runId += 1;
+    this.engine.stop();
+    this.finish();
+  }
+
+  private finish() {
+    this.callbacks.onPosition?.(null);
+    this.setState("idle");
+  }
+
+  private setState(state: ReadAloudState) {
+    if (this.currentState === state) return;
+    this.currentState = state;
+    this.callbacks.onStateChange?.(state);
+  }
+}
+
+/** Engine backed by the browser's Web Speech API; null where it is unavailable. */
+export function createWebSpeechEngine(options: { lang?: string } = {}): TtsEngine | null {
+  if (typeof speechSynthesis === "undefined" || typeof SpeechSynthesisUtterance === "undefined") {
+    return null;
+  }
+
+  return {
+    speak(text) {
+      return new Promise(resolve => {
+        const utterance = new SpeechSynthesisUtterance(text);
+        if (options.lang) utterance.lang = options.lang;
+        utterance.onend = () => resolve();
+        utterance.onerror = () => resolve();
+        speechSynthesis.speak(utterance);
+      });
+    },
+    pause: () => speechSynthesis.pause(),
+    resume: () => speechSynthesis.resume(),
+    stop: () => speechSynthesis.cancel(),
+  };
+}
@@ -0,0 +1,34 @@
+import { describe, expect, it } from "bun:test";
+import { splitAuthors } from "@epub-parser";
+
+describe("splitAuthors", () => {
+  it("splits packed creators and normalizes Last, First order", () => {
+    expect(splitAuthors(["Doe, Jane; Smith, John"], { normalizeAuthorOrder: true })).toEqual([
+      "Jane Doe",
+      "John Smith",
+    ]);
+  });
+
+  it("keeps the original order unless asked to normalize", () => {
+    expect(splitAuthors(["Doe, Jane; Smith, John"])).toEqual(["Doe, Jane", "Smith, John"]);
+  });
+
+  it("splits ampersands and dedupes case-insensitively across creators", () => {
+    expect(splitAuthors(["Jane Doe & John Smith", "jane doe", "张三、李四"])).toEqual([
+      "Jane Doe",
+      "John Smith",
+      "张三",
+      "李四",
+    ]);
+  });
+
+  it("splits on conjunctions only between full names", () => {
+    expect(splitAuthors(["Jane Doe and John Smith", "Marie Curie et Pierre Curie"])).toEqual([
+      "Jane Doe",
+      "John Smith",
+      "Marie Curie",
+      "Pierre Curie",
+    ]);
+    expect(splitAuthors(["Smith et al.", "Simon and Schuster"])).toEqual(["Smith et al.", "Simon and Schuster"]);
+  });
+});
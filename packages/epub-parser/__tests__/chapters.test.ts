@@ -0,0 +1,44 @@
+import { describe, expect, it } from "bun:test";
+import { listNoteElements, loadSpineChapters, openEpub } from "@epub-parser";
+
+const fixturePath = new URL("./fixtures/missing-chapter.epub", import.meta.url).pathname;
+
+describe("loadSpineChapters", () => {
+  it("keeps a placeholder for spine items missing from the manifest or archive", async () => {
+    const book = await openEpub(fixturePath);
+    const { chapters, missingIdrefs } = await loadSpineChapters(book);
+
+    expect(chapters.map(chapter => [chapter.index, chapter.idref, chapter.missing ?? false])).toEqual([
+      [0, "c1", false],
+      [1, "ghost", true],
+      [2, "c3", true],
+      [3, "c4", false],
+    ]);
+    expect(missingIdrefs).toEqual(["ghost", "c3"]);
+    expect(chapters[1]?.content).toContain("（缺失：ghost）");
+    // The placeholder keeps the manifest href so TOC links to the missing file still resolve.
+    expect(chapters[2]?.href).toBe("OEBPS/c3.xhtml");
+    expect(chapters[3]?.content).toContain("<h1>Four</h1>");
+  });
+});
+
+describe("chapter semantic types", () => {
+  it("reads the epub:type of each chapter root and classifies its matter", async () => {
+    const book = await openEpub(new URL("./fixtures/semantic.epub", import.meta.url).pathname);
+    const { chapters } = await loadSpineChapters(book);
+
+    expect(chapters.map(chapter => [chapter.idref, chapter.semanticType, chapter.matter])).toEqual([
+      ["dedication", "frontmatter dedication", "front"],
+      ["c1", "chapter", "body"],
+      ["appendix", "backmatter appendix", "back"],
+      ["plain", undefined, undefined],
+    ]);
+  });
+
+  it("lists the note elements a chapter's note references point to", async () => {
+    const book = await openEpub(new URL("./fixtures/semantic.epub", import.meta.url).pathname);
+    const { chapters } = await loadSpineChapters(book);
+
+    expect(listNoteElements(chapters[1]!.content)).toEqual([{ id: "n1", type: "footnote" }]);
+  });
+});
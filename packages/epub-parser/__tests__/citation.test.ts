@@ -0,0 +1,75 @@
+import { describe, expect, it } from "bun:test";
+import { composeQuote, extractIsbn, formatAttribution, toBibtex } from "@epub-parser";
+
+describe("toBibtex", () => {
+  it("builds a book entry from populated metadata", () => {
+    const entry = toBibtex({
+      title: "Tom & Jerry {Collected}",
+      creator: "Jane Doe",
+      publisher: "Acme_Press",
+      date: "2019-04-01",
+      identifier: "urn:isbn:978-0-306-40615-7",
+      language: "en",
+    });
+
+    expect(entry).toBe(
+      [
+        "@book{doe2019tom,",
+        "  author = {Jane Doe},",
+        "  title = {Tom \\& Jerry \\{Collected\\}},",
+        "  publisher = {Acme\\_Press},",
+        "  year = {2019},",
+        "  isbn = {9780306406157},",
+        "  language = {en}",
+        "}",
+      ].join("\n"),
+    );
+  });
+
+  it("omits missing fields", () => {
+    expect(toBibtex({ title: "Untitled" })).toBe("@book{untitled,\n  title = {Untitled}\n}");
+  });
+
+  it("only treats valid ISBNs as ISBNs", () => {
+    expect(extractIsbn("0-306-40615-2")).toBe("0306406152");
+    expect(extractIsbn("ISBN 978-0-306-40615-7")).toBe("9780306406157");
+    expect(extractIsbn("978-0-306-40615-8")).toBeNull();
+    expect(extractIsbn("urn:uuid:4b5f1c2e-0000-4000-8000-000000000000")).toBeNull();
+  });
+});
+
+describe("composeQuote", () => {
+  const metadata = {
+    title: "Pride and Prejudice",
+    creator: "Jane Austen",
+    authors: ["Jane Austen"],
+    publisher: "T. Egerton",
+    date: "1813-01-28",
+  };
+
+  it("quotes the selection and appends the source in the configured style", () => {
+    const selection = "  It is a truth universally acknowledged.\n";
+
+    expect(composeQuote(selection, metadata, { style: "apa", chapterTitle: "Chapter 1" })).toBe(
+      "“It is a truth universally acknowledged.”\n\n— Jane Austen (1813). Pride and Prejudice. Chapter 1.",
+    );
+    expect(composeQuote(selection, metadata, { style: "mla", chapterTitle: "Chapter 1" })).toBe(
+      '“It is a truth universally acknowledged.”\n\n— Jane Austen. "Chapter 1." Pride and Prejudice, T. Egerton, 1813.',
+    );
+    expect(composeQuote(selection, metadata, { style: "chicago", chapterTitle: "Chapter 1" })).toBe(
+      '“It is a truth universally acknowledged.”\n\n— Jane Austen, Pride and Prejudice, "Chapter 1" (T. Egerton, 1813).',
+    );
+  });
+
+  it("keeps a whole chapter unquoted", () => {
+    expect(composeQuote("First paragraph.\n\nSecond paragraph.", metadata, { style: "mla" })).toBe(
+      "First paragraph.\n\nSecond paragraph.\n\n— Jane Austen. Pride and Prejudice, T. Egerton, 1813.",
+    );
+  });
+
+  it("leaves out what the metadata doesn't have", () => {
+    expect(formatAttribution({ title: "Untitled" }, "apa")).toBe("Untitled. (n.d.).");
+    expect(formatAttribution({ title: "Untitled" }, "chicago")).toBe("Untitled.");
+    expect(formatAttribution({ title: "T", authors: ["A", "B", "C"] }, "mla")).toBe("A, et al. T.");
+  });
+});
@@ -0,0 +1,113 @@
+import { describe, expect, it } from "bun:test";
+import {
+  classifyChapterMatter,
+  listDeepLinkTargets,
+  listImageSources,
+  readChapterLanguage,
+  readChapterSemanticType,
+  replaceImageSources,
+  replaceImagesWithAlt,
+  trimLeadingBlankLines,
+} from "@epub-parser";
+
+describe("readChapterLanguage", () => {
+  it("reads the language declared on the chapter root", () => {
+    const chapter = `<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml" lang="fr">
+  <head><title>Le chapitre</title></head>
+  <body><p>Bonjour.</p></body>
+</html>`;
+
+    expect(readChapterLanguage(chapter)).toBe("fr");
+  });
+
+  it("prefers xml:lang and falls back to the body element", () => {
+    expect(readChapterLanguage('<html xml:lang="de-AT" lang="de"><body></body></html>')).toBe("de-AT");
+    expect(readChapterLanguage('<html><body lang="ar"><p>مرحبا</p></body></html>')).toBe("ar");
+    expect(readChapterLanguage("<html><body><p lang=\"es\">hola</p></body></html>")).toBeUndefined();
+  });
+});
+
+describe("image sources", () => {
+  const figure =
+    '<figure><img src="../images/map.png" alt="Map"/><figcaption>The harbour</figcaption></figure><img src=\'gone.jpg\'>';
+
+  it("lists image sources once each", () => {
+    expect(listImageSources(figure + figure)).toEqual(["../images/map.png", "gone.jpg"]);
+  });
+
+  it("rewrites resolved images and drops missing ones", () => {
+    const html = replaceImageSources(
+      figure,
+      new Map([
+        ["../images/map.png", "data:image/png;base64,AAAA"],
+        ["gone.jpg", null],
+      ]),
+    );
+
+    expect(html).toBe(
+      '<figure><img src="data:image/png;base64,AAAA" alt="Map"/><figcaption>The harbour</figcaption></figure>',
+    );
+  });
+
+  it("shows the alt text of images in formats the reader cannot display", () => {
+    expect(replaceImagesWithAlt(figure, new Set(["../images/map.png", "gone.jpg"]))).toBe(
+      '<figure><span class="reader-image-alt" role="img">Map</span><figcaption>The harbour</figcaption></figure>',
+    );
+  });
+});
+
+describe("trimLeadingBlankLines", () => {
+  it("drops byte order marks and blank lines before the first line", () => {
+    expect(trimLeadingBlankLines('\uFEFF\uFEFF\r\n  \n<?xml version="1.0"?>\n\n<html/>')).toBe(
+      '<?xml version="1.0"?>\n\n<html/>',
+    );
+    expect(trimLeadingBlankLines("  indented first line")).toBe("  indented first line");
+  });
+});
+
+describe("listDeepLinkTargets", () => {
+  it("maps anchors to the block they belong to", () => {
+    const html = `<html><head><title id="ignored">T</title></head><body>
+      <section id="part1">
+        <h1 id="title">Title</h1>
+        <p>First <a id="note-ref-1" href="#n1">1</a> paragraph.</p>
+        <ul><li id="item-a">A</li><li>B <span id="inline-b">b</span></li></ul>
+        <hr/>
+        <p id='last'>Last</p>
+      </section>
+      <div id="trailing"></div>
+    </body></html>`;
+
+    expect(listDeepLinkTargets(html)).toEqual([
+      { id: "part1", blockIndex: 0 },
+      { id: "title", blockIndex: 0 },
+      { id: "note-ref-1", blockIndex: 1 },
+      { id: "item-a", blockIndex: 2 },
+      { id: "inline-b", blockIndex: 3 },
+      { id: "last", blockIndex: 5 },
+      { id: "trailing", blockIndex: 5 },
+    ]);
+  });
+});
+
+describe("readChapterSemanticType", () => {
+  it("reads the type from the body or the outermost section, but not from content", () => {
+    expect(readChapterSemanticType(`<html><body epub:type="bodymatter"><p>x</p></body></html>`)).toBe("bodymatter");
+    const nested = `<html><body><div><section epub:type='part'><h1>I</h1></section></div></body></html>`;
+    expect(readChapterSemanticType(nested)).toBe("part");
+    expect(readChapterSemanticType(`<html><body><p>x</p><section epub:type="chapter"></section></body></html>`))
+      .toBeUndefined();
+  });
+});
+
+describe("classifyChapterMatter", () => {
+  it("prefers partitions over component types", () => {
+    expect(classifyChapterMatter("backmatter chapter")).toBe("back");
+    expect(classifyChapterMatter("preface")).toBe("front");
+    expect(classifyChapterMatter("Appendix")).toBe("back");
+    expect(classifyChapterMatter("chapter")).toBe("body");
+    expect(classifyChapterMatter("sidebar")).toBeUndefined();
+    expect(classifyChapterMatter(undefined)).toBeUndefined();
+  });
+});
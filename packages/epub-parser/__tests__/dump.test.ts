@@ -0,0 +1,40 @@
+import { describe, expect, it } from "bun:test";
+import { dumpBook, listBookDeepLinkTargets, openEpub, writeEpub } from "@epub-parser";
+
+const fixturePath = new URL("./fixtures/test.epub", import.meta.url).pathname;
+
+describe("dumpBook", () => {
+  it("serializes to valid JSON with the structural keys and chapter text", async () => {
+    const book = await openEpub(fixturePath, { id: "dump" });
+    const parsed = JSON.parse(JSON.stringify(await dumpBook(book), null, 2));
+
+    expect(Object.keys(parsed)).toEqual(["id", "metadata", "manifest", "spine", "toc", "anchors", "chapters"]);
+    expect(parsed.metadata.title).toBe("Test Book");
+    expect(parsed.chapters[0].idref).toBe("chapter1");
+    expect(parsed.chapters[0].content).toContain("<body");
+  });
+
+  it("omits chapter text when asked for a compact view", async () => {
+    const book = await openEpub(fixturePath, { id: "dump" });
+    const parsed = JSON.parse(JSON.stringify(await dumpBook(book, { includeText: false })));
+
+    expect(Object.keys(parsed)).toEqual(["id", "metadata", "manifest", "spine", "toc", "anchors"]);
+  });
+
+  it("lists deep-link targets with their chapter and block", async () => {
+    const book = await openEpub(
+      writeEpub({
+        metadata: { title: "Anchors" },
+        chapters: [
+          { title: "One", html: "<h1>One</h1>\n<p>Intro</p>" },
+          { title: "Two", html: '<h1 id="two">Two</h1>\n<p>Text</p>\n<p>See <a id="ref" href="#n">note</a></p>' },
+        ],
+      }),
+    );
+
+    expect(await listBookDeepLinkTargets(book)).toEqual([
+      { spineIndex: 1, href: "OEBPS/chapter-2.xhtml", id: "two", blockIndex: 0 },
+      { spineIndex: 1, href: "OEBPS/chapter-2.xhtml", id: "ref", blockIndex: 2 },
+    ]);
+  });
+});
@@ -0,0 +1,37 @@
+import { describe, expect, it } from "bun:test";
+import { readImageSize, resolveImageMediaType, sniffImageType } from "@epub-parser";
+
+const base64 = (value: string) => new Uint8Array(Buffer.from(value, "base64"));
+// 1×1 WebP images: lossy, lossless and extended (with alpha).
+const WEBP_LOSSY = base64("UklGRiIAAABXRUJQVlA4IBYAAAAwAQCdASoBAAEADsD+JaQAA3AAAAAA");
+const WEBP_LOSSLESS = base64("UklGRhoAAABXRUJQVlA4TA0AAAAvAAAAEAcQERGIiP4HAA==");
+const WEBP_EXTENDED = base64(
+  "UklGRkoAAABXRUJQVlA4WAoAAAAQAAAAAAAAAAAAQUxQSAwAAAARBxAR/Q9ERP8DAABWUDggGAAAABQBAJ0BKgEAAQAAAP4AAA3AAP7mtQAAAA==",
+);
+const AVIF_HEADER = new Uint8Array([0, 0, 0, 0x1c, ...new TextEncoder().encode("ftypmif1\0\0\0\0mif1avifmiaf")]);
+
+describe("image types", () => {
+  it("tells formats apart by their leading bytes", () => {
+    expect([WEBP_LOSSY, WEBP_LOSSLESS, WEBP_EXTENDED].map(sniffImageType)).toEqual([
+      "image/webp",
+      "image/webp",
+      "image/webp",
+    ]);
+    expect(sniffImageType(AVIF_HEADER)).toBe("image/avif");
+    expect(sniffImageType(new TextEncoder().encode('<?xml version="1.0"?>\n<svg xmlns="">'))).toBe("image/svg+xml");
+    expect(sniffImageType(new Uint8Array([0x49, 0x49, 0x2a, 0]))).toBe("image/tiff");
+    expect(sniffImageType(new Uint8Array([1, 2, 3]))).toBeNull();
+  });
+
+  it("prefers the bytes over the declared type and normalises the declared one", () => {
+    expect(resolveImageMediaType(WEBP_LOSSY, "image/jpeg")).toBe("image/webp");
+    expect(resolveImageMediaType(new Uint8Array([1, 2, 3]), " Image/JPG")).toBe("image/jpeg");
+  });
+
+  it("reads the pixel size of every kind of WebP", () => {
+    for (const image of [WEBP_LOSSY, WEBP_LOSSLESS, WEBP_EXTENDED]) {
+      expect(readImageSize(image)).toEqual({ width: 1, height: 1 });
+    }
+    expect(readImageSize(WEBP_LOSSY.subarray(0, 20))).toBeNull();
+  });
+});
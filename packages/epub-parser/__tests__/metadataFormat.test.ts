@@ -0,0 +1,28 @@
+import { describe, expect, it } from "bun:test";
+import { formatPublicationDate, formatSeriesIndex, parseEpubDate } from "@epub-parser";
+
+describe("formatPublicationDate", () => {
+  it("renders an ISO datetime as month and year", () => {
+    expect(formatPublicationDate("2019-03-01T00:00:00Z", "en-US")).toBe("March 2019");
+    expect(formatPublicationDate("2019-03-01T00:00:00+08:00")).toBe("2019年3月");
+  });
+
+  it("keeps a year-only date to the year", () => {
+    expect(parseEpubDate("1899")).toEqual({ year: 1899, month: undefined, day: undefined });
+    expect(formatPublicationDate("1899", "en-US")).toBe("1899");
+  });
+
+  it("returns malformed dates verbatim", () => {
+    expect(formatPublicationDate(" Spring 2019 ")).toBe("Spring 2019");
+    expect(formatPublicationDate("2019-13-01")).toBe("2019-13-01");
+    expect(formatPublicationDate("20190301")).toBe("20190301");
+  });
+});
+
+describe("formatSeriesIndex", () => {
+  it("drops a trailing .0 but keeps real fractions", () => {
+    expect(formatSeriesIndex("2.0")).toBe("2");
+    expect(formatSeriesIndex("2.5")).toBe("2.5");
+    expect(formatSeriesIndex("II")).toBe("II");
+  });
+});
@@ -1,8 +1,46 @@
-import { describe, expect, it } from "bun:test";
-import { openEpub } from "@epub-parser";
+import { describe, expect, it, spyOn } from "bun:test";
+import { openEpub, openEpubMetadata, openEpubs, readImageSize, writeEpub } from "@epub-parser";
+import { ZipWriter } from "@epub-parser/zipWriter";
 
 const fixturePath = new URL("./fixtures/test.epub", import.meta.url).pathname;
 
+interface TestEpubOptions {
+  spineAttributes?: string;
+  /** Manifest items; defaults to the single chapter c1.xhtml. */
+  manifest?: string;
+  spine?: string;
+  /** Archive entries beside (or replacing) c1.xhtml, keyed by path. */
+  files?: Record<string, string | Uint8Array>;
+}
+
+function epubWithMetadata(metadata: string, options: TestEpubOptions = {}): Uint8Array {
+  const {
+    spineAttributes = "",
+    manifest = '<item id="c1" href="c1.xhtml" media-type="application/xhtml+xml"/>',
+    spine = '<itemref idref="c1"/>',
+  } = options;
+  const zip = new ZipWriter();
+  zip.add("mimetype", "application/epub+zip");
+  zip.add(
+    "META-INF/container.xml",
+    '<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container"><rootfiles>' +
+      '<rootfile full-path="content.opf" media-type="application/oebps-package+xml"/></rootfiles></container>',
+  );
+  zip.add(
+    "content.opf",
+    `<package xmlns="http://www.idpf.org/2007/opf" version="3.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <metadata>${metadata}</metadata>
+  <manifest>${manifest}</manifest>
+  <spine${spineAttributes}>${spine}</spine>
+</package>`,
+  );
+  const files = { "c1.xhtml": "<html><body><p>Text</p></body></html>", ...options.files };
+  for (const [path, content] of Object.entries(files)) {
+    zip.add(path, content);
+  }
+  return zip.finish();
+}
+
 describe("openEpub", () => {
   it("parses basic metadata, manifest, and spine from file path input", async () => {
     const book = await openEpub(fixturePath);
@@ -35,4 +73,198 @@ describe("openEpub", () => {
     expect(book.spine).toHaveLength(1);
     expect(duration).toBeLessThan(750);
   });
+
+  it("reads metadata and cover without building the full book model", async () => {
+    const coverFixturePath = new URL("./fixtures/cover.epub", import.meta.url).pathname;
+    const summary = await openEpubMetadata(coverFixturePath);
+
+    expect(summary.metadata.title).toBe("Covered Book");
+    expect(summary.metadata.creator).toBe("Jane Doe");
+    expect(summary.cover?.mediaType).toBe("image/png");
+    expect(summary.cover?.href).toBe("OEBPS/images/cover.png");
+    expect(summary.cover?.data.byteLength).toBeGreaterThan(0);
+  });
+
+  it("reads a WebP cover labelled as JPEG by its bytes", async () => {
+    // 1×1 lossy WebP.
+    const webp = new Uint8Array(Buffer.from("UklGRiIAAABXRUJQVlA4IBYAAAAwAQCdASoBAAEADsD+JaQAA3AAAAAA", "base64"));
+    const manifest =
+      '<item id="c1" href="c1.xhtml" media-type="application/xhtml+xml"/>' +
+      '<item id="cover" href="cover.jpg" media-type="image/jpeg" properties="cover-image"/>';
+
+    const { cover } = await openEpubMetadata(epubWithMetadata("", { manifest, files: { "cover.jpg": webp } }));
+
+    expect(cover?.mediaType).toBe("image/webp");
+    expect(readImageSize(cover!.data)).toEqual({ width: 1, height: 1 });
+  });
+
+  it("records the cover path on the full book model", async () => {
+    const book = await openEpub(new URL("./fixtures/cover.epub", import.meta.url).pathname);
+
+    expect(book.coverHref).toBe("OEBPS/images/cover.png");
+    expect((await openEpub(fixturePath)).coverHref).toBeUndefined();
+  });
+
+  it("orders NCX navigation points by playOrder", async () => {
+    const ncxFixturePath = new URL("./fixtures/ncx-play-order.epub", import.meta.url).pathname;
+    const book = await openEpub(ncxFixturePath);
+
+    expect(book.toc.map(item => item.label)).toEqual(["Chapter 1", "Chapter 2", "Chapter 3"]);
+    expect(book.toc.map(item => item.order)).toEqual([1, 2, 4]);
+    expect(book.toc[1]?.children?.map(item => item.label)).toEqual(["Section 2.1", "Section 2.2"]);
+    expect(book.toc[1]?.children?.[0]?.href).toBe("OEBPS/chapter2.xhtml#s1");
+  });
+
+  it("reads the rights statement and every packed creator", async () => {
+    const rightsFixturePath = new URL("./fixtures/rights.epub", import.meta.url).pathname;
+    const book = await openEpub(rightsFixturePath, { normalizeAuthorOrder: true });
+
+    expect(book.metadata.rights).toBe(
+      "This work is in the public domain in the United States.\n\nTranscribed from the 1899 edition; the transcription is released under CC0.",
+    );
+    expect(book.metadata.authors).toEqual(["Jane Doe", "John Smith"]);
+  });
+
+  it("reads the calibre series name and index", async () => {
+    const seriesFixturePath = new URL("./fixtures/series.epub", import.meta.url).pathname;
+    const book = await openEpub(seriesFixturePath);
+
+    expect(book.metadata.series).toBe("Foundation");
+    expect(book.metadata.seriesIndex).toBe("2.0");
+    expect(book.metadata.date).toBe("2019-03-01T00:00:00+00:00");
+  });
+
+  it("rejects DRM-protected books with a dedicated error", async () => {
+    const drmFixturePath = new URL("./fixtures/drm.epub", import.meta.url).pathname;
+
+    await expect(openEpub(drmFixturePath)).rejects.toMatchObject({
+      code: "EPUB_DRM_PROTECTED",
+      source: "META-INF/encryption.xml",
+    });
+  });
+
+  it("opens books whose encryption.xml only obfuscates fonts", async () => {
+    const fontFixturePath = new URL("./fixtures/obfuscated-font.epub", import.meta.url).pathname;
+    const book = await openEpub(fontFixturePath);
+
+    expect(book.metadata.title).toBe("Font Book");
+    expect(book.spine).toHaveLength(1);
+  });
+
+  it("opens a batch of books in input order with bounded concurrency", async () => {
+    const fixture = (name: string) => new URL(`./fixtures/${name}`, import.meta.url).pathname;
+    // Slow reads keep every book in flight long enough for an unbounded batch to show.
+    const readFile = Bun.file.bind(Bun);
+    let reading = 0;
+    let peak = 0;
+    const file = spyOn(Bun, "file").mockImplementation(((path: string) => {
+      const real = readFile(path);
+      return {
+        exists: () => real.exists(),
+        arrayBuffer: async () => {
+          peak = Math.max(peak, ++reading);
+          await Bun.sleep(5);
+          reading--;
+          return real.arrayBuffer();
+        },
+      };
+    }) as unknown as typeof Bun.file);
+
+    const results = await openEpubs(
+      ["series.epub", "drm.epub", "test.epub", "cover.epub", "rights.epub"].map(fixture),
+      { concurrency: 2 },
+    ).finally(() => file.mockRestore());
+
+    expect(peak).toBe(2);
+    expect(results.map(result => (result.status === "fulfilled" ? result.value.metadata.title : "rejected"))).toEqual([
+      "Second Foundation",
+      "rejected",
+      "Test Book",
+      "Covered Book",
+      "Public Domain Book",
+    ]);
+    expect(results[1]).toMatchObject({ status: "rejected", reason: { code: "EPUB_DRM_PROTECTED" } });
+  });
+
+  it("splits the main title and subtitle marked by title-type refinements", async () => {
+    const refined = await openEpub(
+      epubWithMetadata(`
+    <dc:title id="t2">A Novel</dc:title>
+    <meta refines="#t2" property="title-type">subtitle</meta>
+    <dc:title id="t1">Middlemarch</dc:title>
+    <meta refines="#t1" property="title-type">main</meta>`),
+    );
+    const plain = await openEpub(epubWithMetadata("<dc:title>Middlemarch: A Novel</dc:title>"));
+    const exported = await openEpub(
+      writeEpub({ metadata: { title: "Dune", subtitle: "Book One" }, chapters: [{ title: "One", html: "<p/>" }] }),
+    );
+
+    expect(refined.metadata).toMatchObject({ title: "Middlemarch", subtitle: "A Novel" });
+    expect(plain.metadata.title).toBe("Middlemarch: A Novel");
+    expect(plain.metadata.subtitle).toBeUndefined();
+    expect(exported.metadata).toMatchObject({ title: "Dune", subtitle: "Book One" });
+  });
+
+  it("keeps every other dc:title as an alternate title", async () => {
+    const translated = await openEpub(
+      epubWithMetadata("<dc:title>The Tale of Genji</dc:title><dc:title>源氏物語</dc:title>"),
+    );
+    const single = await openEpub(epubWithMetadata("<dc:title>Middlemarch</dc:title>"));
+
+    expect(translated.metadata.title).toBe("The Tale of Genji");
+    expect(translated.metadata.alternateTitles).toEqual(["源氏物語"]);
+    expect(single.metadata.alternateTitles).toBeUndefined();
+  });
+
+  it("reads the spine's page progression direction", async () => {
+    const rtl = await openEpub(
+      epubWithMetadata("<dc:title>Book</dc:title><dc:language>ja</dc:language>", {
+        spineAttributes: ' page-progression-direction="rtl"',
+      }),
+    );
+    const unset = await openEpub(
+      epubWithMetadata("<dc:title>Book</dc:title>", { spineAttributes: ' page-progression-direction="default"' }),
+    );
+
+    expect(rtl.pageProgression).toBe("rtl");
+    expect(unset.pageProgression).toBeUndefined();
+  });
+
+  it("reads the NCX page list into a print page map", async () => {
+    const ncx = `<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <navMap>
+    <navPoint id="n1" playOrder="1"><navLabel><text>One</text></navLabel><content src="text/c1.xhtml"/></navPoint>
+  </navMap>
+  <pageList>
+    <pageTarget id="p2" type="normal" value="2" playOrder="3">
+      <navLabel><text>2</text></navLabel><content src="text/c2.xhtml#page2"/>
+    </pageTarget>
+    <pageTarget id="p1" type="normal" value="1" playOrder="2">
+      <navLabel><text>1</text></navLabel><content src="text/c1.xhtml#page1"/>
+    </pageTarget>
+  </pageList>
+</ncx>`;
+    const book = await openEpub(
+      epubWithMetadata("<dc:title>Paged</dc:title>", {
+        spineAttributes: ' toc="ncx"',
+        manifest: `
+    <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+    <item id="c1" href="text/c1.xhtml" media-type="application/xhtml+xml"/>
+    <item id="c2" href="text/c2.xhtml" media-type="application/xhtml+xml"/>`,
+        spine: '<itemref idref="c1"/><itemref idref="c2"/>',
+        files: {
+          "toc.ncx": ncx,
+          "text/c1.xhtml": '<html><body><p id="page1">One</p></body></html>',
+          "text/c2.xhtml": '<html><body><p id="page2">Two</p></body></html>',
+        },
+      }),
+    );
+    const plain = await openEpub(epubWithMetadata("<dc:title>Book</dc:title>"));
+
+    expect(book.pageMap).toEqual([
+      { label: "1", href: "text/c1.xhtml#page1" },
+      { label: "2", href: "text/c2.xhtml#page2" },
+    ]);
+    expect(plain.pageMap).toEqual([]);
+  });
 });
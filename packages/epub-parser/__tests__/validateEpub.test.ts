@@ -0,0 +1,30 @@
+import { describe, expect, it } from "bun:test";
+import { validateEpub } from "@epub-parser";
+
+const fixture = (name: string) => new URL(`./fixtures/${name}`, import.meta.url).pathname;
+
+describe("validateEpub", () => {
+  it("reports spine items that are missing from the manifest", async () => {
+    const report = await validateEpub(fixture("invalid.epub"));
+
+    const spineWarnings = report.warnings.filter(warning => warning.code === "SPINE_ITEM_NOT_IN_MANIFEST");
+    expect(spineWarnings.map(warning => warning.target)).toEqual(["chapter2"]);
+  });
+
+  it("reports a book without any table of contents", async () => {
+    const report = await validateEpub(fixture("invalid.epub"));
+
+    expect(report.warnings.some(warning => warning.code === "TOC_MISSING")).toBe(true);
+    expect(report.warnings.some(warning => warning.code === "COVER_MISSING")).toBe(true);
+  });
+
+  it("does not flag structural problems in a well-formed book", async () => {
+    const report = await validateEpub(fixture("test.epub"));
+    const codes = report.warnings.map(warning => warning.code);
+
+    expect(codes).not.toContain("TOC_MISSING");
+    expect(codes).not.toContain("SPINE_ITEM_NOT_IN_MANIFEST");
+    expect(codes).not.toContain("MANIFEST_ITEM_UNREFERENCED");
+    expect(codes).not.toContain("CHAPTER_NOT_UTF8");
+  });
+});
@@ -0,0 +1,35 @@
+import { describe, expect, it } from "bun:test";
+import { openEpub, writeEpub } from "@epub-parser";
+
+// 1×1 transparent PNG.
+const PIXEL =
+  "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
+
+describe("writeEpub", () => {
+  it("round-trips chapters, titles and metadata through openEpub", async () => {
+    const data = writeEpub({
+      metadata: { title: "Collected Stories", authors: ["Jane Doe", "John Smith"], language: "en", rights: "CC0" },
+      chapters: [
+        { title: "Chapter One", html: "<h1>Chapter One</h1>\n<p>Hello &amp; welcome.</p>" },
+        { title: "Chapter Two", html: `<p><img src="data:image/png;base64,${PIXEL}" alt="dot"/></p>` },
+      ],
+    });
+
+    const book = await openEpub(data);
+
+    expect(book.spine).toHaveLength(2);
+    expect(book.toc.map(item => item.label)).toEqual(["Chapter One", "Chapter Two"]);
+    expect(book.metadata.title).toBe("Collected Stories");
+    expect(book.metadata.authors).toEqual(["Jane Doe", "John Smith"]);
+    expect(book.metadata.rights).toBe("CC0");
+
+    const image = Object.values(book.manifest).find(item => item.mediaType === "image/png");
+    expect(image?.href).toBe("OEBPS/images/image-1.png");
+    const chapterTwo = await book.resources.getContent(book.manifest[book.spine[1]!.idref]!.href);
+    expect(chapterTwo).toContain('src="images/image-1.png"');
+  });
+
+  it("rejects a book without chapters", () => {
+    expect(() => writeEpub({ metadata: {}, chapters: [] })).toThrow();
+  });
+});
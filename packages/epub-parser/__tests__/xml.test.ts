@@ -0,0 +1,24 @@
+import { describe, expect, it } from "bun:test";
+import { getText, parseXml } from "@epub-parser/xml";
+
+describe("parseXml", () => {
+  it("merges an element's text runs into one text node where the first run appeared", () => {
+    const root = parseXml("<p>one<b>bold</b>two<br/>three<i>it<u>u</u>alic</i></p>");
+    expect(root?.children.map(child => child.name)).toEqual(["#text", "b", "br", "i"]);
+    expect(root?.children[0]?.text).toBe("onetwothree");
+    expect(getText(root?.children[3] ?? null)).toBe("italic");
+  });
+
+  it("keeps text of sibling elements separate", () => {
+    const root = parseXml("<r><a>1</a>x<a>2</a>y</r>");
+    expect(root?.children.map(child => child.text ?? getText(child))).toEqual(["1", "xy", "2"]);
+  });
+
+  it("joins text split by child elements with the shared spacing rules", () => {
+    expect(parseXml("<a>Chapter <em>One</em> : Start</a>")?.children[0]?.text).toBe("Chapter: Start");
+    expect(parseXml("<a>one <b>x</b> two</a>")?.children[0]?.text).toBe("one two");
+    expect(parseXml('<a xml:lang="fr">Partie <em>I</em> : Début</a>')?.children[0]?.text).toBe(
+      "Partie\u202f: Début",
+    );
+  });
+});
@@ -0,0 +1,52 @@
+export interface AuthorNormalizationOptions {
+  /** Rewrite "Last, First" as "First Last". */
+  normalizeAuthorOrder?: boolean;
+}
+
+// Separators publishers use to pack several people into one dc:creator. Commas are
+// deliberately absent: they usually mean "Last, First" rather than a list.
+const AUTHOR_SEPARATOR = /\s*(?:;|；|&|＆|、)\s*/;
+// Conjunctions also join the words of a single name ("Simon and Schuster", "Smith
+// et al."), so they only split when every side looks like a full personal name.
+const CONJUNCTION_SEPARATOR = /\s+(?:and|und|et)\s+(?!al\b)/i;
+
+/**
+ * Splits raw `dc:creator` values into individual authors, optionally reordering
+ * "Last, First" names. Duplicates are dropped case-insensitively, keeping the
+ * first spelling seen.
+ */
+export function splitAuthors(values: string[], options: AuthorNormalizationOptions = {}): string[] {
+  const authors: string[] = [];
+  const seen = new Set<string>();
+
+  for (const value of values) {
+    for (const part of value.split(AUTHOR_SEPARATOR).flatMap(splitConjunctions)) {
+      let name = part.replace(/\s+/g, " ").trim();
+      if (!name) continue;
+      if (options.normalizeAuthorOrder) {
+        name = reorderAuthorName(name);
+      }
+
+      const key = name.toLocaleLowerCase();
+      if (seen.has(key)) continue;
+      seen.add(key);
+      authors.push(name);
+    }
+  }
+
+  return authors;
+}
+
+function splitConjunctions(value: string): string[] {
+  const names = value.split(CONJUNCTION_SEPARATOR);
+  return names.every(name => name.trim().split(/\s+/).length > 1) ? names : [value];
+}
+
+/** "Doe, Jane" → "Jane Doe". Names with zero or several commas are left alone. */
+export function reorderAuthorName(name: string): string {
+  const parts = name.split(",").map(part => part.trim());
+  if (parts.length !== 2 || !parts[0] || !parts[1]) {
+    return name;
+  }
+  return `${parts[1]} ${parts[0]}`;
+}
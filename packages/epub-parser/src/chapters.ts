@@ -0,0 +1,92 @@
+import { classifyChapterMatter, readChapterSemanticType, type ChapterMatter } from "./content";
+import type { BookModel } from "./types";
+
+export interface SpineChapter {
+  /** Position in the spine, kept stable even when neighbours are skipped. */
+  index: number;
+  idref: string;
+  href: string;
+  mediaType: string;
+  content: string;
+  /** False for `linear="no"` spine items (pop-up notes, answers), reached by links rather than in order. */
+  linear: boolean;
+  /** Set on placeholders standing in for a document the archive does not contain. */
+  missing?: boolean;
+  /** `epub:type` of the chapter's root element, e.g. `bodymatter chapter`. */
+  semanticType?: string;
+  /** Front, body or back matter, when the semantic type says so. */
+  matter?: ChapterMatter;
+}
+
+export interface SpineChapters {
+  chapters: SpineChapter[];
+  /** Spine idrefs whose document could not be found, in spine order. */
+  missingIdrefs: string[];
+}
+
+const CHAPTER_MEDIA_TYPE = /html|xml|^text\//i;
+const PLACEHOLDER_MEDIA_TYPE = "application/xhtml+xml";
+
+function escapeHtml(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+/** Stand-in markup for a spine document missing from the archive. */
+export function missingChapterHtml(idref: string): string {
+  return [
+    `<h1>（缺失：${escapeHtml(idref)}）</h1>`,
+    "<p>这一章的内容在电子书文件中找不到，可能是文件损坏或打包时遗漏。其余章节不受影响。</p>",
+  ].join("\n");
+}
+
+/**
+ * Reads every text document in the spine. A spine entry whose manifest item or
+ * file is missing becomes a placeholder chapter instead of vanishing, so the
+ * reader sees the gap and TOC links to it still land in the right place.
+ * Non-text spine items (e.g. a bare image) are skipped as before.
+ */
+export async function loadSpineChapters(book: BookModel): Promise<SpineChapters> {
+  const missingIdrefs: string[] = [];
+  const chapters = await Promise.all(
+    book.spine.map(async (item, index): Promise<SpineChapter | null> => {
+      const manifestItem = book.manifest[item.idref];
+      if (manifestItem && !CHAPTER_MEDIA_TYPE.test(manifestItem.mediaType)) {
+        return null;
+      }
+
+      const content = manifestItem ? await book.resources.getContent(manifestItem.href) : null;
+      if (!manifestItem || content == null) {
+        return {
+          index,
+          idref: item.idref,
+          href: manifestItem?.href ?? "",
+          mediaType: PLACEHOLDER_MEDIA_TYPE,
+          content: missingChapterHtml(item.idref),
+          linear: item.linear,
+          missing: true,
+        };
+      }
+
+      const text = typeof content === "string" ? content : new TextDecoder("utf-8").decode(content);
+      const semanticType = readChapterSemanticType(text);
+      return {
+        index,
+        idref: item.idref,
+        href: manifestItem.href,
+        mediaType: manifestItem.mediaType,
+        content: text,
+        linear: item.linear,
+        semanticType,
+        matter: classifyChapterMatter(semanticType),
+      };
+    }),
+  );
+
+  for (const chapter of chapters) {
+    if (chapter?.missing) missingIdrefs.push(chapter.idref);
+  }
+  return {
+    chapters: chapters.filter((chapter): chapter is SpineChapter => chapter !== null),
+    missingIdrefs,
+  };
+}
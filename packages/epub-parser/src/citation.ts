@@ -0,0 +1,136 @@
+import type { BookMetadata } from "./types";
+
+const BIBTEX_SPECIAL_CHARACTERS = /[\\{}&%$#_~^]/g;
+const BIBTEX_ESCAPES: Record<string, string> = {
+  "\\": "\\textbackslash{}",
+  "{": "\\{",
+  "}": "\\}",
+  "&": "\\&",
+  "%": "\\%",
+  $: "\\$",
+  "#": "\\#",
+  _: "\\_",
+  "~": "\\textasciitilde{}",
+  "^": "\\textasciicircum{}",
+};
+
+export function escapeBibtex(value: string): string {
+  return value.replace(BIBTEX_SPECIAL_CHARACTERS, char => BIBTEX_ESCAPES[char] ?? char);
+}
+
+/** Returns the bare ISBN-10/13 when the identifier carries a valid one. */
+export function extractIsbn(identifier?: string): string | null {
+  if (!identifier) return null;
+  const candidate = identifier
+    .replace(/^urn:isbn:/i, "")
+    .replace(/^isbn:?/i, "")
+    .replace(/[\s-]/g, "")
+    .toUpperCase();
+
+  if (/^\d{13}$/.test(candidate)) {
+    const sum = [...candidate].reduce((total, digit, index) => total + Number(digit) * (index % 2 === 0 ? 1 : 3), 0);
+    return sum % 10 === 0 ? candidate : null;
+  }
+
+  if (/^\d{9}[\dX]$/.test(candidate)) {
+    const sum = [...candidate].reduce(
+      (total, digit, index) => total + (digit === "X" ? 10 : Number(digit)) * (10 - index),
+      0,
+    );
+    return sum % 11 === 0 ? candidate : null;
+  }
+
+  return null;
+}
+
+function citationKey(metadata: BookMetadata, year: string | undefined): string {
+  const surname = metadata.creator?.trim().split(/[\s,]+/).filter(Boolean);
+  const author = metadata.creator?.includes(",") ? surname?.[0] : surname?.at(-1);
+  const titleWord = metadata.title?.split(/\s+/).find(word => /[\p{L}\p{N}]/u.test(word));
+  const key = [author, year, titleWord]
+    .filter(Boolean)
+    .join("")
+    .normalize("NFKD")
+    .replace(/[^\p{L}\p{N}]/gu, "")
+    .toLowerCase();
+  return key || "book";
+}
+
+/** Builds a `@book` entry; fields missing from the metadata are left out. */
+export function toBibtex(metadata: BookMetadata): string {
+  const year = metadata.date?.match(/\d{4}/)?.[0];
+  const isbn = extractIsbn(metadata.identifier);
+  const fields: Array<[string, string | undefined]> = [
+    ["author", metadata.creator],
+    ["title", metadata.title],
+    ["publisher", metadata.publisher],
+    ["year", year],
+    ["isbn", isbn ?? undefined],
+    ["language", metadata.language],
+  ];
+
+  const lines = fields
+    .filter((field): field is [string, string] => Boolean(field[1]?.trim()))
+    .map(([name, value]) => `  ${name} = {${escapeBibtex(value.trim())}}`);
+
+  return `@book{${citationKey(metadata, year)},\n${lines.join(",\n")}\n}`;
+}
+
+export type CitationStyle = "apa" | "mla" | "chicago";
+
+export const CITATION_STYLES: CitationStyle[] = ["apa", "mla", "chicago"];
+
+function joinAuthors(authors: string[], style: CitationStyle): string {
+  if (authors.length <= 1) return authors[0] ?? "";
+  if (style === "mla" && authors.length > 2) return `${authors[0]}, et al`;
+  const conjunction = style === "apa" ? "&" : "and";
+  if (authors.length === 2) return `${authors[0]} ${conjunction} ${authors[1]}`;
+  return `${authors.slice(0, -1).join(", ")}, ${conjunction} ${authors.at(-1)}`;
+}
+
+/**
+ * One-line source for a quote in the given style, e.g. for APA
+ * `Jane Austen (1813). Pride and Prejudice. Chapter 1.` Plain text, so titles
+ * are not italicised; parts missing from the metadata are left out.
+ */
+export function formatAttribution(metadata: BookMetadata, style: CitationStyle, chapterTitle?: string): string {
+  const authors = metadata.authors?.length ? metadata.authors : metadata.creator ? [metadata.creator.trim()] : [];
+  const author = joinAuthors(authors, style).replace(/\.$/, "");
+  const title = metadata.title?.trim();
+  const chapter = chapterTitle?.trim();
+  const year = metadata.date?.match(/\d{4}/)?.[0];
+  const publisher = metadata.publisher?.trim();
+  const sentence = (...parts: Array<string | undefined>) => parts.filter(Boolean).join(", ");
+
+  switch (style) {
+    case "apa": {
+      // Without an author, APA moves the title to the front.
+      const date = `(${year ?? "n.d."}).`;
+      const lead = author ? `${author} ${date}` : title ? `${title}. ${date}` : date;
+      return [lead, author && title && `${title}.`, chapter && `${chapter}.`].filter(Boolean).join(" ");
+    }
+    case "mla":
+      return [author && `${author}.`, chapter && `"${chapter}."`, `${sentence(title, publisher, year)}.`]
+        .filter(part => part && part !== ".")
+        .join(" ");
+    case "chicago": {
+      const imprint = sentence(publisher, year);
+      return `${sentence(author, title, chapter && `"${chapter}"`)}${imprint ? ` (${imprint})` : ""}.`;
+    }
+  }
+}
+
+/**
+ * Text to copy for a quote: a single passage is put in quotation marks, a
+ * multi-paragraph one (such as a whole chapter) is kept as is, and the
+ * attribution follows on its own line.
+ */
+export function composeQuote(
+  text: string,
+  metadata: BookMetadata,
+  options: { style: CitationStyle; chapterTitle?: string },
+): string {
+  const passage = text.trim();
+  const quoted = passage.includes("\n") ? passage : `“${passage}”`;
+  return `${quoted}\n\n— ${formatAttribution(metadata, options.style, options.chapterTitle)}`;
+}
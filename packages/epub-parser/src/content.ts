@@ -0,0 +1,266 @@
+// Byte order marks (a converter may leave several) and blank lines ahead of the first content.
+const LEADING_BLANK_PATTERN = /^(?:\uFEFF|[ \t\u00a0]*\r?\n)+/;
+
+/**
+ * Strips leading BOMs and collapses leading blank lines so text resources start
+ * at their first real line; indentation on that line is kept.
+ */
+export function trimLeadingBlankLines(text: string): string {
+  return text.replace(LEADING_BLANK_PATTERN, "");
+}
+
+const ROOT_TAG_PATTERN = /<(html|body)\b([^>]*)>/gi;
+const LANG_ATTRIBUTE_PATTERN = /(?:^|\s)(xml:lang|lang)\s*=\s*("([^"]*)"|'([^']*)')/gi;
+
+/**
+ * Reads the language a chapter declares on its `<html>` (or, failing that,
+ * `<body>`) element. `xml:lang` wins over `lang` when both are present.
+ */
+export function readChapterLanguage(html: string): string | undefined {
+  for (const match of html.matchAll(ROOT_TAG_PATTERN)) {
+    const attributes = new Map<string, string>();
+    for (const attribute of match[2]!.matchAll(LANG_ATTRIBUTE_PATTERN)) {
+      const value = (attribute[3] ?? attribute[4] ?? "").trim();
+      if (value) attributes.set(attribute[1]!.toLowerCase(), value);
+    }
+
+    const language = attributes.get("xml:lang") ?? attributes.get("lang");
+    if (language) return language;
+  }
+  return undefined;
+}
+
+const IMG_SRC_PATTERN = /<img\b[^>]*?\ssrc\s*=\s*("([^"]*)"|'([^']*)')[^>]*>/gi;
+
+/** Distinct `src` values of the chapter's `<img>` elements, in document order. */
+export function listImageSources(html: string): string[] {
+  const sources = new Set<string>();
+  for (const match of html.matchAll(IMG_SRC_PATTERN)) {
+    const src = match[2] ?? match[3];
+    if (src) sources.add(src);
+  }
+  return [...sources];
+}
+
+/**
+ * Rewrites `<img>` sources through `replacements`. An entry mapped to null drops
+ * the image (e.g. a resource missing from the archive) so only its caption remains;
+ * sources without an entry are left untouched.
+ */
+export function replaceImageSources(html: string, replacements: Map<string, string | null>): string {
+  return html.replace(IMG_SRC_PATTERN, (tag, quoted: string, double?: string, single?: string) => {
+    const src = double ?? single ?? "";
+    if (!replacements.has(src)) return tag;
+    const replacement = replacements.get(src);
+    if (replacement === null || replacement === undefined) return "";
+    return tag.replace(quoted, `"${replacement.replace(/"/g, "&quot;")}"`);
+  });
+}
+
+const ALT_ATTRIBUTE_PATTERN = /\salt\s*=\s*(?:"([^"]*)"|'([^']*)')/i;
+
+/**
+ * Replaces the `<img>` elements showing `sources` with their alt text, for
+ * images in a format the reader cannot display. Images without alt text are dropped.
+ */
+export function replaceImagesWithAlt(html: string, sources: Set<string>): string {
+  return html.replace(IMG_SRC_PATTERN, (tag, _quoted: string, double?: string, single?: string) => {
+    if (!sources.has(double ?? single ?? "")) return tag;
+    const match = ALT_ATTRIBUTE_PATTERN.exec(tag);
+    const alt = (match?.[1] ?? match?.[2] ?? "").trim().replace(/</g, "&lt;");
+    return alt ? `<span class="reader-image-alt" role="img">${alt}</span>` : "";
+  });
+}
+
+export interface DeepLinkTarget {
+  /** Fragment id, as used in `chapter.xhtml#id`. */
+  id: string;
+  /** Zero-based index of the paragraph-level block the anchor belongs to. */
+  blockIndex: number;
+}
+
+const TAG_PATTERN = /<(\/?)([a-zA-Z][\w:-]*)\b([^>]*)>/g;
+const ID_ATTRIBUTE_PATTERN = /(?:^|\s)id\s*=\s*(?:"([^"]*)"|'([^']*)')/i;
+const LINK_BLOCK_TAGS = new Set([
+  "p",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "li",
+  "dt",
+  "dd",
+  "blockquote",
+  "pre",
+  "figure",
+  "table",
+  "hr",
+]);
+
+/**
+ * Lists the fragment ids a chapter exposes together with the block each one
+ * lands in, so links can be mapped to paragraphs. An id on a block or inside
+ * one belongs to that block; an id on a wrapper such as `<section>` belongs to
+ * the first block after it.
+ */
+export function listDeepLinkTargets(html: string): DeepLinkTarget[] {
+  const bodyStart = html.search(/<body\b/i);
+  const markup = bodyStart >= 0 ? html.slice(bodyStart) : html;
+  const targets: DeepLinkTarget[] = [];
+  const pending: string[] = [];
+  let blockIndex = -1;
+  let openBlocks = 0;
+
+  for (const match of markup.matchAll(TAG_PATTERN)) {
+    const [, closing, rawName, attributes] = match;
+    const name = rawName!.toLowerCase().replace(/^.*:/, "");
+    const isBlock = LINK_BLOCK_TAGS.has(name);
+    const selfClosing = attributes!.trimEnd().endsWith("/");
+
+    if (closing) {
+      if (isBlock) openBlocks = Math.max(0, openBlocks - 1);
+      continue;
+    }
+
+    if (isBlock) {
+      // Blocks nested in a block (a list inside a list item) are blocks of their own.
+      blockIndex += 1;
+      for (const id of pending.splice(0)) targets.push({ id, blockIndex });
+      if (!selfClosing && name !== "hr") openBlocks += 1;
+    }
+
+    const id = attributes!.match(ID_ATTRIBUTE_PATTERN);
+    const value = id ? (id[1] ?? id[2] ?? "").trim() : "";
+    if (!value) continue;
+    if (isBlock || openBlocks > 0) {
+      targets.push({ id: value, blockIndex: Math.max(0, blockIndex) });
+    } else {
+      pending.push(value);
+    }
+  }
+
+  // Ids after the last block (e.g. an empty trailing section) point at the last one.
+  for (const id of pending) targets.push({ id, blockIndex: Math.max(0, blockIndex) });
+  return targets;
+}
+
+const EPUB_TYPE_ATTRIBUTE_PATTERN = /(?:^|\s)epub:type\s*=\s*(?:"([^"]*)"|'([^']*)')/i;
+// Wrappers a chapter's semantic type may sit on before its first real content.
+const SECTIONING_TAGS = new Set(["body", "section", "article", "div", "main"]);
+
+/** Whitespace-separated `epub:type` tokens of an element's attribute source. */
+function readEpubTypes(attributes: string): string[] {
+  const match = attributes.match(EPUB_TYPE_ATTRIBUTE_PATTERN);
+  return (match?.[1] ?? match?.[2] ?? "").trim().split(/\s+/).filter(Boolean);
+}
+
+/** True when an `epub:type` value carries any of `types`. */
+export function hasEpubType(value: string | null | undefined, types: Iterable<string>): boolean {
+  if (!value) return false;
+  const tokens = new Set(value.trim().toLowerCase().split(/\s+/));
+  for (const type of types) {
+    if (tokens.has(type)) return true;
+  }
+  return false;
+}
+
+/**
+ * Reads the `epub:type` of a chapter's root: the `<body>` or the outermost
+ * sectioning element inside it, e.g. `bodymatter chapter` for
+ * `<section epub:type="bodymatter chapter">`. Only wrappers ahead of the first
+ * content element are considered.
+ */
+export function readChapterSemanticType(html: string): string | undefined {
+  const bodyStart = html.search(/<body\b/i);
+  if (bodyStart < 0) return undefined;
+  for (const match of html.slice(bodyStart).matchAll(TAG_PATTERN)) {
+    const [, closing, rawName, attributes] = match;
+    if (closing) continue;
+    if (!SECTIONING_TAGS.has(rawName!.toLowerCase())) return undefined;
+    const types = readEpubTypes(attributes!);
+    if (types.length > 0) return types.join(" ");
+  }
+  return undefined;
+}
+
+export type ChapterMatter = "front" | "body" | "back";
+
+const MATTER_PARTITIONS: Record<string, ChapterMatter> = {
+  cover: "front",
+  frontmatter: "front",
+  bodymatter: "body",
+  backmatter: "back",
+};
+const MATTER_BY_TYPE: Record<string, ChapterMatter> = {
+  titlepage: "front",
+  halftitlepage: "front",
+  seriespage: "front",
+  "copyright-page": "front",
+  dedication: "front",
+  foreword: "front",
+  preface: "front",
+  abstract: "front",
+  toc: "front",
+  landmarks: "front",
+  loi: "front",
+  lot: "front",
+  volume: "body",
+  part: "body",
+  chapter: "body",
+  subchapter: "body",
+  division: "body",
+  prologue: "body",
+  introduction: "body",
+  conclusion: "body",
+  epilogue: "body",
+  afterword: "back",
+  appendix: "back",
+  glossary: "back",
+  bibliography: "back",
+  index: "back",
+  endnotes: "back",
+  rearnotes: "back",
+  colophon: "back",
+  imprint: "back",
+};
+
+/**
+ * Places a chapter in the front, body or back matter from its semantic type.
+ * The partitions (`frontmatter`, `bodymatter`, `backmatter`, `cover`) win over
+ * the component types; returns undefined when neither is present.
+ */
+export function classifyChapterMatter(semanticType: string | undefined): ChapterMatter | undefined {
+  const types = semanticType?.toLowerCase().split(/\s+/).filter(Boolean) ?? [];
+  for (const type of types) {
+    if (MATTER_PARTITIONS[type]) return MATTER_PARTITIONS[type];
+  }
+  for (const type of types) {
+    if (MATTER_BY_TYPE[type]) return MATTER_BY_TYPE[type];
+  }
+  return undefined;
+}
+
+export const NOTE_TYPES = ["footnote", "endnote", "rearnote", "note"];
+
+export interface NoteElement {
+  id: string;
+  /** The element's full `epub:type`, e.g. `footnote`. */
+  type: string;
+}
+
+/** Elements a chapter marks as notes (footnotes, endnotes), which note references can point to. */
+export function listNoteElements(html: string): NoteElement[] {
+  const notes: NoteElement[] = [];
+  for (const match of html.matchAll(TAG_PATTERN)) {
+    const [, closing, , attributes] = match;
+    if (closing) continue;
+    const types = readEpubTypes(attributes!);
+    if (!hasEpubType(types.join(" "), NOTE_TYPES)) continue;
+    const id = attributes!.match(ID_ATTRIBUTE_PATTERN);
+    const value = id ? (id[1] ?? id[2] ?? "").trim() : "";
+    if (value) notes.push({ id: value, type: types.join(" ") });
+  }
+  return notes;
+}
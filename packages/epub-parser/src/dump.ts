@@ -0,0 +1,58 @@
+import { type DeepLinkTarget, listDeepLinkTargets } from "./content";
+import type { BookMetadata, BookModel, ManifestItem, SpineItemRef, TocItem } from "./types";
+
+export interface BookDumpOptions {
+  /** Include each spine document's markup. Off gives a compact structural view. */
+  includeText?: boolean;
+}
+
+export interface BookDump {
+  id: string;
+  metadata: BookMetadata;
+  manifest: Record<string, ManifestItem>;
+  spine: SpineItemRef[];
+  toc: TocItem[];
+  /** Every fragment id in reading order, for tools that link into paragraphs. */
+  anchors: BookDeepLinkTarget[];
+  chapters?: { idref: string; href?: string; content: string | null }[];
+}
+
+export interface BookDeepLinkTarget extends DeepLinkTarget {
+  spineIndex: number;
+  /** Archive path of the chapter; `${href}#${id}` is the link. */
+  href: string;
+}
+
+async function readSpineText(book: BookModel, item: SpineItemRef) {
+  const href = book.manifest[item.idref]?.href;
+  const content = href ? await book.resources.getContent(href) : null;
+  return { href, content: typeof content === "string" ? content : null };
+}
+
+/** Deep-link targets of every spine document, with the spine index they come from. */
+export async function listBookDeepLinkTargets(book: BookModel): Promise<BookDeepLinkTarget[]> {
+  const chapters = await Promise.all(book.spine.map(item => readSpineText(book, item)));
+  return chapters.flatMap(({ href, content }, spineIndex) =>
+    href && content ? listDeepLinkTargets(content).map(target => ({ spineIndex, href, ...target })) : [],
+  );
+}
+
+/** Plain-data view of a parsed book, suitable for `JSON.stringify`. */
+export async function dumpBook(book: BookModel, options: BookDumpOptions = {}): Promise<BookDump> {
+  const dump: BookDump = {
+    id: book.id,
+    metadata: book.metadata,
+    manifest: book.manifest,
+    spine: book.spine,
+    toc: book.toc,
+    anchors: await listBookDeepLinkTargets(book),
+  };
+
+  if (options.includeText ?? true) {
+    dump.chapters = await Promise.all(
+      book.spine.map(async item => ({ idref: item.idref, ...(await readSpineText(book, item)) })),
+    );
+  }
+
+  return dump;
+}
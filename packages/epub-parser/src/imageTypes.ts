@@ -0,0 +1,115 @@
+/** Image formats the reader can display; anything else falls back to its alt text. */
+export const DISPLAYABLE_IMAGE_TYPES = new Set([
+  "image/png",
+  "image/jpeg",
+  "image/gif",
+  "image/webp",
+  "image/avif",
+  "image/svg+xml",
+  "image/bmp",
+]);
+
+// Non-standard media types seen in real manifests.
+const MEDIA_TYPE_ALIASES: Record<string, string> = {
+  "image/jpg": "image/jpeg",
+  "image/pjpeg": "image/jpeg",
+  "image/x-png": "image/png",
+  "image/svg": "image/svg+xml",
+  "image/x-ms-bmp": "image/bmp",
+};
+
+const AVIF_BRANDS = new Set(["avif", "avis"]);
+const SVG_PROLOGUE = /^(?:\s|<\?xml[^>]*>|<!--[\s\S]*?-->|<!DOCTYPE[^>]*>)*<svg\b/i;
+
+export interface ImageSize {
+  width: number;
+  height: number;
+}
+
+function toBytes(data: Uint8Array | ArrayBuffer): Uint8Array {
+  return data instanceof Uint8Array ? data : new Uint8Array(data);
+}
+
+function ascii(bytes: Uint8Array, start: number, end: number): string {
+  return String.fromCharCode(...bytes.subarray(start, end));
+}
+
+function startsWith(bytes: Uint8Array, signature: number[]): boolean {
+  return signature.every((byte, index) => bytes[index] === byte);
+}
+
+function uint16le(bytes: Uint8Array, offset: number): number {
+  return bytes[offset]! | (bytes[offset + 1]! << 8);
+}
+
+function uint24le(bytes: Uint8Array, offset: number): number {
+  return uint16le(bytes, offset) | (bytes[offset + 2]! << 16);
+}
+
+function uint32be(bytes: Uint8Array, offset: number): number {
+  return ((bytes[offset]! << 24) | (bytes[offset + 1]! << 16) | (bytes[offset + 2]! << 8) | bytes[offset + 3]!) >>> 0;
+}
+
+/** Whether an ISO-BMFF `ftyp` box lists an AVIF brand, as major or compatible brand. */
+function isAvif(bytes: Uint8Array): boolean {
+  if (ascii(bytes, 4, 8) !== "ftyp") return false;
+  const end = Math.min(uint32be(bytes, 0), bytes.length);
+  if (AVIF_BRANDS.has(ascii(bytes, 8, 12))) return true;
+  for (let offset = 16; offset + 4 <= end; offset += 4) {
+    if (AVIF_BRANDS.has(ascii(bytes, offset, offset + 4))) return true;
+  }
+  return false;
+}
+
+/**
+ * The image format `data` holds, told by its leading bytes rather than its
+ * name or declared type; null when no known signature matches.
+ */
+export function sniffImageType(data: Uint8Array | ArrayBuffer): string | null {
+  const bytes = toBytes(data);
+  if (startsWith(bytes, [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a])) return "image/png";
+  if (startsWith(bytes, [0xff, 0xd8, 0xff])) return "image/jpeg";
+  if (/^GIF8[79]a$/.test(ascii(bytes, 0, 6))) return "image/gif";
+  if (ascii(bytes, 0, 4) === "RIFF" && ascii(bytes, 8, 12) === "WEBP") return "image/webp";
+  if (isAvif(bytes)) return "image/avif";
+  if (ascii(bytes, 0, 2) === "BM") return "image/bmp";
+  if (ascii(bytes, 0, 4) === "II*\0" || ascii(bytes, 0, 4) === "MM\0*") return "image/tiff";
+  if (startsWith(bytes, [0xff, 0x0a]) || ascii(bytes, 4, 8) === "JXL ") return "image/jxl";
+  const text = new TextDecoder("utf-8").decode(bytes.subarray(0, 1024));
+  return SVG_PROLOGUE.test(text) ? "image/svg+xml" : null;
+}
+
+/** The type to serve an image as: what its bytes say, else the declared media type, normalised. */
+export function resolveImageMediaType(data: Uint8Array | ArrayBuffer, declared: string): string {
+  const normalized = declared.trim().toLowerCase();
+  return sniffImageType(data) ?? MEDIA_TYPE_ALIASES[normalized] ?? normalized;
+}
+
+/** Pixel size from the header of a PNG, GIF or WebP image; null for other formats and truncated headers. */
+export function readImageSize(data: Uint8Array | ArrayBuffer): ImageSize | null {
+  const bytes = toBytes(data);
+  const size = (width: number, height: number) => (width > 0 && height > 0 ? { width, height } : null);
+  switch (sniffImageType(bytes)) {
+    case "image/png":
+      return bytes.length >= 24 ? size(uint32be(bytes, 16), uint32be(bytes, 20)) : null;
+    case "image/gif":
+      return bytes.length >= 10 ? size(uint16le(bytes, 6), uint16le(bytes, 8)) : null;
+    case "image/webp": {
+      const chunk = ascii(bytes, 12, 16);
+      // Lossy: a key frame with its start code, then 14-bit dimensions.
+      if (chunk === "VP8 " && bytes.length >= 30 && startsWith(bytes.subarray(23), [0x9d, 0x01, 0x2a])) {
+        return size(uint16le(bytes, 26) & 0x3fff, uint16le(bytes, 28) & 0x3fff);
+      }
+      // Lossless: a signature byte, then width - 1 and height - 1 packed into 14 bits each.
+      if (chunk === "VP8L" && bytes.length >= 25 && bytes[20] === 0x2f) {
+        const bits = uint16le(bytes, 21) | (uint16le(bytes, 23) << 16);
+        return size((bits & 0x3fff) + 1, ((bits >>> 14) & 0x3fff) + 1);
+      }
+      // Extended: canvas width - 1 and height - 1 as 24-bit values.
+      if (chunk === "VP8X" && bytes.length >= 30) return size(uint24le(bytes, 24) + 1, uint24le(bytes, 27) + 1);
+      return null;
+    }
+    default:
+      return null;
+  }
+}
@@ -2,24 +2,220 @@ import { AppError } from "@core-platform";
 import {
   BookMetadata,
   BookModel,
+  BookSummary,
+  CoverImage,
   ManifestItem,
   OpenEpubOptions,
+  OpenEpubsOptions,
+  PageProgression,
+  PageTarget,
   ResourceStore,
   SpineItemRef,
   TocItem,
+  ValidationReport,
+  ValidationWarning,
 } from "./types";
+import { splitAuthors } from "./authors";
+import { trimLeadingBlankLines } from "./content";
+import { resolveImageMediaType } from "./imageTypes";
 import { parseXml, findFirst, findNodes, getText, XmlNode } from "./xml";
 import { ZipArchive } from "./zip";
 
 type EpubInput = string | ArrayBuffer | Uint8Array;
 
 const CONTAINER_PATH = "META-INF/container.xml";
+const ENCRYPTION_PATH = "META-INF/encryption.xml";
+// Font obfuscation (IDPF and Adobe) is declared in encryption.xml too, but is not DRM.
+const FONT_OBFUSCATION_ALGORITHMS = new Set(["http://www.idpf.org/2008/embedding", "http://ns.adobe.com/pdf/enc#RC"]);
+const ENCRYPTION_METHOD_PATTERN = /<(?:[\w-]+:)?EncryptionMethod\b[^>]*?\sAlgorithm\s*=\s*(?:"([^"]*)"|'([^']*)')/gi;
 const TEXT_MEDIA_TYPE = /(text\/|xml|html)/i;
+export const DEFAULT_PARSE_CONCURRENCY = 4;
 
 export async function openEpub(source: EpubInput, options: OpenEpubOptions = {}): Promise<BookModel> {
   const buffer = await resolveSource(source);
   const archive = new ZipArchive(new Uint8Array(buffer));
+  // Metadata stays readable for library listings; only opening the content is refused.
+  await assertNotDrmProtected(archive);
+  const { opfPath, packageDoc } = await loadPackageDocument(archive);
 
+  const basePath = extractBasePath(opfPath);
+  const metadata = extractMetadata(packageDoc, options);
+  const manifest = extractManifest(packageDoc, basePath);
+  const spine = extractSpine(packageDoc);
+  const toc = await extractToc(archive, manifest);
+  const pageMap = await extractPageMap(archive, manifest);
+
+  const resources = createResourceStore(archive, basePath, manifest);
+
+  return {
+    id: options.id ?? crypto.randomUUID(),
+    metadata,
+    manifest,
+    spine,
+    toc,
+    resources,
+    coverHref: findCoverItem(packageDoc, manifest)?.href,
+    pageProgression: extractPageProgression(packageDoc),
+    pageMap,
+  };
+}
+
+/**
+ * Opens several books, at most `concurrency` at a time, so a large batch does
+ * not hold every archive in memory at once. Results keep the order of
+ * `sources`, and one unreadable book does not stop the others.
+ */
+export async function openEpubs(
+  sources: EpubInput[],
+  options: OpenEpubsOptions = {},
+): Promise<PromiseSettledResult<BookModel>[]> {
+  const { concurrency = DEFAULT_PARSE_CONCURRENCY, ...openOptions } = options;
+  const results = new Array<PromiseSettledResult<BookModel>>(sources.length);
+  let cursor = 0;
+  const worker = async () => {
+    while (cursor < sources.length) {
+      const index = cursor++;
+      try {
+        results[index] = { status: "fulfilled", value: await openEpub(sources[index]!, openOptions) };
+      } catch (reason) {
+        results[index] = { status: "rejected", reason };
+      }
+    }
+  };
+
+  const workers = Math.max(1, Math.min(Math.floor(concurrency) || 1, sources.length));
+  await Promise.all(Array.from({ length: workers }, worker));
+  return results;
+}
+
+/**
+ * Reads only the package document and the cover image, skipping navigation and
+ * chapter content. Intended for scanning many files at once (library views).
+ */
+export async function openEpubMetadata(source: EpubInput, options: OpenEpubOptions = {}): Promise<BookSummary> {
+  const buffer = await resolveSource(source);
+  const archive = new ZipArchive(new Uint8Array(buffer));
+  const { opfPath, packageDoc } = await loadPackageDocument(archive);
+
+  const manifest = extractManifest(packageDoc, extractBasePath(opfPath));
+  const coverItem = findCoverItem(packageDoc, manifest);
+  let cover: CoverImage | undefined;
+  if (coverItem) {
+    const data = await archive.arrayBuffer(coverItem.href);
+    if (data) {
+      // Trust the bytes over the manifest: covers labelled image/jpeg are often WebP or AVIF.
+      cover = { href: coverItem.href, mediaType: resolveImageMediaType(data, coverItem.mediaType), data };
+    }
+  }
+
+  return {
+    metadata: extractMetadata(packageDoc, options),
+    cover,
+  };
+}
+
+/**
+ * Inspects a book for structural problems that do not prevent opening it. Every
+ * finding is a warning; only an unreadable container or package document throws.
+ */
+export async function validateEpub(source: EpubInput): Promise<ValidationReport> {
+  const buffer = await resolveSource(source);
+  const archive = new ZipArchive(new Uint8Array(buffer));
+  const { opfPath, packageDoc } = await loadPackageDocument(archive);
+
+  const manifest = extractManifest(packageDoc, extractBasePath(opfPath));
+  const manifestItems = Object.values(manifest);
+  const spine = extractSpine(packageDoc);
+  const warnings: ValidationWarning[] = [];
+
+  const navItem = manifestItems.find(item => item.properties?.split(" ").includes("nav"));
+  const ncxItem = manifestItems.find(item => item.mediaType === "application/x-dtbncx+xml");
+  const toc = await extractToc(archive, manifest);
+  if (!navItem && !ncxItem) {
+    warnings.push({ code: "TOC_MISSING", message: "No navigation document or NCX is declared in the manifest" });
+  } else if (toc.length === 0) {
+    warnings.push({
+      code: "TOC_MISSING",
+      message: "The navigation document is missing or has no entries",
+      target: (navItem ?? ncxItem)!.href,
+    });
+  }
+
+  for (const ref of spine) {
+    if (!manifest[ref.idref]) {
+      warnings.push({
+        code: "SPINE_ITEM_NOT_IN_MANIFEST",
+        message: `Spine item "${ref.idref}" has no manifest entry`,
+        target: ref.idref,
+      });
+    }
+  }
+
+  const utf8 = new TextDecoder("utf-8", { fatal: true });
+  for (const ref of spine) {
+    const item = manifest[ref.idref];
+    if (!item) continue;
+    const bytes = await archive.bytes(item.href);
+    if (!bytes) continue;
+    try {
+      utf8.decode(bytes);
+    } catch {
+      warnings.push({
+        code: "CHAPTER_NOT_UTF8",
+        message: `Chapter "${item.href}" is not valid UTF-8`,
+        target: item.href,
+      });
+    }
+  }
+
+  const coverItem = findCoverItem(packageDoc, manifest);
+  if (!coverItem) {
+    warnings.push({ code: "COVER_MISSING", message: "No cover image is declared" });
+  }
+
+  const referencedIds = new Set(spine.map(ref => ref.idref));
+  for (const item of [navItem, ncxItem, coverItem]) {
+    if (item) referencedIds.add(item.id);
+  }
+  const textSources: string[] = [];
+  for (const item of manifestItems) {
+    if (TEXT_MEDIA_TYPE.test(item.mediaType)) {
+      textSources.push((await archive.text(item.href)) ?? "");
+    }
+  }
+  for (const item of manifestItems) {
+    if (referencedIds.has(item.id)) continue;
+    const fileName = item.href.split("/").pop() ?? item.href;
+    if (!textSources.some(text => text.includes(fileName))) {
+      warnings.push({
+        code: "MANIFEST_ITEM_UNREFERENCED",
+        message: `Manifest item "${item.id}" (${item.href}) is not referenced anywhere`,
+        target: item.id,
+      });
+    }
+  }
+
+  return { warnings };
+}
+
+/** Throws EPUB_DRM_PROTECTED when encryption.xml declares anything beyond font obfuscation. */
+async function assertNotDrmProtected(archive: ZipArchive) {
+  const encryptionXml = await archive.text(ENCRYPTION_PATH);
+  if (!encryptionXml) return;
+
+  const algorithms = Array.from(encryptionXml.matchAll(ENCRYPTION_METHOD_PATTERN), match => match[1] ?? match[2] ?? "");
+  if (algorithms.every(algorithm => FONT_OBFUSCATION_ALGORITHMS.has(algorithm))) {
+    return;
+  }
+
+  throw new AppError("EPUB content is encrypted", {
+    code: "EPUB_DRM_PROTECTED",
+    source: ENCRYPTION_PATH,
+    userMessage: "这本书受 DRM 保护，无法打开。",
+  });
+}
+
+async function loadPackageDocument(archive: ZipArchive): Promise<{ opfPath: string; packageDoc: XmlNode }> {
   const containerXml = await archive.text(CONTAINER_PATH);
   if (!containerXml) {
     throw new AppError("Unable to locate EPUB container descriptor", {
@@ -56,22 +252,7 @@ export async function openEpub(source: EpubInput, options: OpenEpubOptions = {})
     });
   }
 
-  const basePath = extractBasePath(opfPath);
-  const metadata = extractMetadata(packageDoc);
-  const manifest = extractManifest(packageDoc, basePath);
-  const spine = extractSpine(packageDoc);
-  const toc = await extractToc(archive, manifest);
-
-  const resources = createResourceStore(archive, basePath, manifest);
-
-  return {
-    id: options.id ?? crypto.randomUUID(),
-    metadata,
-    manifest,
-    spine,
-    toc,
-    resources,
-  };
+  return { opfPath, packageDoc };
 }
 
 async function resolveSource(source: EpubInput): Promise<ArrayBuffer> {
@@ -115,23 +296,77 @@ function extractBasePath(opfPath: string): string {
   return parts.join("/");
 }
 
-function extractMetadata(packageDoc: XmlNode): BookMetadata {
+function extractMetadata(packageDoc: XmlNode, options: OpenEpubOptions = {}): BookMetadata {
   const metadataNode = findFirst(packageDoc, "metadata");
   if (!metadataNode) {
     return {};
   }
 
   const readText = (tagName: string) => getText(findFirst(metadataNode, tagName));
+  const creators = findNodes(metadataNode, "dc:creator")
+    .map(node => getText(node))
+    .filter((value): value is string => Boolean(value));
 
   return {
-    title: readText("dc:title"),
+    ...extractTitles(metadataNode),
     creator: readText("dc:creator"),
+    authors: splitAuthors(creators, { normalizeAuthorOrder: options.normalizeAuthorOrder }),
     language: readText("dc:language"),
     publisher: readText("dc:publisher"),
     description: readText("dc:description"),
+    identifier: readText("dc:identifier"),
+    date: readText("dc:date"),
+    rights: readText("dc:rights"),
+    ...extractSeries(metadataNode),
   };
 }
 
+/**
+ * EPUB 3 can mark each `dc:title` with a `title-type` refinement. When it does,
+ * `title` is the main title and `subtitle` the first subtitle; otherwise
+ * `title` is the first `dc:title` as written. Any other titles, such as the
+ * original-language title of a translation, are kept as `alternateTitles`.
+ */
+function extractTitles(metadataNode: XmlNode): Pick<BookMetadata, "title" | "subtitle" | "alternateTitles"> {
+  const titles = findNodes(metadataNode, "dc:title");
+  const metas = findNodes(metadataNode, "meta");
+  const typed = titles.map(node => {
+    const id = node.attributes["id"];
+    const refinement = id
+      ? metas.find(meta => meta.attributes["refines"] === `#${id}` && meta.attributes["property"] === "title-type")
+      : undefined;
+    return { text: getText(node), type: getText(refinement ?? null)?.toLowerCase() };
+  });
+
+  const subtitle = typed.find(entry => entry.type === "subtitle" && entry.text)?.text;
+  const main =
+    typed.find(entry => entry.type === "main" && entry.text)?.text ??
+    typed.find(entry => entry.type !== "subtitle" && entry.text)?.text;
+  const chosen = subtitle && main ? { title: main, subtitle } : { title: getText(titles[0] ?? null) };
+  const alternateTitles = [
+    ...new Set(typed.map(entry => entry.text).filter((text): text is string => !!text)),
+  ].filter(text => text !== chosen.title && text !== chosen.subtitle);
+  return alternateTitles.length > 0 ? { ...chosen, alternateTitles } : chosen;
+}
+
+function extractSeries(metadataNode: XmlNode): Pick<BookMetadata, "series" | "seriesIndex"> {
+  const metas = findNodes(metadataNode, "meta");
+  const collection = metas.find(node => node.attributes["property"] === "belongs-to-collection");
+  const collectionName = getText(collection ?? null);
+  if (collection && collectionName) {
+    const id = collection.attributes["id"];
+    const position = id
+      ? metas.find(node => node.attributes["refines"] === `#${id}` && node.attributes["property"] === "group-position")
+      : undefined;
+    return { series: collectionName, seriesIndex: getText(position ?? null) };
+  }
+
+  const calibreContent = (name: string) =>
+    metas.find(node => node.attributes["name"] === name)?.attributes["content"] || undefined;
+  const series = calibreContent("calibre:series");
+  return series ? { series, seriesIndex: calibreContent("calibre:series_index") } : {};
+}
+
 function extractManifest(packageDoc: XmlNode, basePath: string): Record<string, ManifestItem> {
   const manifestNode = findFirst(packageDoc, "manifest");
   if (!manifestNode) {
@@ -161,6 +396,20 @@ function extractManifest(packageDoc: XmlNode, basePath: string): Record<string,
   return manifest;
 }
 
+function findCoverItem(packageDoc: XmlNode, manifest: Record<string, ManifestItem>): ManifestItem | undefined {
+  const items = Object.values(manifest);
+  const epub3Cover = items.find(item => item.properties?.split(" ").includes("cover-image"));
+  if (epub3Cover) {
+    return epub3Cover;
+  }
+
+  // EPUB 2 books point at the cover through <meta name="cover" content="manifest-id"/>.
+  const coverMeta = findNodes(packageDoc, "meta").find(node => node.attributes["name"] === "cover");
+  const coverId = coverMeta?.attributes["content"];
+  const legacyCover = coverId ? manifest[coverId] : undefined;
+  return legacyCover?.mediaType.startsWith("image/") ? legacyCover : undefined;
+}
+
 function extractSpine(packageDoc: XmlNode): SpineItemRef[] {
   const spineNode = findFirst(packageDoc, "spine");
   if (!spineNode) {
@@ -180,6 +429,12 @@ function extractSpine(packageDoc: XmlNode): SpineItemRef[] {
     .filter((item): item is SpineItemRef => item !== null);
 }
 
+function extractPageProgression(packageDoc: XmlNode): PageProgression | undefined {
+  // "default" and anything unrecognised leave the direction to the content's language.
+  const direction = findFirst(packageDoc, "spine")?.attributes["page-progression-direction"]?.trim().toLowerCase();
+  return direction === "ltr" || direction === "rtl" ? direction : undefined;
+}
+
 async function extractToc(archive: ZipArchive, manifest: Record<string, ManifestItem>): Promise<TocItem[]> {
   const navManifestItem = Object.values(manifest).find(item => item.properties?.split(" ").includes("nav"));
   if (navManifestItem) {
@@ -206,6 +461,42 @@ async function extractToc(archive: ZipArchive, manifest: Record<string, Manifest
   return [];
 }
 
+async function extractPageMap(archive: ZipArchive, manifest: Record<string, ManifestItem>): Promise<PageTarget[]> {
+  const navManifestItem = Object.values(manifest).find(item => item.properties?.split(" ").includes("nav"));
+  const navContent = navManifestItem ? await archive.text(navManifestItem.href) : null;
+  const navDoc = navContent ? parseXml(navContent) : null;
+  const pageListNode = findNodes(navDoc, "nav").find(node => {
+    const type = node.attributes["epub:type"] ?? node.attributes["role"];
+    return type?.includes("page-list") || type?.includes("pagelist");
+  });
+  if (navManifestItem && pageListNode) {
+    const basePath = extractBasePath(navManifestItem.href);
+    return findNodes(pageListNode, "a").flatMap(anchor => {
+      const href = anchor.attributes["href"];
+      const label = getText(anchor);
+      return href && label ? [{ label, href: normalizeRelativePath(basePath, href) }] : [];
+    });
+  }
+
+  // EPUB 2 books, and EPUB 3 books that kept their page list only in the NCX.
+  const ncxManifestItem = Object.values(manifest).find(item => item.mediaType === "application/x-dtbncx+xml");
+  const ncxContent = ncxManifestItem ? await archive.text(ncxManifestItem.href) : null;
+  const pageList = findFirst(ncxContent ? parseXml(ncxContent) : null, "pagelist");
+  if (!ncxManifestItem || !pageList) return [];
+  const basePath = extractBasePath(ncxManifestItem.href);
+  const targets = findNodes(pageList, "pagetarget").map(target => {
+    const playOrder = Number.parseInt(target.attributes["playorder"] ?? "", 10);
+    return {
+      label: getText(findFirst(target, "text") ?? null) ?? target.attributes["value"] ?? "",
+      href: findFirst(target, "content")?.attributes["src"] ?? "",
+      order: Number.isNaN(playOrder) ? undefined : playOrder,
+    };
+  });
+  return sortByPlayOrder(targets)
+    .filter(target => target.label && target.href)
+    .map(target => ({ label: target.label, href: normalizeRelativePath(basePath, target.href) }));
+}
+
 function parseNavDocument(doc: XmlNode, basePath: string): TocItem[] {
   const navNodes = findNodes(doc, "nav");
   const tocNode = navNodes.find(node => {
@@ -261,17 +552,27 @@ function parseNcxDocument(doc: XmlNode, basePath: string): TocItem[] {
 
     const rawHref = contentNode?.attributes["src"] ?? "";
     const childrenPoints = point.children.filter(child => child.name === "navpoint");
+    const playOrder = Number.parseInt(point.attributes["playorder"] ?? "", 10);
 
     return {
       id: point.attributes["id"] ?? crypto.randomUUID(),
       label: (getText(labelNode) ?? "Chapter").trim(),
       href: rawHref ? normalizeRelativePath(basePath, rawHref) : "",
-      children: childrenPoints.length > 0 ? childrenPoints.map(buildItem) : undefined,
+      order: Number.isNaN(playOrder) ? undefined : playOrder,
+      children: childrenPoints.length > 0 ? sortByPlayOrder(childrenPoints.map(buildItem)) : undefined,
     };
   };
 
   const topLevelPoints = navMap.children.filter(child => child.name === "navpoint");
-  return topLevelPoints.map(buildItem);
+  return sortByPlayOrder(topLevelPoints.map(buildItem));
+}
+
+function sortByPlayOrder<T extends { order?: number }>(items: T[]): T[] {
+  // Only reorder when every sibling declares a playOrder; partial data keeps document order.
+  if (!items.every(item => item.order !== undefined)) {
+    return items;
+  }
+  return [...items].sort((a, b) => a.order! - b.order!);
 }
 
 function createResourceStore(
@@ -290,7 +591,8 @@ function createResourceStore(
       }
 
       if (TEXT_MEDIA_TYPE.test(manifestItem.mediaType)) {
-        return archive.text(normalized);
+        const text = await archive.text(normalized);
+        return text === null ? null : trimLeadingBlankLines(text);
       }
 
       return archive.arrayBuffer(normalized);
@@ -353,3 +655,11 @@ function applySuffix(path: string, suffix: string): string {
 }
 
 export * from "./types";
+export * from "./content";
+export * from "./citation";
+export * from "./authors";
+export * from "./dump";
+export * from "./writer";
+export * from "./metadataFormat";
+export * from "./chapters";
+export * from "./imageTypes";
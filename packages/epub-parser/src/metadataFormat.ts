@@ -0,0 +1,49 @@
+const DATE_PART_PATTERN = /^(\d{4})(?:-(\d{1,2})(?:-(\d{1,2}))?)?/;
+// Optional ISO time of day and zone after the date.
+const TIME_PART_PATTERN = /^(?:[T ]\d{1,2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?)?$/i;
+
+export interface ParsedEpubDate {
+  year: number;
+  /** 1-based month, when the date carries one. */
+  month?: number;
+  day?: number;
+}
+
+/** Reads the calendar parts of a `dc:date` value, ignoring any time of day or zone. */
+export function parseEpubDate(raw: string): ParsedEpubDate | null {
+  const value = raw.trim();
+  const match = value.match(DATE_PART_PATTERN);
+  if (!match || !TIME_PART_PATTERN.test(value.slice(match[0].length))) return null;
+  const year = Number(match[1]);
+  const month = match[2] ? Number(match[2]) : undefined;
+  const day = match[3] ? Number(match[3]) : undefined;
+  if (month !== undefined && (month < 1 || month > 12)) return null;
+  if (day !== undefined && (day < 1 || day > 31)) return null;
+  return { year, month, day };
+}
+
+/**
+ * Formats a publication date for display, e.g. "2019年3月" for
+ * `2019-03-01T00:00:00Z`. The day is dropped since most books only record the
+ * month; values that don't parse are returned verbatim.
+ */
+export function formatPublicationDate(raw: string, locale = "zh-CN"): string {
+  const parsed = parseEpubDate(raw);
+  if (!parsed) return raw.trim();
+  // Built in UTC so the zone of the runtime can never move the date into another month;
+  // setUTCFullYear keeps years below 100 from being read as 19xx.
+  const date = new Date(Date.UTC(parsed.year, (parsed.month ?? 1) - 1, 1));
+  date.setUTCFullYear(parsed.year);
+  return new Intl.DateTimeFormat(locale, {
+    year: "numeric",
+    ...(parsed.month !== undefined ? { month: "long" } : {}),
+    timeZone: "UTC",
+  }).format(date);
+}
+
+/** Formats a series position, turning calibre's "2.0" into "2" while keeping real fractions like "2.5". */
+export function formatSeriesIndex(raw: string): string {
+  const value = raw.trim();
+  if (!/^\d+(?:\.\d+)?$/.test(value)) return value;
+  return String(Number(value));
+}
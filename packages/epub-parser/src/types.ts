@@ -1,10 +1,27 @@
 export interface BookMetadata {
+  /** Main title; the whole `dc:title` unless EPUB 3 `title-type` refinements split it. */
   title?: string;
+  /** `dc:title` refined as `title-type` "subtitle". */
+  subtitle?: string;
+  /** The remaining `dc:title` entries, e.g. the original or a romanized title, in package order. */
+  alternateTitles?: string[];
+  /** First `dc:creator`, as written in the package document. */
   creator?: string;
+  /** Every `dc:creator`, split on common separators and deduplicated. */
+  authors?: string[];
   language?: string;
   publisher?: string;
   description?: string;
-  [key: string]: string | undefined;
+  identifier?: string;
+  /** Raw `dc:date`, usually an ISO 8601 date or year. */
+  date?: string;
+  /** `dc:rights` statement; may span several paragraphs. */
+  rights?: string;
+  /** Series name, from EPUB 3 `belongs-to-collection` or calibre's `calibre:series`. */
+  series?: string;
+  /** Raw position within the series, e.g. "2" or calibre's "2.0". */
+  seriesIndex?: string;
+  [key: string]: string | string[] | undefined;
 }
 
 export interface ManifestItem {
@@ -23,6 +40,8 @@ export interface TocItem {
   id: string;
   label: string;
   href: string;
+  /** NCX `playOrder`, when the navigation document declares one. */
+  order?: number;
   children?: TocItem[];
 }
 
@@ -39,8 +58,58 @@ export interface BookModel {
   manifest: Record<string, ManifestItem>;
   toc: TocItem[];
   resources: ResourceStore;
+  /** Archive path of the cover image, when the package declares one. */
+  coverHref?: string;
+  /** The spine's `page-progression-direction`; absent when the book leaves it to the reader. */
+  pageProgression?: PageProgression;
+  /** Print page numbers from the navigation page list, in book order; empty when the book has none. */
+  pageMap?: PageTarget[];
+}
+
+export interface PageTarget {
+  /** The page number as printed, e.g. "12" or "xiv". */
+  label: string;
+  href: string;
+}
+
+export type PageProgression = "ltr" | "rtl";
+
+export interface CoverImage {
+  href: string;
+  mediaType: string;
+  data: ArrayBuffer;
+}
+
+export interface BookSummary {
+  metadata: BookMetadata;
+  cover?: CoverImage;
+}
+
+export type ValidationWarningCode =
+  | "TOC_MISSING"
+  | "SPINE_ITEM_NOT_IN_MANIFEST"
+  | "MANIFEST_ITEM_UNREFERENCED"
+  | "CHAPTER_NOT_UTF8"
+  | "COVER_MISSING";
+
+export interface ValidationWarning {
+  code: ValidationWarningCode;
+  message: string;
+  /** Manifest id, spine idref or archive path the warning refers to. */
+  target?: string;
+}
+
+export interface ValidationReport {
+  warnings: ValidationWarning[];
 }
 
 export interface OpenEpubOptions {
   id?: string;
+  /** Rewrite "Last, First" creators as "First Last" in `metadata.authors`. */
+  normalizeAuthorOrder?: boolean;
+}
+
+export interface OpenEpubsOptions extends Omit<OpenEpubOptions, "id"> {
+  /** How many books are read and parsed at once; defaults to `DEFAULT_PARSE_CONCURRENCY`. */
+  concurrency?: number;
 }
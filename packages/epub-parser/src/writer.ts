@@ -0,0 +1,168 @@
+import { AppError } from "@core-platform";
+import type { BookMetadata } from "./types";
+import { ZipWriter } from "./zipWriter";
+
+export interface ExportChapter {
+  title: string;
+  /** Well-formed XHTML body content, e.g. from `toSemanticHtml`. */
+  html: string;
+}
+
+export interface ExportBook {
+  metadata: BookMetadata;
+  chapters: ExportChapter[];
+}
+
+const IMAGE_EXTENSIONS: Record<string, string> = {
+  "image/jpeg": "jpg",
+  "image/png": "png",
+  "image/gif": "gif",
+  "image/webp": "webp",
+  "image/avif": "avif",
+  "image/svg+xml": "svg",
+};
+
+const DATA_URL_IMAGE_PATTERN = /(<img\b[^>]*?\ssrc=")data:(image\/[\w.+-]+);base64,([^"]*)"/gi;
+
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+function decodeBase64(value: string): Uint8Array {
+  const binary = atob(value);
+  const bytes = new Uint8Array(binary.length);
+  for (let index = 0; index < binary.length; index += 1) {
+    bytes[index] = binary.charCodeAt(index);
+  }
+  return bytes;
+}
+
+function xhtmlDocument(title: string, body: string, language: string, extraNamespaces = ""): string {
+  const lang = escapeXml(language);
+  return `<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml"${extraNamespaces} xml:lang="${lang}" lang="${lang}">
+<head><title>${escapeXml(title)}</title></head>
+<body>
+${body}
+</body>
+</html>
+`;
+}
+
+/**
+ * Writes a minimal EPUB 3 from already-simplified chapters: one XHTML file per
+ * chapter, a navigation document listing them, and an OPF with the metadata.
+ * Images given as data URLs are stored as files in the archive.
+ */
+export function writeEpub(book: ExportBook): Uint8Array {
+  if (book.chapters.length === 0) {
+    throw new AppError("Cannot export a book without chapters", {
+      code: "EPUB_EXPORT_EMPTY",
+      userMessage: "这本书没有可导出的章节。",
+    });
+  }
+
+  const { metadata } = book;
+  const language = metadata.language ?? "und";
+  const title = metadata.title ?? "Untitled";
+  const zip = new ZipWriter();
+  const images: { href: string; mediaType: string }[] = [];
+
+  zip.add("mimetype", "application/epub+zip");
+  zip.add(
+    "META-INF/container.xml",
+    `<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+`,
+  );
+
+  const chapterFiles = book.chapters.map((chapter, index) => {
+    const href = `chapter-${index + 1}.xhtml`;
+    const body = chapter.html.replace(DATA_URL_IMAGE_PATTERN, (match, prefix: string, type: string, data: string) => {
+      const mediaType = type.toLowerCase();
+      const extension = IMAGE_EXTENSIONS[mediaType];
+      if (!extension) return match;
+      const imageHref = `images/image-${images.length + 1}.${extension}`;
+      zip.add(`OEBPS/${imageHref}`, decodeBase64(data));
+      images.push({ href: imageHref, mediaType });
+      return `${prefix}${imageHref}"`;
+    });
+    zip.add(`OEBPS/${href}`, xhtmlDocument(chapter.title, body, language));
+    return { id: `chapter-${index + 1}`, href, title: chapter.title };
+  });
+
+  const navItems = chapterFiles
+    .map(file => `      <li><a href="${file.href}">${escapeXml(file.title)}</a></li>`)
+    .join("\n");
+  zip.add(
+    "OEBPS/nav.xhtml",
+    xhtmlDocument(
+      title,
+      `<nav epub:type="toc" id="toc">
+  <h1>${escapeXml(title)}</h1>
+  <ol>
+${navItems}
+  </ol>
+</nav>`,
+      language,
+      ' xmlns:epub="http://www.idpf.org/2007/ops"',
+    ),
+  );
+
+  const authors = metadata.authors?.length ? metadata.authors : metadata.creator ? [metadata.creator] : [];
+  const optionalFields = (["publisher", "date", "rights", "description"] as const).filter(key => metadata[key]);
+  const identifier = metadata.identifier ?? `urn:uuid:${crypto.randomUUID()}`;
+  const metadataLines = [
+    `<dc:identifier id="book-id">${escapeXml(identifier)}</dc:identifier>`,
+    ...(metadata.subtitle
+      ? [
+          `<dc:title id="title">${escapeXml(title)}</dc:title>`,
+          '<meta refines="#title" property="title-type">main</meta>',
+          `<dc:title id="subtitle">${escapeXml(metadata.subtitle)}</dc:title>`,
+          '<meta refines="#subtitle" property="title-type">subtitle</meta>',
+        ]
+      : [`<dc:title>${escapeXml(title)}</dc:title>`]),
+    `<dc:language>${escapeXml(language)}</dc:language>`,
+    ...authors.map(author => `<dc:creator>${escapeXml(author)}</dc:creator>`),
+    ...optionalFields.map(key => `<dc:${key}>${escapeXml(metadata[key]!)}</dc:${key}>`),
+    `<meta property="dcterms:modified">${new Date().toISOString().replace(/\.\d+Z$/, "Z")}</meta>`,
+  ]
+    .map(line => `    ${line}`)
+    .join("\n");
+  const manifest = [
+    '    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>',
+    ...chapterFiles.map(file => `    <item id="${file.id}" href="${file.href}" media-type="application/xhtml+xml"/>`),
+    ...images.map(
+      (image, index) => `    <item id="image-${index + 1}" href="${image.href}" media-type="${image.mediaType}"/>`,
+    ),
+  ].join("\n");
+  const spine = chapterFiles.map(file => `    <itemref idref="${file.id}"/>`).join("\n");
+
+  zip.add(
+    "OEBPS/content.opf",
+    `<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+${metadataLines}
+  </metadata>
+  <manifest>
+${manifest}
+  </manifest>
+  <spine>
+${spine}
+  </spine>
+</package>
+`,
+  );
+
+  return zip.finish();
+}
@@ -1,3 +1,5 @@
+import { joinTextRuns, spacingRulesFor } from "@core-platform";
+
 export interface XmlNode {
   name: string;
   attributes: Record<string, string>;
@@ -7,10 +9,19 @@ export interface XmlNode {
 
 const TEXT_NODE = "#text";
 
+interface OpenText {
+  node?: XmlNode;
+  spaced: boolean;
+  language?: string;
+}
+
 export function parseXml(xml: string): XmlNode | null {
   const sanitized = xml.replace(/\r\n/g, "\n");
   const root: XmlNode = { name: "__root__", attributes: {}, children: [] };
   const stack: XmlNode[] = [root];
+  // Per open element: its text node (so merging text never scans its children again), whether
+  // whitespace came after its last text, and the language in effect for spacing rules.
+  const textStack: OpenText[] = [{ spaced: false }];
   const tokenRegex = /<[^>]+>|[^<]+/g;
   let match: RegExpExecArray | null;
 
@@ -22,6 +33,7 @@ export function parseXml(xml: string): XmlNode | null {
 
     if (token.startsWith("</")) {
       stack.pop();
+      textStack.pop();
       continue;
     }
 
@@ -42,22 +54,29 @@ export function parseXml(xml: string): XmlNode | null {
 
       if (!isSelfClosing) {
         stack.push(node);
+        const language = node.attributes["xml:lang"] ?? node.attributes["lang"];
+        textStack.push({ spaced: false, language: language ?? textStack[textStack.length - 1]?.language });
       }
       continue;
     }
 
+    const depth = stack.length - 1;
+    const open = textStack[depth]!;
     const text = token.trim();
     if (!text) {
+      open.spaced = true;
       continue;
     }
 
-    const parent = stack[stack.length - 1];
-    const existingTextChild = parent.children.find(child => child.name === TEXT_NODE);
-    if (existingTextChild) {
-      existingTextChild.text = `${existingTextChild.text ?? ""}${text}`;
+    // All of an element's text is merged into one text node, placed where the first run of text appeared.
+    if (open.node) {
+      const spaced = open.spaced || /^\s/.test(token);
+      open.node.text = joinTextRuns(open.node.text ?? "", text, spaced, spacingRulesFor("smart", open.language));
     } else {
-      parent.children.push({ name: TEXT_NODE, attributes: {}, children: [], text });
+      open.node = { name: TEXT_NODE, attributes: {}, children: [], text };
+      stack[depth].children.push(open.node);
     }
+    open.spaced = /\s$/.test(token);
   }
 
   return root.children[0] ?? null;
@@ -33,6 +33,10 @@ export class ZipArchive {
     return content ? content.buffer.slice(content.byteOffset, content.byteOffset + content.byteLength) : null;
   }
 
+  async bytes(path: string): Promise<Uint8Array | null> {
+    return this.read(path);
+  }
+
   listPaths(): string[] {
     return Array.from(this.entries.keys());
   }
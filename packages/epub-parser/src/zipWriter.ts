@@ -0,0 +1,108 @@
+const LOC_SIGNATURE = 0x04034b50;
+const CEN_SIGNATURE = 0x02014b50;
+const EOCD_SIGNATURE = 0x06054b50;
+// Bit 11: file names are UTF-8.
+const UTF8_FLAG = 0x0800;
+// DOS date for 1980-01-01, the earliest a ZIP can express; entries carry no real timestamp.
+const DOS_EPOCH_DATE = (1 << 5) | 1;
+
+const CRC_TABLE = (() => {
+  const table = new Uint32Array(256);
+  for (let n = 0; n < 256; n++) {
+    let c = n;
+    for (let k = 0; k < 8; k++) {
+      c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+    }
+    table[n] = c >>> 0;
+  }
+  return table;
+})();
+
+export function crc32(data: Uint8Array): number {
+  let crc = 0xffffffff;
+  for (const byte of data) {
+    crc = CRC_TABLE[(crc ^ byte) & 0xff]! ^ (crc >>> 8);
+  }
+  return (crc ^ 0xffffffff) >>> 0;
+}
+
+interface WrittenEntry {
+  name: Uint8Array;
+  crc: number;
+  size: number;
+  offset: number;
+}
+
+/**
+ * Minimal ZIP writer that stores entries uncompressed. EPUB requires `mimetype`
+ * to be the first, uncompressed entry, and storing everything keeps this free of
+ * Node-only compression APIs so it also runs in the browser.
+ */
+export class ZipWriter {
+  private readonly chunks: Uint8Array[] = [];
+  private readonly entries: WrittenEntry[] = [];
+  private readonly encoder = new TextEncoder();
+  private offset = 0;
+
+  add(path: string, content: string | Uint8Array) {
+    const data = typeof content === "string" ? this.encoder.encode(content) : content;
+    const name = this.encoder.encode(path);
+    const crc = crc32(data);
+
+    const header = new DataView(new ArrayBuffer(30));
+    header.setUint32(0, LOC_SIGNATURE, true);
+    header.setUint16(4, 20, true);
+    header.setUint16(6, UTF8_FLAG, true);
+    header.setUint16(8, 0, true);
+    header.setUint16(12, DOS_EPOCH_DATE, true);
+    header.setUint32(14, crc, true);
+    header.setUint32(18, data.byteLength, true);
+    header.setUint32(22, data.byteLength, true);
+    header.setUint16(26, name.byteLength, true);
+
+    this.entries.push({ name, crc, size: data.byteLength, offset: this.offset });
+    this.push(new Uint8Array(header.buffer), name, data);
+  }
+
+  finish(): Uint8Array {
+    const directoryOffset = this.offset;
+    for (const entry of this.entries) {
+      const header = new DataView(new ArrayBuffer(46));
+      header.setUint32(0, CEN_SIGNATURE, true);
+      header.setUint16(4, 20, true);
+      header.setUint16(6, 20, true);
+      header.setUint16(8, UTF8_FLAG, true);
+      header.setUint16(10, 0, true);
+      header.setUint16(14, DOS_EPOCH_DATE, true);
+      header.setUint32(16, entry.crc, true);
+      header.setUint32(20, entry.size, true);
+      header.setUint32(24, entry.size, true);
+      header.setUint16(28, entry.name.byteLength, true);
+      header.setUint32(42, entry.offset, true);
+      this.push(new Uint8Array(header.buffer), entry.name);
+    }
+
+    const end = new DataView(new ArrayBuffer(22));
+    end.setUint32(0, EOCD_SIGNATURE, true);
+    end.setUint16(8, this.entries.length, true);
+    end.setUint16(10, this.entries.length, true);
+    end.setUint32(12, this.offset - directoryOffset, true);
+    end.setUint32(16, directoryOffset, true);
+    this.push(new Uint8Array(end.buffer));
+
+    const output = new Uint8Array(this.offset);
+    let position = 0;
+    for (const chunk of this.chunks) {
+      output.set(chunk, position);
+      position += chunk.byteLength;
+    }
+    return output;
+  }
+
+  private push(...chunks: Uint8Array[]) {
+    for (const chunk of chunks) {
+      this.chunks.push(chunk);
+      this.offset += chunk.byteLength;
+    }
+  }
+}
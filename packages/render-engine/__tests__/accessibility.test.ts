@@ -0,0 +1,38 @@
+import { describe, expect, it } from "bun:test";
+import { accessibleRole, applyEpubTypeRoles, listAccessibleElements, readAttributes } from "@render-engine";
+
+describe("accessibility roles", () => {
+  it("exposes headings with their level and links with the link role", () => {
+    const html = [
+      '<h1 epub:type="title">Chapter One</h1>',
+      '<p role="heading" aria-level="3">A run-in heading</p>',
+      '<p>See <a href="#map">the map</a> or <a id="anchor">here</a>.</p>',
+      "<ul><li>One</li></ul>",
+    ].join("");
+
+    expect(listAccessibleElements(html)).toEqual([
+      { tag: "h1", role: "heading", level: 1 },
+      { tag: "p", role: "heading", level: 3 },
+      { tag: "a", role: "link" },
+      { tag: "ul", role: "list" },
+      { tag: "li", role: "listitem" },
+    ]);
+  });
+
+  it("maps epub:type to DPUB-ARIA roles without overriding explicit ones", () => {
+    const html = applyEpubTypeRoles(
+      '<body epub:type="bodymatter"><section epub:type="bodymatter chapter">' +
+        '<p>Text<a epub:type="noteref" href="#n1">1</a></p>' +
+        '<span epub:type="pagebreak" id="p7"/>' +
+        '<aside epub:type="footnote" role="note" id="n1">Note.</aside></section></body>',
+    );
+
+    expect(html).toBe(
+      '<body epub:type="bodymatter"><section role="doc-chapter" epub:type="bodymatter chapter">' +
+        '<p>Text<a role="doc-noteref" epub:type="noteref" href="#n1">1</a></p>' +
+        '<span role="doc-pagebreak" epub:type="pagebreak" id="p7"/>' +
+        '<aside epub:type="footnote" role="note" id="n1">Note.</aside></section></body>',
+    );
+    expect(accessibleRole("a", readAttributes(' epub:type="noteref" href="#n1"'))).toEqual({ role: "doc-noteref" });
+  });
+});
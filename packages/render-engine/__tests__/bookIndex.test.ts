@@ -0,0 +1,48 @@
+import { describe, expect, it } from "bun:test";
+import { indexEntryTargets, isIndexChapter, linkIndexEntries, parseIndexLine } from "@render-engine";
+
+const pageMap = [
+  { label: "ix", href: "text/preface.xhtml#pix" },
+  { label: "42", href: "text/ch3.xhtml#p42" },
+  { label: "57", href: "text/ch4.xhtml#p57" },
+];
+
+describe("book index", () => {
+  it("reads an index line into its term and two jump targets", () => {
+    const entry = parseIndexLine("Widgets, 42, 57");
+
+    expect(entry).toEqual({ term: "Widgets", pages: ["42", "57"] });
+    expect(indexEntryTargets(entry!, pageMap).map(target => target.href)).toEqual([
+      "text/ch3.xhtml#p42",
+      "text/ch4.xhtml#p57",
+    ]);
+  });
+
+  it("keeps commas in the term, takes the first page of ranges and accepts roman numerals", () => {
+    expect(parseIndexLine("Smith, John, ix, 42–45")).toEqual({ term: "Smith, John", pages: ["ix", "42"] });
+    expect(parseIndexLine("Widgets are covered in chapter 3")).toBeNull();
+    expect(parseIndexLine("civil, 12")?.pages).toEqual(["12"]);
+    expect(parseIndexLine("Smith, civil")).toBeNull();
+  });
+
+  it("recognizes an index by its title or by lines that are nearly all entries", () => {
+    const entries = Array.from({ length: 20 }, (_, index) => `<p>Term ${index}, ${index + 1}</p>`).join("");
+
+    expect(isIndexChapter("<h1>Index</h1><p>Widgets, 42, 57</p>")).toBe(true);
+    expect(isIndexChapter("<p>Widgets, 42</p>", { semanticType: "backmatter index" })).toBe(true);
+    expect(isIndexChapter(`<h1>Appendix</h1>${entries}`)).toBe(true);
+    expect(isIndexChapter("<h1>Chapter 1</h1><p>In 1984, 12</p><p>It was a bright cold day.</p>")).toBe(false);
+    expect(isIndexChapter("<h1>Index</h1><p>No entries here.</p>")).toBe(false);
+  });
+
+  it("links the page numbers the page map knows and leaves the rest as text", () => {
+    const html = '<p>Widgets, 42, 57, 99</p><p>Gears, <a href="ch4.xhtml#p57">57</a></p>';
+
+    expect(linkIndexEntries(html, pageMap)).toBe(
+      '<p>Widgets, <a href="#" class="reader-index-link" data-book-href="text/ch3.xhtml#p42">42</a>, ' +
+        '<a href="#" class="reader-index-link" data-book-href="text/ch4.xhtml#p57">57</a>, 99</p>' +
+        '<p>Gears, <a href="ch4.xhtml#p57">57</a></p>',
+    );
+    expect(linkIndexEntries(html, [])).toBe(html);
+  });
+});
@@ -0,0 +1,29 @@
+import { describe, expect, it } from "bun:test";
+import { buildSyntheticPages, syntheticPageAt } from "@render-engine";
+
+describe("synthetic book pages", () => {
+  it("starts every chapter on a new page", () => {
+    // 1000 chars -> 1 page, 3600 -> 2, 3601 -> 3, empty -> 1.
+    const pages = buildSyntheticPages([1000, 3600, 3601, 0], 1800);
+
+    expect(pages.chapterStarts).toEqual([0, 1, 3, 6]);
+    expect(pages.totalPages).toBe(7);
+  });
+
+  it("maps a chapter and a position within it to a page number", () => {
+    const pages = buildSyntheticPages([1000, 3600, 3601, 0], 1800);
+
+    expect(syntheticPageAt(pages, 0, 0)).toBe(1);
+    expect(syntheticPageAt(pages, 1, 0)).toBe(2);
+    expect(syntheticPageAt(pages, 1, 0.5)).toBe(3);
+    expect(syntheticPageAt(pages, 2, 0.99)).toBe(6);
+    expect(syntheticPageAt(pages, 2, 1)).toBe(6);
+    expect(syntheticPageAt(pages, 3, 0.5)).toBe(7);
+  });
+
+  it("handles an empty book", () => {
+    const pages = buildSyntheticPages([]);
+    expect(pages.totalPages).toBe(0);
+    expect(syntheticPageAt(pages, 0, 0)).toBe(0);
+  });
+});
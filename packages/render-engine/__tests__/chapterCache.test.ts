@@ -0,0 +1,123 @@
+import { describe, expect, it } from "bun:test";
+import { ChapterCache, type ScheduleTask } from "@render-engine";
+
+function createCountingBuilder() {
+  const builds: number[] = [];
+  return {
+    builds,
+    build: (index: number) => {
+      builds.push(index);
+      return `chapter-${index}`;
+    },
+  };
+}
+
+const runImmediately: ScheduleTask = task => {
+  task();
+  return () => {};
+};
+
+describe("ChapterCache", () => {
+  it("has the next chapter ready after prefetching around the current one", () => {
+    const { builds, build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: 4, build, schedule: runImmediately });
+
+    expect(cache.get(0)).toBe("chapter-0");
+    cache.prefetchAround(0, 5);
+
+    expect(cache.has(1)).toBe(true);
+    expect(builds).toEqual([0, 1]);
+
+    expect(cache.get(1)).toBe("chapter-1");
+    expect(builds).toEqual([0, 1]);
+  });
+
+  it("cancels queued prefetches when the reader jumps elsewhere", () => {
+    const { builds, build } = createCountingBuilder();
+    const queue: Array<{ task: () => void; cancelled: boolean }> = [];
+    const cache = new ChapterCache({
+      capacity: 4,
+      build,
+      schedule: task => {
+        const entry = { task, cancelled: false };
+        queue.push(entry);
+        return () => {
+          entry.cancelled = true;
+        };
+      },
+    });
+
+    cache.get(3);
+    cache.prefetchAround(3, 20);
+    cache.get(15);
+    cache.prefetchAround(15, 20);
+    queue.filter(entry => !entry.cancelled).forEach(entry => entry.task());
+
+    expect(builds.sort((a, b) => a - b)).toEqual([3, 14, 15, 16]);
+  });
+
+  it("evicts the least recently used chapter beyond capacity", () => {
+    const { builds, build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: 2, build, schedule: runImmediately });
+
+    cache.get(0);
+    cache.get(1);
+    cache.get(0);
+    cache.get(2);
+
+    expect(cache.size).toBe(2);
+    expect(cache.has(1)).toBe(false);
+    expect(cache.has(0)).toBe(true);
+    expect(builds).toEqual([0, 1, 2]);
+  });
+
+  it("rebuilds an evicted chapter when it is revisited", () => {
+    const { builds, build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: 3, build, schedule: runImmediately });
+
+    for (const index of [0, 1, 2, 3]) cache.get(index);
+    expect(cache.has(0)).toBe(false);
+
+    cache.get(0);
+    expect(builds.filter(index => index === 0)).toHaveLength(2);
+    expect(cache.has(1)).toBe(false);
+  });
+
+  it("evicts down to a smaller capacity when resized", () => {
+    const { build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: 4, build, schedule: runImmediately });
+
+    for (const index of [0, 1, 2, 3]) cache.get(index);
+    cache.get(0);
+    cache.resize(2);
+
+    expect(cache.size).toBe(2);
+    expect([0, 1, 2, 3].filter(index => cache.has(index))).toEqual([0, 3]);
+  });
+
+  it("has every chapter built once an eager preload finishes", async () => {
+    const { builds, build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: Number.POSITIVE_INFINITY, build, schedule: runImmediately });
+    const progress: number[] = [];
+
+    cache.get(2);
+    await cache.preloadAll(5, { onProgress: loaded => progress.push(loaded) });
+
+    expect([0, 1, 2, 3, 4].every(index => cache.has(index))).toBe(true);
+    expect(builds).toEqual([2, 0, 1, 3, 4]);
+    expect(progress).toEqual([1, 2, 3, 4, 5]);
+  });
+
+  it("stops preloading when aborted", async () => {
+    const { builds, build } = createCountingBuilder();
+    const cache = new ChapterCache({ capacity: 10, build, schedule: runImmediately });
+    const controller = new AbortController();
+
+    await cache.preloadAll(5, {
+      signal: controller.signal,
+      onProgress: loaded => loaded === 2 && controller.abort(),
+    });
+
+    expect(builds).toEqual([0, 1]);
+  });
+});
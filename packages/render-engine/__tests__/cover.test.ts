@@ -0,0 +1,34 @@
+import { describe, expect, it } from "bun:test";
+import { coverColor, generateCoverDataUrl, generateCoverSvg, wrapCoverText } from "@render-engine";
+
+describe("generateCoverSvg", () => {
+  it("produces a non-empty SVG with the escaped title and authors", () => {
+    const svg = generateCoverSvg({ title: "Tom & Jerry", authors: ["Jane Doe", "John Smith"] });
+
+    expect(svg.startsWith("<svg ")).toBe(true);
+    expect(svg.endsWith("</svg>")).toBe(true);
+    expect(svg).toContain("Tom &amp; Jerry");
+    expect(svg).toContain("Jane Doe、John Smith");
+    expect(generateCoverDataUrl({ title: "Emma" })).toMatch(/^data:image\/svg\+xml;charset=utf-8,%3Csvg/);
+  });
+
+  it("derives the same color from the same title every time", () => {
+    expect(coverColor("Moby Dick")).toBe(coverColor(" moby dick"));
+    expect(coverColor("Moby Dick")).not.toBe(coverColor("Emma"));
+    expect(generateCoverSvg({ title: "Emma" })).toBe(generateCoverSvg({ title: "Emma" }));
+  });
+});
+
+describe("wrapCoverText", () => {
+  it("breaks Latin text at spaces and CJK text anywhere", () => {
+    expect(wrapCoverText("The Hitchhiker's Guide to the Galaxy", 20)).toEqual([
+      "The Hitchhiker's",
+      "Guide to the Galaxy",
+    ]);
+    expect(wrapCoverText("三体地球往事三部曲之一", 10)).toEqual(["三体地球往", "事三部曲之", "一"]);
+  });
+
+  it("ends a truncated title with an ellipsis", () => {
+    expect(wrapCoverText("one two three four five six", 9, 2)).toEqual(["one two", "thre…"]);
+  });
+});
@@ -0,0 +1,15 @@
+import { describe, expect, it } from "bun:test";
+import { buildDebugOutlineCss } from "@render-engine";
+
+describe("buildDebugOutlineCss", () => {
+  it("outlines and badges bold, italic, code and span elements inside the reader", () => {
+    const css = buildDebugOutlineCss();
+
+    for (const badge of ["B", "I", "C", "span"]) {
+      expect(css).toContain(`content: "${badge}";`);
+    }
+    expect(css).toContain(".reader-scope :is(:is(b, strong), [style*='font-weight']) {");
+    // Badges are decoration only and never end up in selected or copied text.
+    expect(css.match(/user-select: none;/g)).toHaveLength(4);
+  });
+});
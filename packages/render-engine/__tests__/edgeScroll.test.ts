@@ -0,0 +1,28 @@
+import { describe, expect, it } from "bun:test";
+import { accumulateOverscroll, EDGE_SCROLL_IDLE_MS, NO_OVERSCROLL, type EdgeOverscroll } from "@render-engine";
+
+const atBottom = { atTop: false, atBottom: true };
+const middle = { atTop: false, atBottom: false };
+
+function wheel(deltas: number[], edges = atBottom, gap = 50): Array<1 | -1 | null> {
+  let overscroll: EdgeOverscroll = NO_OVERSCROLL;
+  return deltas.map((deltaY, index) => {
+    const result = accumulateOverscroll(overscroll, deltaY, edges, index * gap, 300);
+    overscroll = result.overscroll;
+    return result.turn;
+  });
+}
+
+describe("edge overscroll", () => {
+  it("turns the page only after enough wheel travel past the edge", () => {
+    expect(wheel([100, 100])).toEqual([null, null]);
+    expect(wheel([100, 100, 100])).toEqual([null, null, 1]);
+    expect(wheel([-120, -120, -120], { atTop: true, atBottom: false })).toEqual([null, null, -1]);
+  });
+
+  it("ignores scrolling away from the edge, content that is not at an edge, and slow nudges", () => {
+    expect(wheel([200, -50, 200])).toEqual([null, null, null]);
+    expect(wheel([400, 400], middle)).toEqual([null, null]);
+    expect(wheel([200, 200], atBottom, EDGE_SCROLL_IDLE_MS + 1)).toEqual([null, null]);
+  });
+});
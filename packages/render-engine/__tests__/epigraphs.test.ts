@@ -0,0 +1,41 @@
+import { describe, expect, it } from "bun:test";
+import { detectEpigraph, markEpigraph } from "@render-engine";
+
+describe("epigraphs", () => {
+  it("detects a leading blockquote followed by an em-dash attribution", () => {
+    const html = [
+      "<body><section><h1>Chapter One</h1>",
+      '<blockquote class="quote"><p>All happy families are alike.</p></blockquote>',
+      "<p>&#8212; Leo Tolstoy</p>",
+      "<p>It was a bright cold day in April.</p></section></body>",
+    ].join("\n");
+
+    expect(detectEpigraph(html)).toEqual({ quote: "All happy families are alike.", attribution: "— Leo Tolstoy" });
+    expect(markEpigraph(html)).toContain(
+      '<blockquote class="reader-epigraph quote"><p>All happy families are alike.</p></blockquote>\n' +
+        '<p class="reader-epigraph-attribution">&#8212; Leo Tolstoy</p>',
+    );
+  });
+
+  it("accepts an all-italic paragraph or an attribution inside the quote", () => {
+    const italic = "<h2>二</h2><p><i>悲观的净化，出于对人生的惊惧。</i></p><p>——鲁迅</p><p>正文</p>";
+    expect(detectEpigraph(italic)).toEqual({
+      quote: "悲观的净化，出于对人生的惊惧。",
+      attribution: "——鲁迅",
+    });
+    expect(detectEpigraph("<blockquote><p>Call me Ishmael.</p><p>-- Melville</p></blockquote>")).toEqual({
+      quote: "Call me Ishmael.",
+      attribution: "-- Melville",
+    });
+  });
+
+  it("only looks at the opening of the chapter", () => {
+    const later = "<h1>One</h1><p>Prose first.</p><blockquote><p>Quoted.</p></blockquote><p>— Someone</p>";
+    const partlyItalic = "<h1>One</h1><p><i>Mostly</i> italic.</p><p>— Someone</p>";
+
+    expect(detectEpigraph(later)).toBeNull();
+    expect(detectEpigraph(partlyItalic)).toBeNull();
+    expect(detectEpigraph("<h1>One</h1><p><em>Italic but unattributed.</em></p><p>Prose.</p>")).toBeNull();
+    expect(markEpigraph(later)).toBe(later);
+  });
+});
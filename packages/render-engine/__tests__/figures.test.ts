@@ -0,0 +1,20 @@
+import { describe, expect, it } from "bun:test";
+import { collectFigures } from "@render-engine";
+
+describe("collectFigures", () => {
+  it("lists images across chapters in reading order", () => {
+    const figures = collectFigures([
+      {
+        content:
+          '<p>Intro</p><figure><img src="a.png" alt="Harbour"/><figcaption>Figure 1. The <em>old</em> harbour</figcaption></figure>',
+      },
+      { content: "<p>No images here.</p>" },
+      { content: '<div><img src="b.png" title="Lighthouse"/></div><p class="caption">Figure 2. Lighthouse at dusk</p>' },
+    ]);
+
+    expect(figures).toEqual([
+      { chapterIndex: 0, alt: "Harbour", caption: "Figure 1. The old harbour" },
+      { chapterIndex: 2, alt: "Lighthouse", caption: "Figure 2. Lighthouse at dusk" },
+    ]);
+  });
+});
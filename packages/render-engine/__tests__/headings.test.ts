@@ -0,0 +1,94 @@
+import { describe, expect, it } from "bun:test";
+import {
+  applyHeadingNumbers,
+  decodeEntities,
+  deriveChapterTitle,
+  findAdjacentHeading,
+  findPageWithHeading,
+  numberBookHeadings,
+  numberHeadings,
+} from "@render-engine";
+
+const chapter = {
+  id: "chapter3",
+  html: "<body><h1>The Long Night</h1><p>It was cold.</p></body>",
+  tocLabel: "Chapter",
+};
+
+describe("deriveChapterTitle", () => {
+  it("uses the TOC label first by default", () => {
+    expect(deriveChapterTitle(chapter)).toBe("Chapter");
+    expect(deriveChapterTitle({ ...chapter, tocLabel: null })).toBe("The Long Night");
+  });
+
+  it("prefers the first heading when asked to", () => {
+    expect(deriveChapterTitle(chapter, { preferHeading: true })).toBe("The Long Night");
+    expect(deriveChapterTitle({ ...chapter, html: "<p>No heading</p>" }, { preferHeading: true })).toBe("Chapter");
+  });
+
+  it("falls back to plain text and then the id", () => {
+    expect(deriveChapterTitle({ id: "c1", html: "<p>Once upon a time</p>" })).toBe("Once upon a time");
+    expect(deriveChapterTitle({ id: "c2", html: "<div></div>" })).toBe("c2");
+  });
+
+  it("skips a BOM and leading blank lines to find the first line of text", () => {
+    const html = "\uFEFF\n\n\n<body>\n\n<p>&nbsp;</p><br/>\n<p>Chapter\n One</p><p>It was a dark night.</p></body>";
+
+    expect(deriveChapterTitle({ id: "c3", html })).toBe("Chapter One");
+  });
+
+  it("leaves numeric entities outside the Unicode range undecoded", () => {
+    expect(decodeEntities("A&#x110000;B&#99999999;C&#x263A;")).toBe("A&#x110000;B&#99999999;C\u263A");
+    expect(deriveChapterTitle({ id: "c4", html: "<h1>Bad &#x110000; entity</h1>" }, { preferHeading: true })).toBe(
+      "Bad &#x110000; entity",
+    );
+  });
+});
+
+describe("heading numbering", () => {
+  it("numbers an h1/h2/h2/h3 sequence hierarchically", () => {
+    expect(numberHeadings([1, 2, 2, 3])).toEqual(["1", "1.1", "1.2", "1.2.1"]);
+  });
+
+  it("resets deeper counters and tolerates skipped levels", () => {
+    expect(numberHeadings([1, 3, 3, 2, 1, 2])).toEqual(["1", "1.1", "1.2", "1.3", "2", "2.1"]);
+    expect(numberHeadings([2, 1])).toEqual(["1", "2"]);
+  });
+
+  it("continues numbering across chapters and tags the markup", () => {
+    const chapters = ["<h1>Part</h1><h2>Intro</h2>", "<h2></h2><h2>Next</h2>"];
+    const numbers = numberBookHeadings(chapters);
+
+    expect(numbers).toEqual([["1", "1.1"], ["1.2"]]);
+    expect(applyHeadingNumbers(chapters[1]!, numbers[1]!)).toBe('<h2></h2><h2 data-heading-number="1.2">Next</h2>');
+  });
+});
+
+describe("heading navigation", () => {
+  const page = (...html: string[]) => ({ fragments: html.map(fragment => ({ html: fragment })) });
+  const pages = [
+    page("<p>Opening</p>", "<h1>One</h1>"),
+    page("<p>Body</p>", "<blockquote>Quote</blockquote>"),
+    page("<figure><img/></figure>", "<h2>Two</h2>", "<p>More</p>"),
+    page("<p>End</p>"),
+  ];
+
+  it("finds the pages that hold headings in a mixed block list", () => {
+    expect(findPageWithHeading(pages, 0, 1)).toBe(2);
+    expect(findPageWithHeading(pages, 2, 1)).toBe(-1);
+    expect(findPageWithHeading(pages, 3, -1)).toBe(2);
+    expect(findPageWithHeading(pages, 2, -1)).toBe(0);
+    expect(findPageWithHeading(pages, -1, 1)).toBe(0);
+    expect(findPageWithHeading(pages, pages.length, -1)).toBe(2);
+  });
+
+  it("steps to the next and previous heading offsets", () => {
+    const markers = [{ offset: 0 }, { offset: 400 }, { offset: 900 }];
+
+    expect(findAdjacentHeading(markers, 0, 1)).toBe(markers[1]);
+    expect(findAdjacentHeading(markers, 402, 1)).toBe(markers[2]);
+    expect(findAdjacentHeading(markers, 900, 1)).toBeUndefined();
+    expect(findAdjacentHeading(markers, 900, -1)).toBe(markers[1]);
+    expect(findAdjacentHeading(markers, 2, -1)).toBeUndefined();
+  });
+});
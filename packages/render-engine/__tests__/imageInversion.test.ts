@@ -0,0 +1,30 @@
+import { describe, expect, it } from "bun:test";
+import { isLikelyDiagram } from "@render-engine";
+
+function image(size: number, pixelAt: (x: number, y: number) => [number, number, number, number]): Uint8ClampedArray {
+  const data = new Uint8ClampedArray(size * size * 4);
+  for (let y = 0; y < size; y += 1) {
+    for (let x = 0; x < size; x += 1) data.set(pixelAt(x, y), (y * size + x) * 4);
+  }
+  return data;
+}
+
+describe("image inversion", () => {
+  it("treats black-on-white line art as a diagram", () => {
+    const chart = image(32, (x, y) => (x === 8 || y === 24 || x === y ? [20, 20, 20, 255] : [255, 255, 255, 255]));
+    const transparentSketch = image(32, (x, y) => (x === y ? [0, 0, 0, 255] : [0, 0, 0, 0]));
+
+    expect(isLikelyDiagram(chart)).toBe(true);
+    expect(isLikelyDiagram(transparentSketch)).toBe(true);
+  });
+
+  it("leaves photos and dark images alone", () => {
+    // A smooth gradient across every channel, like a sky or a face, spans far more colours than a diagram.
+    const photo = image(32, (x, y) => [120 + x * 4, 100 + y * 4, 180 + ((x * y) % 60), 255]);
+    const night = image(32, (x, y) => (x === y ? [255, 255, 255, 255] : [10, 10, 30, 255]));
+
+    expect(isLikelyDiagram(photo)).toBe(false);
+    expect(isLikelyDiagram(night)).toBe(false);
+    expect(isLikelyDiagram(new Uint8ClampedArray())).toBe(false);
+  });
+});
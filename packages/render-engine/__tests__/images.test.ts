@@ -0,0 +1,19 @@
+import { describe, expect, it } from "bun:test";
+import { applyImageAltFallback, readAttributes, resolveImageAlt } from "@render-engine";
+
+describe("image alt fallback", () => {
+  it("uses the title when alt is empty", () => {
+    const html = applyImageAltFallback('<p><img src="map.png" alt="" title="A map of the harbour"/></p>');
+
+    expect(html).toBe('<p><img alt="A map of the harbour" src="map.png" title="A map of the harbour"/></p>');
+    expect(resolveImageAlt(readAttributes(' src="x.png" aria-label="Label only"'))).toBe("Label only");
+  });
+
+  it("keeps existing alt text and images without any description", () => {
+    const described = '<img src="a.png" alt="Original" title="Other">';
+    const bare = '<img src="b.png">';
+
+    expect(applyImageAltFallback(described)).toBe(described);
+    expect(applyImageAltFallback(bare)).toBe(bare);
+  });
+});
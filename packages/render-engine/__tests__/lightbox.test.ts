@@ -0,0 +1,40 @@
+import { describe, expect, it } from "bun:test";
+import { LIGHTBOX_CLOSED, LIGHTBOX_MAX_ZOOM, lightboxReducer, svgToDataUrl, wheelZoomFactor } from "@render-engine";
+
+const diagram = { src: "data:image/png;base64,AAAA", alt: "Diagram" };
+
+describe("lightboxReducer", () => {
+  it("opens on an image click and closes again", () => {
+    const opened = lightboxReducer(LIGHTBOX_CLOSED, { type: "imageClick", image: diagram });
+    expect(opened).toEqual({ open: true, image: diagram, zoom: 1 });
+
+    expect(lightboxReducer(opened, { type: "close" })).toEqual({ open: false });
+    expect(lightboxReducer(LIGHTBOX_CLOSED, { type: "close" })).toBe(LIGHTBOX_CLOSED);
+  });
+
+  it("ignores clicks on images without a source", () => {
+    expect(lightboxReducer(LIGHTBOX_CLOSED, { type: "imageClick", image: { src: "", alt: "" } })).toBe(LIGHTBOX_CLOSED);
+  });
+
+  it("opens inline SVG through a data URL and resets the zoom for a new image", () => {
+    const zoomed = lightboxReducer(lightboxReducer(LIGHTBOX_CLOSED, { type: "imageClick", image: diagram }), {
+      type: "zoom",
+      factor: 2,
+    });
+    const svg = { src: svgToDataUrl('<svg xmlns="http://www.w3.org/2000/svg"><rect/></svg>'), alt: "" };
+
+    const next = lightboxReducer(zoomed, { type: "imageClick", image: svg });
+    expect(next).toEqual({ open: true, image: svg, zoom: 1 });
+    expect(svg.src.startsWith("data:image/svg+xml;charset=utf-8,%3Csvg")).toBe(true);
+  });
+
+  it("clamps zoom and ignores it while closed", () => {
+    const opened = lightboxReducer(LIGHTBOX_CLOSED, { type: "imageClick", image: diagram });
+
+    expect(lightboxReducer(opened, { type: "zoom", factor: 0.5 })).toBe(opened);
+    expect(lightboxReducer(opened, { type: "zoom", factor: 100 })).toMatchObject({ zoom: LIGHTBOX_MAX_ZOOM });
+    expect(lightboxReducer(LIGHTBOX_CLOSED, { type: "zoom", factor: 2 })).toBe(LIGHTBOX_CLOSED);
+    expect(wheelZoomFactor(-100)).toBeGreaterThan(1);
+    expect(wheelZoomFactor(100)).toBeLessThan(1);
+  });
+});
@@ -0,0 +1,35 @@
+import { describe, expect, it } from "bun:test";
+import { findPageWithLink, stepLinkFocus } from "@render-engine";
+
+describe("link focus", () => {
+  it("advances through the links of a page in both directions", () => {
+    const visited: number[] = [];
+    for (let index = stepLinkFocus(-1, 4, 1); index !== null; index = stepLinkFocus(index, 4, 1)) {
+      visited.push(index);
+    }
+
+    expect(visited).toEqual([0, 1, 2, 3]);
+    expect(stepLinkFocus(-1, 4, -1)).toBe(3);
+    expect(stepLinkFocus(2, 4, -1)).toBe(1);
+  });
+
+  it("returns null past either end or when the page has no links", () => {
+    expect(stepLinkFocus(3, 4, 1)).toBeNull();
+    expect(stepLinkFocus(0, 4, -1)).toBeNull();
+    expect(stepLinkFocus(-1, 0, 1)).toBeNull();
+  });
+
+  it("finds the nearest page with a link", () => {
+    const page = (...html: string[]) => ({ fragments: html.map(fragment => ({ html: fragment })) });
+    const pages = [
+      page('<p>See <a href="#n1">1</a></p>'),
+      page("<p>Plain</p>", '<p><a id="anchor-only">x</a></p>'),
+      page("<p>More</p>", "<p>Go <a class=\"x\" href='ch2.xhtml'>on</a></p>"),
+    ];
+
+    expect(findPageWithLink(pages, 0, 1)).toBe(2);
+    expect(findPageWithLink(pages, 2, -1)).toBe(0);
+    expect(findPageWithLink(pages, pages.length, -1)).toBe(2);
+    expect(findPageWithLink(pages, 2, 1)).toBe(-1);
+  });
+});
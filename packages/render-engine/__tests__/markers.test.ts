@@ -0,0 +1,35 @@
+import { describe, expect, it } from "bun:test";
+import { clusterMarkers, pageAtFraction } from "@render-engine";
+
+describe("clusterMarkers", () => {
+  it("merges markers that would overlap and keeps distant ones apart", () => {
+    const clusters = clusterMarkers(
+      [
+        { fraction: 0.5, id: "c" },
+        { fraction: 0.1, id: "a" },
+        { fraction: 0.11, id: "b" },
+        { fraction: 0.9, id: "d" },
+      ],
+      0.02,
+    );
+
+    expect(clusters.map(cluster => cluster.fraction)).toEqual([0.1, 0.5, 0.9]);
+    expect(clusters[0]?.items.map(item => item.id)).toEqual(["a", "b"]);
+  });
+
+  it("returns nothing for no markers", () => {
+    expect(clusterMarkers([])).toEqual([]);
+  });
+});
+
+describe("pageAtFraction", () => {
+  it("maps a text position to the page that holds it", () => {
+    const pages = [{ textLength: 100 }, { textLength: 300 }, { textLength: 100 }];
+
+    expect(pageAtFraction(pages, 0)).toBe(0);
+    expect(pageAtFraction(pages, 0.3)).toBe(1);
+    expect(pageAtFraction(pages, 0.85)).toBe(2);
+    expect(pageAtFraction(pages, 1)).toBe(2);
+    expect(pageAtFraction([], 0.5)).toBe(0);
+  });
+});
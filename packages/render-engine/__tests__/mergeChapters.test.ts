@@ -0,0 +1,68 @@
+import { describe, expect, it } from "bun:test";
+import { countWords, mergeShortChapters } from "@render-engine";
+
+const page = (href: string, body: string) => ({
+  href,
+  title: href,
+  content: `<html><head><title>${href}</title></head><body>${body}</body></html>`,
+});
+
+describe("mergeShortChapters", () => {
+  it("merges consecutive tiny chapters and leaves normal ones alone", () => {
+    const longBody = `<p>${"word ".repeat(120)}</p>`;
+    const chapters = [
+      page("p1.xhtml", "<h1>Part One</h1>"),
+      page("p2.xhtml", "<p>A short page.</p>"),
+      page("p3.xhtml", "<p>Another short page.</p>"),
+      page("c1.xhtml", longBody),
+    ];
+
+    const merged = mergeShortChapters(chapters, 50);
+
+    expect(merged).toHaveLength(2);
+    expect(merged[0]?.title).toBe("p1.xhtml");
+    expect(merged[0]?.mergedHrefs).toEqual(["p1.xhtml", "p2.xhtml", "p3.xhtml"]);
+    expect(merged[0]?.content).toContain("<body><h1>Part One</h1>\n<p>A short page.</p>\n<p>Another short page.</p></body>");
+    expect(merged[0]?.content).toContain("<title>p1.xhtml</title>");
+    expect(merged[1]?.mergedHrefs).toEqual(["c1.xhtml"]);
+    expect(merged[1]?.content).toBe(chapters[3]!.content);
+  });
+
+  it("does not merge across front, body and back matter", () => {
+    const chapters = [
+      { ...page("dedication.xhtml", "<p>For you.</p>"), matter: "front" },
+      { ...page("epigraph.xhtml", "<p>A quote.</p>"), matter: "front" },
+      { ...page("part1.xhtml", "<h1>Part One</h1>"), matter: "body" },
+      { ...page("colophon.xhtml", "<p>Set in Garamond.</p>"), matter: "back" },
+    ];
+
+    expect(mergeShortChapters(chapters, 50).map(chapter => chapter.mergedHrefs)).toEqual([
+      ["dedication.xhtml", "epigraph.xhtml"],
+      ["part1.xhtml"],
+      ["colophon.xhtml"],
+    ]);
+  });
+
+  it("keeps non-linear chapters on their own", () => {
+    const chapters = [
+      page("p1.xhtml", "<h1>Part One</h1>"),
+      { ...page("answers.xhtml", "<p>1. B</p>"), linear: false },
+      page("p2.xhtml", "<h1>Part Two</h1>"),
+    ];
+
+    expect(mergeShortChapters(chapters, 50).map(chapter => chapter.mergedHrefs)).toEqual([
+      ["p1.xhtml"],
+      ["answers.xhtml"],
+      ["p2.xhtml"],
+    ]);
+  });
+
+  it("is a no-op when the threshold is zero", () => {
+    const chapters = [page("a.xhtml", "<p>a</p>"), page("b.xhtml", "<p>b</p>")];
+    expect(mergeShortChapters(chapters, 0).map(chapter => chapter.mergedHrefs)).toEqual([["a.xhtml"], ["b.xhtml"]]);
+  });
+
+  it("counts CJK characters as words", () => {
+    expect(countWords("<p>你好 world</p>")).toBe(3);
+  });
+});
@@ -0,0 +1,18 @@
+import { describe, expect, it } from "bun:test";
+import { chapterTransitionsEnabled, DEFAULT_PREFS, motionAllowed } from "@render-engine";
+
+describe("reduced motion", () => {
+  const animated = { ...DEFAULT_PREFS, animateTransitions: true };
+
+  it("disables chapter transitions when reduce motion is on", () => {
+    expect(chapterTransitionsEnabled(animated)).toBe(true);
+    expect(chapterTransitionsEnabled({ ...animated, reduceMotion: true })).toBe(false);
+    expect(chapterTransitionsEnabled(DEFAULT_PREFS)).toBe(false);
+  });
+
+  it("also stops motion for e-ink themes and the system preference", () => {
+    expect(motionAllowed(DEFAULT_PREFS)).toBe(true);
+    expect(motionAllowed(DEFAULT_PREFS, { eInk: true })).toBe(false);
+    expect(chapterTransitionsEnabled(animated, { systemReducedMotion: true })).toBe(false);
+  });
+});
@@ -0,0 +1,145 @@
+import { describe, expect, it } from "bun:test";
+import {
+  adjacentChapter,
+  chapterWindow,
+  findChapterIndex,
+  isBlankChapter,
+  nonLinearChapters,
+  resolveBookHref,
+  resolveHrefTarget,
+  tocChapterOrder,
+} from "@render-engine";
+
+// Spine: 0 cover, 1 intro, 2 chapter-a, 3 chapter-b, 4 notes. The TOC lists the chapters
+// before the introduction, points twice into chapter-a and has an entry for a missing file.
+const chapters = ["cover.xhtml", "intro.xhtml", "a.xhtml", "b.xhtml", "notes.xhtml"];
+const resolve = (href: string) => chapters.indexOf(href.split("#")[0]!);
+const tocHrefs = ["a.xhtml", "a.xhtml#s2", "b.xhtml", "gone.xhtml", "intro.xhtml", "notes.xhtml"];
+
+function walk(order: "spine" | "toc", from: number, tocOrder: number[]): number[] {
+  const visited = [from];
+  for (let next = adjacentChapter(from, 1, chapters.length, order, tocOrder); next !== null; ) {
+    visited.push(next);
+    next = adjacentChapter(next, 1, chapters.length, order, tocOrder);
+  }
+  return visited;
+}
+
+describe("navigation order", () => {
+  it("resolves TOC hrefs to chapters, skipping unresolved entries and repeats", () => {
+    expect(tocChapterOrder(tocHrefs, resolve)).toEqual([2, 3, 1, 4]);
+  });
+
+  it("walks the spine or the TOC depending on the setting", () => {
+    const tocOrder = tocChapterOrder(tocHrefs, resolve);
+
+    expect(walk("spine", 0, tocOrder)).toEqual([0, 1, 2, 3, 4]);
+    expect(walk("toc", 2, tocOrder)).toEqual([2, 3, 1, 4]);
+    expect(adjacentChapter(1, -1, chapters.length, "toc", tocOrder)).toBe(3);
+    expect(adjacentChapter(2, -1, chapters.length, "toc", tocOrder)).toBeNull();
+  });
+
+  it("falls back to the spine for chapters the TOC doesn't list", () => {
+    const tocOrder = tocChapterOrder(tocHrefs, resolve);
+
+    expect(adjacentChapter(0, 1, chapters.length, "toc", tocOrder)).toBe(1);
+    expect(adjacentChapter(4, 1, chapters.length, "toc", [])).toBeNull();
+  });
+});
+
+describe("hidden empty chapters", () => {
+  const contents = [
+    "<html><head><title>One</title></head><body><p>First chapter.</p></body></html>",
+    '<html><head><title>Separator</title></head><body><p>&nbsp;</p><div class="break"></div></body></html>',
+    "<html><body><p>Second chapter.</p></body></html>",
+    '<html><body><img src="plate.png" alt=""/></body></html>',
+  ];
+
+  it("recognizes documents without text or media", () => {
+    expect(contents.map(isBlankChapter)).toEqual([false, true, false, false]);
+  });
+
+  it("skips an empty chapter between two non-empty ones only when hiding is on", () => {
+    const hidden = new Set(contents.flatMap((html, index) => (isBlankChapter(html) ? [index] : [])));
+
+    expect(adjacentChapter(0, 1, contents.length, "spine", [], hidden)).toBe(2);
+    expect(adjacentChapter(2, -1, contents.length, "spine", [], hidden)).toBe(0);
+    expect(adjacentChapter(0, 1, contents.length, "toc", [0, 1, 2, 3], hidden)).toBe(2);
+    expect(adjacentChapter(0, 1, contents.length, "spine")).toBe(1);
+    expect(adjacentChapter(2, 1, 3, "spine", [], new Set([1]))).toBeNull();
+  });
+});
+
+describe("chapterWindow", () => {
+  it("centres on the current chapter", () => {
+    expect(chapterWindow(10, 50, 2)).toEqual([8, 9, 10, 11, 12]);
+  });
+
+  it("slides inward at either end instead of shrinking", () => {
+    expect(chapterWindow(0, 50, 2)).toEqual([0, 1, 2, 3, 4]);
+    expect(chapterWindow(49, 50, 2)).toEqual([45, 46, 47, 48, 49]);
+    expect(chapterWindow(120, 50, 2)).toEqual([45, 46, 47, 48, 49]);
+  });
+
+  it("shows the whole book when it is shorter than the window", () => {
+    expect(chapterWindow(1, 3, 5)).toEqual([0, 1, 2]);
+    expect(chapterWindow(0, 0)).toEqual([]);
+  });
+});
+
+describe("resolveHrefTarget", () => {
+  const chapters = [
+    { href: "text/part1.xhtml" },
+    { href: "text/part2.xhtml", mergedHrefs: ["text/part2.xhtml", "text/part2b.xhtml"] },
+  ];
+  const anchorPages: Record<string, number> = { "ch%2": 1, "section 7": 3 };
+
+  it("switches to the chapter and keeps the fragment as a pending scroll", () => {
+    const target = resolveHrefTarget(chapters, "text/part2b.xhtml#section%207", (chapter, anchor) =>
+      chapter === 1 ? anchorPages[anchor] : undefined,
+    );
+
+    expect(target).toEqual({ chapter: 1, page: 3, anchor: "section 7" });
+    expect(resolveHrefTarget(chapters, "text/part1.xhtml")).toEqual({ chapter: 0, page: 0 });
+  });
+
+  it("keeps a malformed fragment as written and ignores unknown documents", () => {
+    expect(resolveHrefTarget(chapters, "text/part1.xhtml#ch%2", (_chapter, anchor) => anchorPages[anchor])).toEqual({
+      chapter: 0,
+      page: 1,
+      anchor: "ch%2",
+    });
+    expect(resolveHrefTarget(chapters, "text/missing.xhtml#top")).toBeNull();
+    expect(findChapterIndex(chapters, "#only-a-fragment")).toBe(-1);
+  });
+});
+
+describe("non-linear chapters", () => {
+  const chapters = [
+    { href: "text/ch1.xhtml", linear: true },
+    { href: "text/answers.xhtml", linear: false },
+    { href: "text/ch2.xhtml", linear: true },
+  ];
+
+  it("are skipped by Next and Previous but still reached by links", () => {
+    const skipped = nonLinearChapters(chapters);
+
+    expect([...skipped]).toEqual([1]);
+    expect(adjacentChapter(0, 1, chapters.length, "spine", [], skipped)).toBe(2);
+    expect(adjacentChapter(2, -1, chapters.length, "spine", [], skipped)).toBe(0);
+    expect(resolveHrefTarget(chapters, resolveBookHref("text/ch1.xhtml", "answers.xhtml#q3")!)).toEqual({
+      chapter: 1,
+      page: 0,
+      anchor: "q3",
+    });
+  });
+
+  it("resolves links relative to the linking document", () => {
+    expect(resolveBookHref("OEBPS/text/ch1.xhtml", "../notes/n.xhtml#n2")).toBe("OEBPS/notes/n.xhtml#n2");
+    expect(resolveBookHref("OEBPS/text/ch1.xhtml", "./ch2.xhtml")).toBe("OEBPS/text/ch2.xhtml");
+    expect(resolveBookHref("ch1.xhtml", "/OEBPS/ch2.xhtml")).toBe("OEBPS/ch2.xhtml");
+    expect(resolveBookHref("ch1.xhtml", "#n1")).toBeNull();
+    expect(resolveBookHref("ch1.xhtml", "https://example.com/")).toBeNull();
+    expect(resolveBookHref("ch1.xhtml", "mailto:someone@example.com")).toBeNull();
+  });
+});
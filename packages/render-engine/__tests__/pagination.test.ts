@@ -0,0 +1,64 @@
+import { describe, expect, it } from "bun:test";
+import { createPaginationSession } from "@render-engine";
+
+describe("createPaginationSession", () => {
+  it("keeps ruby annotations attached to their base text", () => {
+    const session = createPaginationSession({
+      spineIndex: 0,
+      html: "<p><ruby>漢<rp>(</rp><rt>かん</rt><rp>)</rp></ruby>字</p>",
+    });
+
+    const [fragment] = session.page(0)?.fragments ?? [];
+    expect(fragment?.html).toContain("<ruby>漢<rp>(</rp><rt>かん</rt><rp>)</rp></ruby>");
+    // Only the base characters count towards the page budget.
+    expect(fragment?.textLength).toBe("漢 字".length);
+  });
+
+  it("pages through a 10k-block chapter wrapped in one section", () => {
+    const blocks = Array.from({ length: 10_000 }, (_, index) => `<p>Paragraph ${index} of a very long chapter.</p>`);
+    const html = `<section class="chapter">${blocks.join("")}</section>`;
+
+    const session = createPaginationSession({ spineIndex: 0, html, prefs: { pageCharLimit: 1200 } });
+
+    expect(session.page(0)?.fragments[0]?.html).toBe("<p>Paragraph 0 of a very long chapter.</p>");
+    expect(session.totalPages).toBeGreaterThan(100);
+    for (const percent of [0, 0.137, 0.5, 0.9, 1]) {
+      const page = session.locate(percent);
+      expect(page).toBeDefined();
+      expect(page!.textLength).toBeLessThanOrEqual(1200);
+      expect(page!.fragments.length).toBeLessThan(100);
+    }
+    expect(session.locate(1)?.fragments.at(-1)?.html).toContain("Paragraph 9999");
+  });
+
+  it("keeps a figure and its caption in one fragment", () => {
+    const session = createPaginationSession({
+      spineIndex: 0,
+      html: '<p>Before.</p><figure><img src="map.png" alt=""/><figcaption>The harbour</figcaption></figure><p>After.</p>',
+    });
+
+    const fragments = session.page(0)?.fragments ?? [];
+    expect(fragments).toHaveLength(3);
+    expect(fragments[1]?.html).toBe('<figure><img src="map.png" alt=""/><figcaption>The harbour</figcaption></figure>');
+    expect(fragments[1]?.textLength).toBe("The harbour".length);
+  });
+
+  it("resolves fragment-only TOC targets within a single-document book to pages", () => {
+    const filler = (label: string) => Array.from({ length: 20 }, () => `<p>${label} text that fills the page.</p>`).join("");
+    const html = ["intro", "sec1", "sec2"]
+      .map(id => `<section id="${id}"><h2>${id}</h2>${filler(id)}</section>`)
+      .join("");
+    const toc = ["content.xhtml#intro", "content.xhtml#sec1", "content.xhtml#sec2"];
+
+    const session = createPaginationSession({ spineIndex: 0, html, prefs: { pageCharLimit: 300 } });
+    const pages = toc.map(href => session.findAnchor(href.split("#")[1]!)?.pageIndex);
+
+    expect(pages[0]).toBe(0);
+    expect(pages[1]).toBeGreaterThan(pages[0]!);
+    expect(pages[2]).toBeGreaterThan(pages[1]!);
+    // The section is split into its blocks; its id moves to an anchor on the first one.
+    const sectionStart = session.page(pages[2]!)?.fragments.find(fragment => fragment.html.includes("<h2>sec2</h2>"));
+    expect(sectionStart?.html.startsWith('<a id="sec2"></a>')).toBe(true);
+    expect(session.findAnchor("missing")).toBeUndefined();
+  });
+});
@@ -0,0 +1,92 @@
+import { describe, expect, it } from "bun:test";
+import { htmlToPlainText, htmlToPlainTextBlocks, renderPlainTextParagraphs } from "@render-engine";
+
+const messyMarkup = `
+  <head><title>Ignored</title><style>p { color: red; }</style></head>
+  <div class="calibre1"><span class="h">Chapter&nbsp;One</span></div>
+  <table><tr><td><h2>Not <b>really</b> a heading</h2></td></tr></table>
+  <ul><li>first</li><li>second &amp; third</li></ul>
+  <div><div><p>Deeply <i>nested</i><br/>text</p></div></div>
+`;
+
+describe("plain text rendering", () => {
+  it("renders complex markup as paragraphs only", () => {
+    const html = renderPlainTextParagraphs(messyMarkup);
+    const tags = new Set(Array.from(html.matchAll(/<\/?([a-z0-9]+)/gi), match => match[1]!.toLowerCase()));
+
+    expect([...tags].sort()).toEqual(["br", "p"]);
+    expect(html).toContain("<p>Chapter One</p>");
+    expect(html).toContain('<p style="padding-left: 1.5em">\u2022 second &amp; third</p>');
+    expect(html).toContain("<p>Deeply nested<br/>text</p>");
+    expect(html).not.toContain("Ignored");
+  });
+
+  it("keeps blank lines between blocks", () => {
+    expect(htmlToPlainText("<p>One</p><p>Two</p>")).toBe("One\n\nTwo");
+  });
+
+  it("keeps the indentation of code blocks while prose still collapses", () => {
+    const html = [
+      "<p>Call   it   like  this:</p>",
+      "<pre><code>function greet() {\n    if (ready) {\n        say(\"hi  there\");\n    }\n}</code></pre>",
+    ].join("\n");
+
+    expect(htmlToPlainTextBlocks(html)).toEqual([
+      { text: "Call it like this:", whitespace: "collapse" },
+      {
+        text: 'function greet() {\n    if (ready) {\n        say("hi  there");\n    }\n}',
+        whitespace: "preformatted",
+      },
+    ]);
+    expect(renderPlainTextParagraphs(html)).toContain("<pre>function greet() {\n    if (ready) {");
+  });
+
+  it("keeps deliberate spacing in verse but not the indentation of the source", () => {
+    const html = `<div class="poem">
+      <p>The  rain  falls<br/>
+      \u00a0\u00a0\u00a0\u00a0on  the  sea</p>
+    </div>`;
+
+    expect(htmlToPlainText(html)).toBe("The  rain  falls\n\u00a0\u00a0\u00a0\u00a0on  the  sea");
+  });
+
+  it("keeps the depth and marker of nested list items", () => {
+    const html = [
+      '<ol start="3"><li>Gather<ul><li>flour</li><li>eggs</li></ul></li>',
+      "<li><p>Mix</p><p>until smooth</p></li></ol>",
+    ].join("");
+
+    expect(htmlToPlainTextBlocks(html)).toEqual([
+      { text: "Gather", whitespace: "collapse", list: { depth: 1, marker: "3." } },
+      { text: "flour", whitespace: "collapse", list: { depth: 2, marker: "\u25e6" } },
+      { text: "eggs", whitespace: "collapse", list: { depth: 2, marker: "\u25e6" } },
+      { text: "Mix", whitespace: "collapse", list: { depth: 1, marker: "4." } },
+      { text: "until smooth", whitespace: "collapse", list: { depth: 1, marker: "" } },
+    ]);
+    expect(renderPlainTextParagraphs(html)).toContain('<p style="padding-left: 3em">\u25e6 flour</p>');
+    expect(htmlToPlainText(html)).toBe("Gather\n\nflour\n\neggs\n\nMix\n\nuntil smooth");
+  });
+
+  it("keeps the expansion of abbreviations", () => {
+    const html = '<p><abbr title="HyperText Markup Language">HTML</abbr> is not XHTML; HTML &amp; <abbr>CSS</abbr>.</p>';
+
+    expect(htmlToPlainTextBlocks(html)).toEqual([
+      {
+        text: "HTML is not XHTML; HTML & CSS.",
+        whitespace: "collapse",
+        abbreviations: [{ text: "HTML", title: "HyperText Markup Language" }],
+      },
+    ]);
+    expect(renderPlainTextParagraphs(html)).toBe(
+      '<p><abbr title="HyperText Markup Language">HTML</abbr> is not XHTML; ' +
+        '<abbr title="HyperText Markup Language">HTML</abbr> &amp; CSS.</p>',
+    );
+  });
+
+  it("spaces text split by inline markup with the spacing rules", () => {
+    const html = "<p>He said <q>“ <i>wait</i> ”</q> —and left <b>!</b></p>";
+    expect(htmlToPlainText(html)).toBe("He said “wait”—and left!");
+    expect(htmlToPlainText(html, { spacing: "source" })).toBe("He said “ wait ” —and left !");
+    expect(htmlToPlainText('<p>Oui <span lang="fr">Bonjour <b>!</b></span></p>')).toBe("Oui Bonjour\u202f!");
+  });
+});
@@ -0,0 +1,31 @@
+import { describe, expect, it } from "bun:test";
+import { findPrintPage, printPageAt, resolveHrefTarget } from "@render-engine";
+
+const chapters = [{ href: "text/front.xhtml" }, { href: "text/ch1.xhtml" }, { href: "text/ch2.xhtml" }];
+const pageMap = [
+  { label: "xi", href: "text/front.xhtml#pxi" },
+  { label: "1", href: "text/ch1.xhtml#p1" },
+  { label: "2", href: "text/ch1.xhtml#p2" },
+  { label: "3", href: "text/ch2.xhtml" },
+];
+// In ch1, page 1 starts on the first laid-out page and page 2 on the third.
+const anchorPages: Record<string, number> = { pxi: 0, p1: 0, p2: 2 };
+const locate = (href: string) => resolveHrefTarget(chapters, href, (_chapter, anchor) => anchorPages[anchor]);
+
+describe("print pages", () => {
+  it("resolves a print page label to its chapter and anchor", () => {
+    const target = findPrintPage(pageMap, " XI ");
+
+    expect(target?.href).toBe("text/front.xhtml#pxi");
+    expect(locate(findPrintPage(pageMap, "2")!.href)).toEqual({ chapter: 1, page: 2, anchor: "p2" });
+    expect(findPrintPage(pageMap, "99")).toBeUndefined();
+    expect(findPrintPage(pageMap, "")).toBeUndefined();
+  });
+
+  it("names the print page the reader is on", () => {
+    expect(printPageAt(pageMap, { chapter: 1, page: 1 }, locate)).toBe("1");
+    expect(printPageAt(pageMap, { chapter: 1, page: 3 }, locate)).toBe("2");
+    expect(printPageAt(pageMap, { chapter: 2, page: 0 }, locate)).toBe("3");
+    expect(printPageAt([{ label: "5", href: "text/ch2.xhtml" }], { chapter: 0, page: 0 }, locate)).toBeNull();
+  });
+});
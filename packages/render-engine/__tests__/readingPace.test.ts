@@ -0,0 +1,28 @@
+import { describe, expect, it } from "bun:test";
+import { DEFAULT_WORDS_PER_MINUTE, estimateReadingMinutes, recordReadingSample } from "@render-engine";
+
+const MINUTE = 60_000;
+
+describe("reading pace", () => {
+  it("averages timed chapters and ignores implausibly long dwells", () => {
+    let pace = { wordsPerMinute: 0, samples: 0 };
+    pace = recordReadingSample(pace, 2000, 10 * MINUTE);
+    pace = recordReadingSample(pace, 3000, 10 * MINUTE);
+    expect(pace).toEqual({ wordsPerMinute: 250, samples: 2 });
+
+    // Left open overnight: 2000 words over eight hours is not a reading speed.
+    expect(recordReadingSample(pace, 2000, 8 * 60 * MINUTE)).toBe(pace);
+    // Nor is paging straight through.
+    expect(recordReadingSample(pace, 2000, 5_000)).toBe(pace);
+  });
+
+  it("uses the default pace until enough chapters have been timed", () => {
+    const early = { wordsPerMinute: 200, samples: 2 };
+    const known = { wordsPerMinute: 200, samples: 3 };
+
+    expect(estimateReadingMinutes(DEFAULT_WORDS_PER_MINUTE * 12, early)).toEqual({ minutes: 12, personalized: false });
+    expect(estimateReadingMinutes(2400, known)).toEqual({ minutes: 12, personalized: true });
+    expect(estimateReadingMinutes(10, known).minutes).toBe(1);
+    expect(estimateReadingMinutes(0).minutes).toBe(0);
+  });
+});
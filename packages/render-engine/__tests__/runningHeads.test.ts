@@ -0,0 +1,31 @@
+import { describe, expect, it } from "bun:test";
+import { findRunningHeads, stripRunningHeads } from "@render-engine";
+
+const chapter = (body: string) => `<html><body><p class="header">The Long Road</p>${body}</body></html>`;
+
+describe("running heads", () => {
+  it("suppresses a header paragraph repeated at the top of every chapter", () => {
+    const chapters = [
+      chapter("<h1>One</h1><p>First chapter.</p>"),
+      chapter("<h1>Two</h1><p>Second chapter.</p>"),
+      chapter("<h1>Three</h1><p>Third chapter.</p>"),
+    ];
+
+    const heads = findRunningHeads(chapters);
+
+    expect([...heads]).toEqual(["The Long Road"]);
+    expect(stripRunningHeads(chapters[1]!, heads)).toBe("<html><body><h1>Two</h1><p>Second chapter.</p></body></html>");
+  });
+
+  it("keeps blocks that repeat in too few chapters or are too long", () => {
+    const long = "A".repeat(100);
+    const chapters = [
+      `<p>Note</p><p>${long}</p>`,
+      `<p>Note</p><p>Middle</p><p>${long}</p>`,
+      "<p>Other</p><p>Third body</p>",
+      "<p>Else</p><p>Fourth body</p>",
+    ];
+
+    expect(findRunningHeads(chapters).size).toBe(0);
+  });
+});
@@ -0,0 +1,139 @@
+import { describe, expect, it } from "bun:test";
+import { DebouncedSearch, parseSearchQuery, searchChapters, searchNotes } from "@render-engine";
+
+const chapters = [
+  { content: "<p>The white whale surfaced near the ship.</p>" },
+  { content: "<p>A whale song echoed; the <em>white</em> sails were torn.</p>" },
+  { content: "<p>Ahab hunted the White Whale across the sea.</p>" },
+  { content: "<p>No cetaceans here, only the ship.</p>" },
+];
+
+describe("search queries", () => {
+  it("parses terms, phrases and exclusions", () => {
+    expect(parseSearchQuery('whale "white whale" -ship -"open sea"').terms).toEqual([
+      { text: "whale", phrase: false, exclude: false },
+      { text: "white whale", phrase: true, exclude: false },
+      { text: "ship", phrase: false, exclude: true },
+      { text: "open sea", phrase: true, exclude: true },
+    ]);
+  });
+
+  it("ANDs space separated terms within a chapter", () => {
+    const hits = searchChapters(chapters, "WHITE whale");
+
+    expect(hits.map(hit => hit.chapterIndex)).toEqual([0, 1, 2]);
+    expect(hits[1]?.ranges.map(([start, end]) => hits[1]!.text.slice(start, end))).toEqual(["whale", "white"]);
+  });
+
+  it("matches quoted phrases exactly", () => {
+    const hits = searchChapters(chapters, '"white whale"');
+
+    expect(hits.map(hit => hit.chapterIndex)).toEqual([0, 2]);
+    expect(hits[1]?.text.slice(...hits[1]!.ranges[0]!)).toBe("White Whale");
+  });
+
+  it("excludes chapters containing a negated term", () => {
+    expect(searchChapters(chapters, "whale -ship").map(hit => hit.chapterIndex)).toEqual([1, 2]);
+  });
+
+  it("combines phrases, terms and exclusions", () => {
+    const hits = searchChapters(chapters, '"white whale" hunted -"the ship"');
+
+    expect(hits.map(hit => hit.chapterIndex)).toEqual([2]);
+    expect(hits[0]?.ranges.map(([start, end]) => hits[0]!.text.slice(start, end))).toEqual(["hunted", "White Whale"]);
+    expect(searchChapters(chapters, "-whale")).toEqual([]);
+  });
+});
+
+describe("searchNotes", () => {
+  it("matches bookmark notes and skips bookmarks without one", () => {
+    const bookmarks = [
+      { spineIndex: 0, offset: 2, note: "Ahab  first mentions the whale" },
+      { spineIndex: 1, offset: 0 },
+      { spineIndex: 3, offset: 1, note: "Storm scene" },
+    ];
+
+    const hits = searchNotes(bookmarks, "whale ahab");
+
+    expect(hits.map(hit => hit.item)).toEqual([bookmarks[0]]);
+    expect(hits[0]?.text).toBe("Ahab first mentions the whale");
+    expect(hits[0]?.ranges.map(([start, end]) => hits[0]!.text.slice(start, end))).toEqual(["Ahab", "whale"]);
+  });
+});
+
+class ManualClock {
+  private now = 0;
+  private nextId = 1;
+  private readonly timers = new Map<number, { at: number; callback: () => void }>();
+
+  setTimeout(callback: () => void, ms: number) {
+    const id = this.nextId++;
+    this.timers.set(id, { at: this.now + ms, callback });
+    return id;
+  }
+
+  clearTimeout(handle: unknown) {
+    this.timers.delete(handle as number);
+  }
+
+  advance(ms: number) {
+    this.now += ms;
+    for (const [id, timer] of [...this.timers]) {
+      if (timer.at <= this.now) {
+        this.timers.delete(id);
+        timer.callback();
+      }
+    }
+  }
+}
+
+describe("DebouncedSearch", () => {
+  const setup = () => {
+    const clock = new ManualClock();
+    const searched: string[] = [];
+    const results: Array<[string, string[] | null]> = [];
+    const search = new DebouncedSearch(
+      query => {
+        searched.push(query);
+        return [`hits for ${query}`];
+      },
+      { clock, delayMs: 150, onResult: (query, result) => results.push([query, result]) },
+    );
+    return { clock, searched, results, search };
+  };
+
+  it("searches only after typing pauses", () => {
+    const { clock, searched, results, search } = setup();
+    search.update("w");
+    clock.advance(100);
+    search.update("wh");
+    clock.advance(100);
+    search.update("whale");
+    clock.advance(149);
+    expect(searched).toEqual([]);
+
+    clock.advance(1);
+    expect(searched).toEqual(["whale"]);
+    expect(results).toEqual([["whale", ["hits for whale"]]]);
+  });
+
+  it("clears immediately for an empty query and drops the pending search", () => {
+    const { clock, searched, results, search } = setup();
+    search.update("whale");
+    search.update("  ");
+    expect(results).toEqual([["  ", null]]);
+
+    clock.advance(500);
+    expect(searched).toEqual([]);
+  });
+
+  it("reuses the cached result for a repeated query", () => {
+    const { clock, searched, results, search } = setup();
+    search.flush("whale");
+    search.update("whale ");
+    clock.advance(500);
+
+    expect(searched).toEqual(["whale"]);
+    expect(results.map(([query]) => query)).toEqual(["whale", "whale "]);
+  });
+});
@@ -0,0 +1,16 @@
+import { describe, expect, it } from "bun:test";
+import { findOccurrence, occurrenceAt } from "@render-engine";
+
+const page = "The cat sat. The cat ran. The dog sat.";
+
+describe("selection occurrences", () => {
+  it("tells repeated passages apart and finds them again", () => {
+    const second = page.indexOf("The cat", 1);
+
+    expect(occurrenceAt(page, "The cat", 0)).toBe(0);
+    expect(occurrenceAt(page, "The cat", second)).toBe(1);
+    expect(findOccurrence(page, "The cat", 1)).toBe(second);
+    expect(findOccurrence(page, "The cat", 2)).toBe(-1);
+    expect(findOccurrence(page, "", 0)).toBe(-1);
+  });
+});
@@ -0,0 +1,34 @@
+import { describe, expect, it } from "bun:test";
+import { toSemanticHtml } from "@render-engine";
+
+describe("toSemanticHtml", () => {
+  it("renders a heading and a bold paragraph as escaped semantic HTML", () => {
+    const html = toSemanticHtml(
+      '<h1 class="title">Tom &amp; Jerry</h1><div class="body"><p style="margin:0">A <b>bold</b> &lt;move&gt;</p></div>',
+    );
+
+    expect(html).toBe("<h1>Tom &amp; Jerry</h1>\n<p>A <strong>bold</strong> &lt;move&gt;</p>");
+    expect(toSemanticHtml(html)).toBe(html);
+  });
+
+  it("keeps lists and quotes, drops other elements and closes unclosed blocks", () => {
+    const html = toSemanticHtml(
+      '<ul>\n  <li><i>one</i></li>\n  <li>two</li>\n</ul><blockquote><p>quoted<script>x()</script></blockquote><p>open <span>tail</span>',
+    );
+
+    expect(html).toBe(
+      "<ul><li><em>one</em></li>\n<li>two</li>\n</ul>\n<blockquote><p>quoted</p>\n</blockquote>\n<p>open tail</p>",
+    );
+  });
+
+  it("keeps inline code spans and escapes their text", () => {
+    expect(toSemanticHtml('<p>use <code class="fn">foo()</code> here</p>')).toBe("<p>use <code>foo()</code> here</p>");
+    expect(toSemanticHtml("<p><kbd>a &lt; b</kbd></p>")).toBe("<p><code>a &lt; b</code></p>");
+  });
+
+  it("keeps images with only their source and alt text", () => {
+    expect(toSemanticHtml('<p><img class="x" src="map.png" title="The &quot;harbour&quot;"/></p>')).toBe(
+      '<p><img src="map.png" alt="The &quot;harbour&quot;"/></p>',
+    );
+  });
+});
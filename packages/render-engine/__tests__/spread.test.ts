@@ -0,0 +1,43 @@
+import { describe, expect, it } from "bun:test";
+import {
+  adjacentSpreadPage,
+  MIN_SPREAD_COLUMN_EM,
+  resolvePageLayout,
+  SPREAD_GUTTER_PX,
+  spreadLabel,
+  spreadMarkup,
+  spreadPages,
+} from "@render-engine";
+
+describe("two-page spreads", () => {
+  it("falls back to a single page when two readable columns don't fit", () => {
+    const needed = 2 * MIN_SPREAD_COLUMN_EM * 18 + SPREAD_GUTTER_PX;
+
+    expect(resolvePageLayout("twoPage", needed, 18)).toBe("twoPage");
+    expect(resolvePageLayout("twoPage", needed - 1, 18)).toBe("single");
+    // Larger type needs wider columns.
+    expect(resolvePageLayout("twoPage", needed, 24)).toBe("single");
+    expect(resolvePageLayout("single", 4000, 18)).toBe("single");
+  });
+
+  it("shows pages in pairs starting on even pages and moves two at a time", () => {
+    expect(spreadPages(3, 5, "twoPage")).toEqual([2, 3]);
+    expect(spreadPages(4, 5, "twoPage")).toEqual([4]);
+    expect(spreadPages(3, 5, "single")).toEqual([3]);
+    expect(spreadLabel(spreadPages(3, 5, "twoPage"))).toBe("3\u20134");
+    expect(spreadLabel(spreadPages(4, 5, "twoPage"))).toBe("5");
+
+    expect(adjacentSpreadPage(3, 1, 5, "twoPage")).toBe(4);
+    expect(adjacentSpreadPage(4, 1, 5, "twoPage")).toBeNull();
+    expect(adjacentSpreadPage(3, -1, 5, "twoPage")).toBe(0);
+    expect(adjacentSpreadPage(1, -1, 5, "twoPage")).toBeNull();
+    expect(adjacentSpreadPage(3, 1, 5, "single")).toBe(4);
+  });
+
+  it("puts each page in its own column", () => {
+    expect(spreadMarkup(["<p>a</p>", "<p>b</p>"])).toBe(
+      '<div class="reader-spread"><div class="reader-spread-page"><p>a</p></div>' +
+        '<div class="reader-spread-page"><p>b</p></div></div>',
+    );
+  });
+});
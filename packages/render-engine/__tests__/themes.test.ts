@@ -0,0 +1,163 @@
+import { describe, expect, it } from "bun:test";
+import {
+  BUILT_IN_THEMES,
+  buildThemeCss,
+  contrastRatio,
+  findTheme,
+  isDarkTheme,
+  parseThemeJson,
+  PAPER_TEXTURE,
+  resolveSourceColor,
+  themeToJson,
+  type ReaderTheme,
+} from "@render-engine";
+
+const WCAG_AA = 4.5;
+const WCAG_AAA = 7;
+
+function themePairs(theme: ReaderTheme): Array<[string, string]> {
+  return [
+    [theme.text, theme.background],
+    [theme.muted, theme.background],
+    [theme.accent, theme.background],
+  ];
+}
+
+describe("reader themes", () => {
+  it("computes WCAG contrast ratios", () => {
+    expect(contrastRatio("#000000", "#ffffff")).toBeCloseTo(21, 5);
+    expect(contrastRatio("#777", "#777777")).toBeCloseTo(1, 5);
+    expect(contrastRatio("not-a-color", "#ffffff")).toBe(1);
+  });
+
+  it("tells dark themes from light ones by their background", () => {
+    const dark = BUILT_IN_THEMES.filter(isDarkTheme).map(theme => theme.id);
+
+    expect(dark).toEqual(["dark", "high-contrast"]);
+  });
+
+  it("keeps every built-in theme readable", () => {
+    for (const theme of BUILT_IN_THEMES) {
+      expect(contrastRatio(theme.text, theme.background)).toBeGreaterThanOrEqual(WCAG_AA);
+    }
+  });
+
+  it("meets AAA contrast for all high-contrast colour pairs", () => {
+    const theme = findTheme(BUILT_IN_THEMES, "high-contrast");
+
+    expect(theme.id).toBe("high-contrast");
+    for (const [foreground, background] of themePairs(theme)) {
+      expect(contrastRatio(foreground, background)).toBeGreaterThanOrEqual(WCAG_AAA);
+    }
+    expect(contrastRatio(theme.focusRing, theme.background)).toBeGreaterThanOrEqual(WCAG_AAA);
+    expect(theme.focusWidth).toBeGreaterThanOrEqual(3);
+  });
+
+  it("keeps legible source colours and drops ones that vanish into the background", () => {
+    const light = findTheme(BUILT_IN_THEMES, "light");
+    const dark = findTheme(BUILT_IN_THEMES, "dark");
+
+    expect(resolveSourceColor("#b91c1c", light.background)).toBe("#b91c1c");
+    expect(resolveSourceColor("rgb(185, 28, 28)", light.background)).toBe("rgb(185, 28, 28)");
+    expect(resolveSourceColor("#111111", dark.background)).toBeNull();
+    expect(resolveSourceColor("currentcolor", light.background)).toBeNull();
+  });
+
+  it("only lets book colours through when asked to", () => {
+    const theme = findTheme(BUILT_IN_THEMES, "light");
+
+    expect(buildThemeCss(theme)).toContain("color: inherit !important;");
+    expect(buildThemeCss(theme, { respectSourceColors: true })).not.toContain("color: inherit !important;");
+  });
+
+  it("colours bold and italic text only when the theme has an emphasis colour and it is enabled", () => {
+    const dark = findTheme(BUILT_IN_THEMES, "dark");
+    const plain: ReaderTheme = { ...dark, emphasisColor: undefined };
+
+    const css = buildThemeCss(dark, { emphasisColors: true });
+    expect(css).toContain(`--reader-emphasis: ${dark.emphasisColor};`);
+    expect(css).toMatch(/\.reader-scope :is\(b, strong, i, em\) \{\s*color: var\(--reader-emphasis\) !important;/);
+    expect(buildThemeCss(dark)).not.toContain("var(--reader-emphasis)");
+    expect(buildThemeCss(plain, { emphasisColors: true })).not.toContain("--reader-emphasis");
+  });
+
+  it("draws the background texture only when enabled and the theme has one", () => {
+    const sepia = findTheme(BUILT_IN_THEMES, "sepia");
+    const light = findTheme(BUILT_IN_THEMES, "light");
+
+    const css = buildThemeCss(sepia, { texture: true });
+    expect(sepia.backgroundTexture).toBe(PAPER_TEXTURE);
+    expect(css).toContain('--reader-texture: url("data:image/svg+xml;');
+    expect(css).toContain("--reader-texture-repeat: repeat;");
+    expect(css).toMatch(/:host \.reader-scope \{\s*background: transparent;/);
+    expect(buildThemeCss(sepia)).not.toContain("--reader-texture");
+    expect(buildThemeCss(light, { texture: true })).not.toContain("--reader-texture");
+
+    const stretched: ReaderTheme = { ...light, backgroundTexture: { url: 'paper "1".png', fit: "stretch" } };
+    expect(buildThemeCss(stretched, { texture: true })).toContain('--reader-texture: url("paper \\"1\\".png");');
+  });
+
+  it("keeps every built-in emphasis colour readable", () => {
+    for (const theme of BUILT_IN_THEMES) {
+      expect(contrastRatio(theme.emphasisColor!, theme.background)).toBeGreaterThanOrEqual(WCAG_AA);
+    }
+  });
+
+  it("highlights selections with the theme's selection colour", () => {
+    const dark = BUILT_IN_THEMES.find(theme => theme.id === "dark")!;
+    const css = buildThemeCss({ ...dark, selectionColor: "#123456" });
+
+    expect(css).toContain("--reader-selection: #123456;");
+    expect(css).toMatch(/\.reader-scope ::selection \{\s*background: var\(--reader-selection\);/);
+    expect(buildThemeCss({ ...dark, selectionColor: undefined })).not.toContain("::selection");
+    expect(() => parseThemeJson(JSON.stringify({ ...dark, selectionColor: "glow" }))).toThrow('"selectionColor"');
+  });
+
+  it("keeps text readable on every built-in selection colour", () => {
+    // The e-ink theme inverts selections rather than tinting them.
+    for (const theme of BUILT_IN_THEMES.filter(theme => !theme.eInk)) {
+      expect(contrastRatio(theme.text, theme.selectionColor!)).toBeGreaterThanOrEqual(WCAG_AA);
+    }
+  });
+
+  it("keeps the e-ink theme to pure black and white", () => {
+    const eInk = findTheme(BUILT_IN_THEMES, "e-ink");
+    const colors = [eInk.background, eInk.text, eInk.muted, eInk.accent, eInk.focusRing, eInk.emphasisColor];
+
+    expect(eInk.eInk).toBe(true);
+    expect(new Set(colors)).toEqual(new Set(["#ffffff", "#000000"]));
+    expect(buildThemeCss(eInk)).toMatch(
+      /::selection \{\s*color: var\(--reader-bg\);\s*background: var\(--reader-text\);/,
+    );
+  });
+
+  it("round-trips themes through JSON", () => {
+    for (const theme of BUILT_IN_THEMES) {
+      expect(parseThemeJson(themeToJson(theme))).toEqual(theme);
+    }
+
+    const minimal = parseThemeJson(
+      JSON.stringify({ id: "ocean", background: "#001f3f", text: "#f0f8ff", muted: "#7fdbff", accent: "#39cccc", focusRing: "#39cccc" }),
+    );
+    expect(minimal.label).toBe("ocean");
+    expect(minimal.focusWidth).toBe(2);
+  });
+
+  it("rejects malformed theme files", () => {
+    expect(() => parseThemeJson("{not json")).toThrow("Theme file is not valid JSON");
+    expect(() => parseThemeJson(JSON.stringify({ id: "broken", background: "#000000" }))).toThrow('"text"');
+    expect(() =>
+      parseThemeJson(
+        JSON.stringify({ id: "bad", background: "#000", text: "purple-ish", muted: "#111", accent: "#222", focusRing: "#333" }),
+      ),
+    ).toThrow('"text"');
+    expect(() => parseThemeJson("[]")).toThrow("Theme is missing an id");
+    const light = findTheme(BUILT_IN_THEMES, "light");
+    expect(() => parseThemeJson(JSON.stringify({ ...light, emphasisColor: "loud" }))).toThrow('"emphasisColor"');
+    const injected = { ...light, text: "rgb(0,0,0); } .reader-scope * { display:none" };
+    expect(() => parseThemeJson(JSON.stringify(injected))).toThrow('"text"');
+    expect(() => parseThemeJson(JSON.stringify({ ...light, text: "rgb(256, 0, 0)" }))).toThrow('"text"');
+    const badTexture = { ...light, backgroundTexture: { url: "a.png", fit: "cover" } };
+    expect(() => parseThemeJson(JSON.stringify(badTexture))).toThrow('"backgroundTexture"');
+  });
+});
@@ -0,0 +1,46 @@
+import { describe, expect, it } from "bun:test";
+import { disambiguateTocLabels } from "@render-engine";
+
+const chapters = ["ch1.xhtml", "ch2.xhtml", "ch3.xhtml", "ch4.xhtml"];
+const resolve = (href: string) => chapters.indexOf(href.split("#")[0]!);
+
+describe("disambiguateTocLabels", () => {
+  it("appends the parent entry to repeated labels", () => {
+    const toc = [
+      { label: "Vectors", href: "ch1.xhtml", children: [{ label: "Exercises", href: "ch1.xhtml#ex" }] },
+      { label: "Matrices", href: "ch2.xhtml", children: [{ label: "Exercises", href: "ch2.xhtml#ex" }] },
+      { label: "Summary", href: "ch3.xhtml" },
+    ];
+
+    expect(disambiguateTocLabels(toc, resolve)).toEqual([
+      "Vectors",
+      "Exercises（Vectors）",
+      "Matrices",
+      "Exercises（Matrices）",
+      "Summary",
+    ]);
+    expect(toc[0]!.children![0]!.label).toBe("Exercises");
+  });
+
+  it("falls back to the chapter number, then to an ordinal", () => {
+    const flat = [
+      { label: "Exercises", href: "ch2.xhtml" },
+      { label: "Exercises", href: "ch4.xhtml" },
+      { label: "Notes", href: "ch4.xhtml#a" },
+      { label: "notes ", href: "ch4.xhtml#b" },
+    ];
+
+    expect(disambiguateTocLabels(flat, resolve)).toEqual([
+      "Exercises（第 2 章）",
+      "Exercises（第 4 章）",
+      "Notes（1）",
+      "notes（2）",
+    ]);
+  });
+
+  it("leaves labels that only repeat at different levels alone", () => {
+    const toc = [{ label: "Appendix", href: "ch4.xhtml", children: [{ label: "Appendix", href: "ch4.xhtml#a" }] }];
+
+    expect(disambiguateTocLabels(toc, resolve)).toEqual(["Appendix", "Appendix"]);
+  });
+});
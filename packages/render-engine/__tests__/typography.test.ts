@@ -0,0 +1,56 @@
+import { describe, expect, it } from "bun:test";
+import { buildTypographyCss, isRtlLanguage, resolveHyphenationLanguage, resolveTextDirection } from "@render-engine";
+
+describe("buildTypographyCss", () => {
+  it("enables hyphenation for justified English text", () => {
+    const css = buildTypographyCss({ textAlign: "justify", hyphenate: true }, { language: "en-US" });
+
+    expect(resolveHyphenationLanguage("en-US")).toBe("en");
+    expect(css).toContain("text-align: justify;");
+    expect(css).toContain("hyphens: auto;");
+    expect(css).toContain(".reader-scope blockquote");
+  });
+
+  it("skips hyphenation for languages without a dictionary", () => {
+    const css = buildTypographyCss({ textAlign: "justify", hyphenate: true }, { language: "zh-CN" });
+
+    expect(resolveHyphenationLanguage("zh-CN")).toBeNull();
+    expect(css).toContain("text-align: justify;");
+    expect(css).not.toContain("hyphens");
+  });
+
+  it("indents paragraphs except the one following a heading", () => {
+    const css = buildTypographyCss({ textAlign: "start", hyphenate: false, firstLineIndent: 2, paragraphGap: false });
+
+    expect(css).toContain(".reader-scope p {\n  text-indent: 2em;\n}");
+    expect(css).toContain(".reader-scope :is(h1, h2, h3, h4, h5, h6) + p {\n  text-indent: 0;\n}");
+    // The flush rule comes after the indent so it wins at equal specificity.
+    expect(css.indexOf("text-indent: 0")).toBeGreaterThan(css.indexOf("text-indent: 2em"));
+    expect(css).toContain("margin-bottom: 0;");
+  });
+
+  it("keeps the paragraph gap unless asked to drop it", () => {
+    const css = buildTypographyCss({ textAlign: "start", hyphenate: false, firstLineIndent: 1.5, paragraphGap: true });
+
+    expect(css).toContain("text-indent: 1.5em;");
+    expect(css).not.toContain("margin-bottom");
+  });
+
+  it("leaves ragged text untouched", () => {
+    expect(buildTypographyCss({ textAlign: "start", hyphenate: true }, { language: "en" })).toBe("");
+  });
+
+  it("detects right-to-left languages from their primary subtag", () => {
+    expect(isRtlLanguage("ar-EG")).toBe(true);
+    expect(isRtlLanguage("he")).toBe(true);
+    expect(isRtlLanguage("fr")).toBe(false);
+    expect(isRtlLanguage(undefined)).toBe(false);
+  });
+
+  it("prefers the declared page progression over the language", () => {
+    expect(resolveTextDirection("rtl", "ja")).toBe("rtl");
+    expect(resolveTextDirection("ltr", "ar")).toBe("ltr");
+    expect(resolveTextDirection(undefined, "he")).toBe("rtl");
+    expect(resolveTextDirection(undefined, "en")).toBeUndefined();
+  });
+});
@@ -0,0 +1,111 @@
+import { readAttributes } from "./images";
+
+export interface AccessibleRole {
+  role: string;
+  /** Heading level, 1–6. */
+  level?: number;
+}
+
+export interface AccessibleElement extends AccessibleRole {
+  tag: string;
+}
+
+const START_TAG_PATTERN = /<([a-zA-Z][\w:-]*)\b([^>]*)>/g;
+const DOCUMENT_TAGS = new Set(["html", "head", "body"]);
+
+// epub:type → DPUB-ARIA role, after the EPUB 3 structural semantics mapping.
+const EPUB_TYPE_ROLES: Record<string, string> = {
+  abstract: "doc-abstract",
+  acknowledgments: "doc-acknowledgments",
+  afterword: "doc-afterword",
+  appendix: "doc-appendix",
+  backlink: "doc-backlink",
+  biblioentry: "doc-biblioentry",
+  bibliography: "doc-bibliography",
+  biblioref: "doc-biblioref",
+  chapter: "doc-chapter",
+  colophon: "doc-colophon",
+  conclusion: "doc-conclusion",
+  credit: "doc-credit",
+  credits: "doc-credits",
+  dedication: "doc-dedication",
+  endnotes: "doc-endnotes",
+  epigraph: "doc-epigraph",
+  epilogue: "doc-epilogue",
+  errata: "doc-errata",
+  footnote: "doc-footnote",
+  foreword: "doc-foreword",
+  glossary: "doc-glossary",
+  glossref: "doc-glossref",
+  index: "doc-index",
+  introduction: "doc-introduction",
+  noteref: "doc-noteref",
+  notice: "doc-notice",
+  pagebreak: "doc-pagebreak",
+  "page-list": "doc-pagelist",
+  part: "doc-part",
+  preface: "doc-preface",
+  prologue: "doc-prologue",
+  pullquote: "doc-pullquote",
+  qna: "doc-qna",
+  subtitle: "doc-subtitle",
+  tip: "doc-tip",
+  toc: "doc-toc",
+};
+
+function epubTypeRole(epubType: string | undefined): string | undefined {
+  for (const type of (epubType ?? "").trim().split(/\s+/)) {
+    const role = EPUB_TYPE_ROLES[type.toLowerCase()];
+    if (role) return role;
+  }
+  return undefined;
+}
+
+/**
+ * The role assistive technology sees for an element: an explicit `role`, then
+ * the one implied by its `epub:type`, then the element's native role for
+ * headings, links and lists. Null for elements without a meaningful role.
+ */
+export function accessibleRole(tag: string, attributes: Map<string, string>): AccessibleRole | null {
+  const name = tag.toLowerCase();
+  const heading = /^h([1-6])$/.exec(name);
+  const role =
+    attributes.get("role")?.trim().split(/\s+/)[0] ||
+    epubTypeRole(attributes.get("epub:type")) ||
+    (heading ? "heading" : undefined);
+  // ARIA's default heading level is 2; `aria-level` overrides the tag's own.
+  if (role === "heading") return { role, level: Number(attributes.get("aria-level")) || Number(heading?.[1] ?? 2) };
+  if (role) return { role };
+
+  if ((name === "a" || name === "area") && attributes.has("href")) return { role: "link" };
+  if (name === "ul" || name === "ol") return { role: "list" };
+  if (name === "li") return { role: "listitem" };
+  if (name === "nav") return { role: "navigation" };
+  return null;
+}
+
+/**
+ * Adds the DPUB-ARIA role matching each element's `epub:type`, so screen
+ * readers announce chapters, footnotes, note references and page breaks.
+ * Elements that already carry a `role` are left alone.
+ */
+export function applyEpubTypeRoles(html: string): string {
+  return html.replace(START_TAG_PATTERN, (tag, name: string, attributeSource: string) => {
+    if (DOCUMENT_TAGS.has(name.toLowerCase()) || !/epub:type/i.test(attributeSource)) return tag;
+    const attributes = readAttributes(attributeSource);
+    if (attributes.has("role")) return tag;
+    const role = epubTypeRole(attributes.get("epub:type"));
+    return role ? `<${name} role="${role}"${attributeSource}>` : tag;
+  });
+}
+
+/** The elements of a chapter that expose a role, in document order: roughly what a screen reader announces. */
+export function listAccessibleElements(html: string): AccessibleElement[] {
+  const elements: AccessibleElement[] = [];
+  for (const match of html.matchAll(START_TAG_PATTERN)) {
+    const tag = match[1]!.toLowerCase();
+    const role = accessibleRole(tag, readAttributes(match[2]!));
+    if (role) elements.push({ tag, ...role });
+  }
+  return elements;
+}
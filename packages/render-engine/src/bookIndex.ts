@@ -0,0 +1,87 @@
+import { extractHeadings } from "./headings";
+import { htmlToPlainText } from "./plainText";
+import { findPrintPage, type PrintPageTarget } from "./printPages";
+
+export const INDEX_LINK_CLASS = "reader-index-link";
+const INDEX_TITLE_PATTERN = /\bindex\b|\bregister\b|索引/i;
+// Arabic page numbers, or lower-case roman ones for front matter.
+const PAGE = String.raw`(?:\d+|(?=[ivxlc])c{0,3}(?:xc|xl|l?x{0,3})(?:ix|iv|v?i{0,3}))`;
+const PAGE_REF = String.raw`${PAGE}(?:\s*(?:[-\u2013]|&ndash;)\s*${PAGE})?`;
+const PAGE_LIST = String.raw`${PAGE_REF}(?:\s*,\s*${PAGE_REF})*`;
+const INDEX_LINE_PATTERN = new RegExp(String.raw`^(.+?),\s*(${PAGE_LIST})\.?$`);
+const PAGE_TAIL_PATTERN = new RegExp(String.raw`(,\s*)(${PAGE_LIST})(\.?\s*)$`);
+const PAGE_REF_PATTERN = new RegExp(String.raw`(${PAGE})((?:\s*(?:[-\u2013]|&ndash;)\s*${PAGE})?)`, "g");
+const MAX_INDEX_LINE_LENGTH = 120;
+// Without an "Index" title, most lines of a long chapter must look like entries.
+const MIN_UNTITLED_ENTRIES = 20;
+const MIN_UNTITLED_DENSITY = 0.5;
+
+export interface IndexEntry {
+  term: string;
+  /** Page labels in order; a range such as "42–45" contributes its first page. */
+  pages: string[];
+}
+
+export interface IndexChapterHints {
+  /** Defaults to the chapter's first heading. */
+  title?: string;
+  /** `epub:type` of the chapter root. */
+  semanticType?: string;
+}
+
+/** Reads a short `Widgets, 42, 57` style index line; null for anything else. */
+export function parseIndexLine(line: string): IndexEntry | null {
+  const trimmed = line.trim();
+  if (trimmed.length > MAX_INDEX_LINE_LENGTH) return null;
+  const match = INDEX_LINE_PATTERN.exec(trimmed);
+  const term = match?.[1]?.trim();
+  if (!match || !term) return null;
+  return { term, pages: Array.from(match[2]!.matchAll(PAGE_REF_PATTERN), ref => ref[1]!) };
+}
+
+/** The page-list entries an index entry points to, skipping pages the book does not mark. */
+export function indexEntryTargets<T extends PrintPageTarget>(entry: IndexEntry, pageMap: T[]): T[] {
+  return entry.pages.map(page => findPrintPage(pageMap, page)).filter((target): target is T => Boolean(target));
+}
+
+/** True for a back-of-book index: titled or typed as one, or made almost entirely of entry lines. */
+export function isIndexChapter(html: string, hints: IndexChapterHints = {}): boolean {
+  const lines = htmlToPlainText(html)
+    .split("\n")
+    .filter(line => line.trim());
+  const entries = lines.filter(line => parseIndexLine(line)).length;
+  if (entries === 0) return false;
+  const title = hints.title ?? extractHeadings(html)[0]?.text ?? "";
+  if (INDEX_TITLE_PATTERN.test(title) || /\bindex\b/i.test(hints.semanticType ?? "")) return true;
+  return entries >= MIN_UNTITLED_ENTRIES && entries / lines.length >= MIN_UNTITLED_DENSITY;
+}
+
+function escapeAttribute(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/"/g, "&quot;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+/**
+ * Turns the page numbers ending each index entry into links marked with
+ * `data-book-href`, pointing where `pageMap` places that print page. Numbers
+ * already inside links, and pages the map does not know, are left alone.
+ */
+export function linkIndexEntries(html: string, pageMap: PrintPageTarget[]): string {
+  if (pageMap.length === 0) return html;
+  let linkDepth = 0;
+  return html.replace(/<[^>]*>|[^<]+/g, token => {
+    if (token.startsWith("<")) {
+      if (/^<a\b/i.test(token) && !token.endsWith("/>")) linkDepth += 1;
+      else if (/^<\/a\s*>/i.test(token)) linkDepth = Math.max(0, linkDepth - 1);
+      return token;
+    }
+    if (linkDepth > 0) return token;
+    return token.replace(PAGE_TAIL_PATTERN, (_tail, lead: string, pages: string, end: string) => {
+      const linked = pages.replace(PAGE_REF_PATTERN, (ref, page: string) => {
+        const target = findPrintPage(pageMap, page);
+        if (!target) return ref;
+        return `<a href="#" class="${INDEX_LINK_CLASS}" data-book-href="${escapeAttribute(target.href)}">${ref}</a>`;
+      });
+      return `${lead}${linked}${end}`;
+    });
+  });
+}
@@ -0,0 +1,33 @@
+export const DEFAULT_CHARS_PER_PAGE = 1800;
+
+export interface SyntheticPages {
+  /** Zero-based page each chapter starts on; every chapter starts on a fresh page. */
+  chapterStarts: number[];
+  totalPages: number;
+}
+
+/**
+ * Approximate book-wide page numbers from text length alone: each chapter
+ * takes `ceil(length / charsPerPage)` pages (at least one). These are not laid
+ * out pages, just a stable "page X of Y" for readers who expect one.
+ */
+export function buildSyntheticPages(chapterLengths: number[], charsPerPage = DEFAULT_CHARS_PER_PAGE): SyntheticPages {
+  const perPage = Math.max(1, charsPerPage);
+  const chapterStarts: number[] = [];
+  let totalPages = 0;
+  for (const length of chapterLengths) {
+    chapterStarts.push(totalPages);
+    totalPages += Math.max(1, Math.ceil(length / perPage));
+  }
+  return { chapterStarts, totalPages };
+}
+
+/** One-based page number for a position `fraction` (0–1) of the way through a chapter. */
+export function syntheticPageAt(pages: SyntheticPages, chapterIndex: number, fraction: number): number {
+  if (pages.totalPages === 0) return 0;
+  const index = Math.min(Math.max(chapterIndex, 0), pages.chapterStarts.length - 1);
+  const start = pages.chapterStarts[index]!;
+  const end = pages.chapterStarts[index + 1] ?? pages.totalPages;
+  const offset = Math.floor(Math.min(Math.max(fraction, 0), 1) * (end - start));
+  return start + Math.min(offset, end - start - 1) + 1;
+}
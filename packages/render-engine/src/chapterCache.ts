@@ -0,0 +1,135 @@
+export type CancelTask = () => void;
+export type ScheduleTask = (task: () => void) => CancelTask;
+
+export interface ChapterCacheOptions<T> {
+  /** Maximum number of materialized chapters kept in memory. */
+  capacity: number;
+  build: (index: number) => T;
+  /** Defers prefetch work; defaults to idle callbacks (or a zero timeout). */
+  schedule?: ScheduleTask;
+}
+
+export interface PreloadOptions {
+  /** Called after each chapter with how many of the `total` are ready. */
+  onProgress?: (loaded: number, total: number) => void;
+  signal?: AbortSignal;
+}
+
+const defaultSchedule: ScheduleTask = task => {
+  if (typeof requestIdleCallback === "function") {
+    const handle = requestIdleCallback(() => task());
+    return () => cancelIdleCallback(handle);
+  }
+  const handle = setTimeout(task, 0);
+  return () => clearTimeout(handle);
+};
+
+/**
+ * Least-recently-used cache of prepared chapters with background prefetching of
+ * the neighbours of the chapter being read.
+ */
+export class ChapterCache<T> {
+  private readonly entries = new Map<number, T>();
+  private capacity: number;
+  private readonly build: (index: number) => T;
+  private readonly schedule: ScheduleTask;
+  private pendingPrefetch: CancelTask[] = [];
+
+  constructor(options: ChapterCacheOptions<T>) {
+    this.capacity = Math.max(1, options.capacity);
+    this.build = options.build;
+    this.schedule = options.schedule ?? defaultSchedule;
+  }
+
+  get size() {
+    return this.entries.size;
+  }
+
+  has(index: number) {
+    return this.entries.has(index);
+  }
+
+  /** Changes how many chapters stay materialized; shrinking evicts the least recently used right away. */
+  resize(capacity: number) {
+    this.capacity = Math.max(1, capacity);
+    this.evict();
+  }
+
+  get(index: number): T {
+    const existing = this.entries.get(index);
+    if (existing !== undefined) {
+      // Re-insert to mark as most recently used.
+      this.entries.delete(index);
+      this.entries.set(index, existing);
+      return existing;
+    }
+
+    const value = this.build(index);
+    this.entries.set(index, value);
+    this.evict();
+    return value;
+  }
+
+  /**
+   * Schedules the chapters around `index` to be built in the background. Any
+   * prefetch still queued from an earlier position is cancelled first.
+   */
+  prefetchAround(index: number, total: number, radius = 1) {
+    this.cancelPrefetch();
+
+    const targets: number[] = [];
+    for (let offset = 1; offset <= radius; offset += 1) {
+      targets.push(index + offset, index - offset);
+    }
+
+    for (const target of targets) {
+      if (target < 0 || target >= total || this.entries.has(target)) continue;
+      this.pendingPrefetch.push(
+        this.schedule(() => {
+          if (!this.entries.has(target)) {
+            this.get(target);
+          }
+        }),
+      );
+    }
+  }
+
+  /**
+   * Builds every chapter up front instead of on demand, yielding to the
+   * scheduler between chapters so progress can be painted. The capacity must
+   * cover the whole book or the first chapters are evicted again.
+   */
+  async preloadAll(total: number, options: PreloadOptions = {}) {
+    this.cancelPrefetch();
+    for (let index = 0; index < total; index += 1) {
+      if (options.signal?.aborted) return;
+      if (!this.entries.has(index)) {
+        await new Promise<void>(resolve => {
+          this.schedule(() => resolve());
+        });
+        if (options.signal?.aborted) return;
+        this.get(index);
+      }
+      options.onProgress?.(index + 1, total);
+    }
+  }
+
+  cancelPrefetch() {
+    for (const cancel of this.pendingPrefetch) {
+      cancel();
+    }
+    this.pendingPrefetch = [];
+  }
+
+  clear() {
+    this.cancelPrefetch();
+    this.entries.clear();
+  }
+
+  private evict() {
+    while (this.entries.size > this.capacity) {
+      const oldest = this.entries.keys().next().value as number;
+      this.entries.delete(oldest);
+    }
+  }
+}
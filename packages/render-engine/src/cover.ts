@@ -0,0 +1,114 @@
+export interface CoverSource {
+  title?: string;
+  creator?: string;
+  authors?: string[];
+}
+
+const COVER_WIDTH = 300;
+const COVER_HEIGHT = 450;
+// Width budget per line in "units": Latin characters count 1, wide (CJK) characters 2.
+const TITLE_LINE_UNITS = 20;
+const TITLE_MAX_LINES = 5;
+const AUTHOR_LINE_UNITS = 30;
+const WIDE_CHARACTER = /[\u1100-\u115f\u2e80-\ua4cf\uac00-\ud7a3\uf900-\ufaff\ufe30-\ufe4f\uff00-\uff60\uffe0-\uffe6]/;
+
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+/** FNV-1a, so a title always maps to the same color across sessions and machines. */
+function hashString(value: string): number {
+  let hash = 0x811c9dc5;
+  for (const char of value) {
+    hash ^= char.codePointAt(0)!;
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return hash >>> 0;
+}
+
+/** Background hue for a title's placeholder cover, as an `hsl()` color. */
+export function coverColor(title: string): string {
+  const hash = hashString(title.trim().toLowerCase());
+  const hue = hash % 360;
+  const saturation = 35 + ((hash >>> 9) % 20);
+  return `hsl(${hue} ${saturation}% 32%)`;
+}
+
+/** Greedy line wrapping that prefers spaces and breaks anywhere inside CJK runs. */
+export function wrapCoverText(text: string, maxUnits: number, maxLines = Number.POSITIVE_INFINITY): string[] {
+  const lines: string[] = [];
+  let line = "";
+  let units = 0;
+  let lastSpace = -1;
+
+  for (const char of text.replace(/\s+/g, " ").trim()) {
+    const width = WIDE_CHARACTER.test(char) ? 2 : 1;
+    if (units + width > maxUnits && line) {
+      const breakAt = char === " " ? line.length : lastSpace;
+      if (breakAt > 0) {
+        lines.push(line.slice(0, breakAt).trimEnd());
+        line = line.slice(breakAt).trimStart();
+      } else {
+        lines.push(line);
+        line = "";
+      }
+      units = [...line].reduce((sum, rest) => sum + (WIDE_CHARACTER.test(rest) ? 2 : 1), 0);
+      lastSpace = -1;
+      if (char === " ") continue;
+    }
+    if (char === " ") lastSpace = line.length;
+    line += char;
+    units += width;
+  }
+  if (line) lines.push(line);
+
+  if (lines.length > maxLines) {
+    const kept = lines.slice(0, maxLines);
+    kept[maxLines - 1] = `${kept[maxLines - 1]!.replace(/.$/u, "").trimEnd()}…`;
+    return kept;
+  }
+  return lines;
+}
+
+/**
+ * Draws a placeholder cover for books without one: the title and author on a
+ * background whose color is derived from the title. Returns SVG markup.
+ */
+export function generateCoverSvg(source: CoverSource): string {
+  const title = source.title?.trim() || "未命名书籍";
+  const author = source.authors?.length ? source.authors.join("、") : source.creator?.trim() ?? "";
+  const titleLines = wrapCoverText(title, TITLE_LINE_UNITS, TITLE_MAX_LINES);
+  const authorLines = author ? wrapCoverText(author, AUTHOR_LINE_UNITS, 2) : [];
+
+  const tspans = (lines: string[], top: number, lineHeight: number) =>
+    lines
+      .map((line, index) => `<tspan x="${COVER_WIDTH / 2}" y="${top + index * lineHeight}">${escapeXml(line)}</tspan>`)
+      .join("");
+  // The title block is centered around the upper third; the author sits near the bottom.
+  const titleText = tspans(titleLines, 150 - (titleLines.length - 1) * 17, 34);
+  const authorText = tspans(authorLines, 380, 22);
+
+  return [
+    `<svg xmlns="http://www.w3.org/2000/svg" width="${COVER_WIDTH}" height="${COVER_HEIGHT}"`,
+    ` viewBox="0 0 ${COVER_WIDTH} ${COVER_HEIGHT}">`,
+    `<rect width="100%" height="100%" fill="${coverColor(title)}"/>`,
+    `<rect x="24" y="24" width="${COVER_WIDTH - 48}" height="${COVER_HEIGHT - 48}"`,
+    ` fill="none" stroke="rgba(255,255,255,0.35)" stroke-width="2"/>`,
+    `<text text-anchor="middle" font-family="Georgia, 'Songti SC', serif" font-size="26" font-weight="bold"`,
+    ` fill="#f8fafc">${titleText}</text>`,
+    authorText
+      ? `<text text-anchor="middle" font-family="system-ui, sans-serif" font-size="16"` +
+        ` fill="rgba(248,250,252,0.85)">${authorText}</text>`
+      : "",
+    "</svg>",
+  ].join("");
+}
+
+/** `generateCoverSvg` as a data URL, ready for an `<img src>`. */
+export function generateCoverDataUrl(source: CoverSource): string {
+  return `data:image/svg+xml;charset=utf-8,${encodeURIComponent(generateCoverSvg(source))}`;
+}
@@ -0,0 +1,25 @@
+// Each inline styling element gets its own outline color and a badge naming the flag.
+const DEBUG_OUTLINE_RULES: Array<{ selector: string; color: string; badge: string }> = [
+  { selector: ":is(b, strong), [style*='font-weight']", color: "#ef4444", badge: "B" },
+  { selector: ":is(i, em, cite), [style*='italic']", color: "#3b82f6", badge: "I" },
+  { selector: ":is(code, kbd, samp, tt)", color: "#22c55e", badge: "C" },
+  { selector: "span", color: "#a855f7", badge: "span" },
+];
+
+/**
+ * Developer aid for chasing styling bugs: outlines every inline element that
+ * carries bold, italic or code styling and labels it with a small badge. Only
+ * CSS outlines and `::after` badges are added, so the markup and the text content
+ * (including copied text) stay the same.
+ */
+export function buildDebugOutlineCss(): string {
+  return DEBUG_OUTLINE_RULES.map(({ selector, color, badge }) => {
+    const scoped = `.reader-scope :is(${selector})`;
+    return [
+      `${scoped} {\n  outline: 1px solid ${color};\n  outline-offset: 1px;\n}`,
+      `${scoped}::after {\n  content: "${badge}";\n  margin-left: 1px;\n  padding: 0 2px;\n  border-radius: 2px;` +
+        `\n  background: ${color};\n  color: #fff;\n  font: 600 9px/1.2 ui-monospace, monospace;` +
+        `\n  font-style: normal;\n  vertical-align: super;\n  user-select: none;\n  pointer-events: none;\n}`,
+    ].join("\n");
+  }).join("\n");
+}
@@ -0,0 +1,39 @@
+/** Wheel travel, in pixels, past the top or bottom edge before the page turns. */
+export const EDGE_SCROLL_THRESHOLD = 300;
+/** A pause this long between wheel events starts the count again. */
+export const EDGE_SCROLL_IDLE_MS = 400;
+
+export interface EdgeOverscroll {
+  /** 1 while pushing past the bottom, -1 past the top, 0 when not at an edge. */
+  direction: 1 | -1 | 0;
+  distance: number;
+  lastAt: number;
+}
+
+export const NO_OVERSCROLL: EdgeOverscroll = { direction: 0, distance: 0, lastAt: 0 };
+
+export interface ScrollEdges {
+  atTop: boolean;
+  atBottom: boolean;
+}
+
+/**
+ * Adds one wheel movement to the overscroll gathered at an edge. Only
+ * movement pushing further past the edge the content already rests at
+ * counts, so scrolling to the end and stopping never turns the page on its
+ * own. `turn` is set once the threshold is crossed, and the count restarts.
+ */
+export function accumulateOverscroll(
+  current: EdgeOverscroll,
+  deltaY: number,
+  edges: ScrollEdges,
+  now: number,
+  threshold = EDGE_SCROLL_THRESHOLD,
+): { overscroll: EdgeOverscroll; turn: 1 | -1 | null } {
+  const direction = deltaY > 0 && edges.atBottom ? 1 : deltaY < 0 && edges.atTop ? -1 : 0;
+  if (direction === 0) return { overscroll: NO_OVERSCROLL, turn: null };
+  const continuing = current.direction === direction && now - current.lastAt <= EDGE_SCROLL_IDLE_MS;
+  const distance = (continuing ? current.distance : 0) + Math.abs(deltaY);
+  if (distance >= threshold) return { overscroll: NO_OVERSCROLL, turn: direction };
+  return { overscroll: { direction, distance, lastAt: now }, turn: null };
+}
@@ -0,0 +1,118 @@
+import { decodeEntities } from "./headings";
+
+export interface Epigraph {
+  quote: string;
+  attribution?: string;
+}
+
+interface EpigraphMatch extends Epigraph {
+  /** Offsets of the opening tags to mark. */
+  quoteTag: number;
+  attributionTag?: number;
+}
+
+export const EPIGRAPH_CLASS = "reader-epigraph";
+export const EPIGRAPH_ATTRIBUTION_CLASS = "reader-epigraph-attribution";
+
+// Headings, wrappers and whitespace that may come before the epigraph.
+const LEADING_PATTERN =
+  /(?:\s+|<!--[\s\S]*?-->|<\/?(?:section|div|article|main|header|hgroup)\b[^>]*>|<h([1-6])\b[^>]*>[\s\S]*?<\/h\1>)*/iy;
+const BLOCKQUOTE_PATTERN = /<blockquote\b[^>]*>([\s\S]*?)<\/blockquote>/iy;
+const PARAGRAPH_PATTERN = /\s*<p\b[^>]*>([\s\S]*?)<\/p>/iy;
+const INNER_BLOCK_PATTERN = /<(p|footer|cite|div)\b[^>]*>([\s\S]*?)<\/\1>/gi;
+const ITALIC_PATTERN = /<(i|em)\b[^>]*>[\s\S]*?<\/\1>/gi;
+const ATTRIBUTION_DASH = /^(?:[—―–]|--|~)/;
+const MAX_ATTRIBUTION_LENGTH = 120;
+const MAX_ITALIC_QUOTE_LENGTH = 400;
+
+function toText(html: string): string {
+  return decodeEntities(html.replace(/<[^>]*>/g, " "))
+    .replace(/\s+/g, " ")
+    .trim();
+}
+
+function isAttribution(text: string): boolean {
+  return ATTRIBUTION_DASH.test(text) && text.length <= MAX_ATTRIBUTION_LENGTH;
+}
+
+function attributionAfter(html: string, offset: number): { tag: number; text: string } | null {
+  PARAGRAPH_PATTERN.lastIndex = offset;
+  const match = PARAGRAPH_PATTERN.exec(html);
+  if (!match) return null;
+  const text = toText(match[1]!);
+  return isAttribution(text) ? { tag: match.index + match[0].indexOf("<"), text } : null;
+}
+
+function findEpigraph(html: string): EpigraphMatch | null {
+  const body = /<body\b[^>]*>/i.exec(html);
+  LEADING_PATTERN.lastIndex = body ? body.index + body[0].length : 0;
+  LEADING_PATTERN.exec(html);
+  const start = LEADING_PATTERN.lastIndex;
+
+  BLOCKQUOTE_PATTERN.lastIndex = start;
+  const blockquote = BLOCKQUOTE_PATTERN.exec(html);
+  if (blockquote) {
+    const inner = blockquote[1]!;
+    const innerStart = start + blockquote[0].indexOf(">") + 1;
+    // The attribution is either the quote's last block or a paragraph right after it.
+    const last = [...inner.matchAll(INNER_BLOCK_PATTERN)].at(-1);
+    const lastText = last ? toText(last[2]!) : "";
+    if (last && isAttribution(lastText) && toText(inner.slice(0, last.index))) {
+      return {
+        quote: toText(inner.slice(0, last.index)),
+        attribution: lastText,
+        quoteTag: start,
+        attributionTag: innerStart + last.index!,
+      };
+    }
+    const following = attributionAfter(html, start + blockquote[0].length);
+    const quote = toText(inner);
+    if (!quote) return null;
+    return following
+      ? { quote, attribution: following.text, quoteTag: start, attributionTag: following.tag }
+      : { quote, quoteTag: start };
+  }
+
+  // Otherwise a short, wholly italic paragraph counts only with an attribution under it.
+  PARAGRAPH_PATTERN.lastIndex = start;
+  const paragraph = PARAGRAPH_PATTERN.exec(html);
+  if (!paragraph) return null;
+  const inner = paragraph[1]!;
+  const quote = toText(inner);
+  if (!quote || quote.length > MAX_ITALIC_QUOTE_LENGTH || toText(inner.replace(ITALIC_PATTERN, ""))) return null;
+  const following = attributionAfter(html, start + paragraph[0].length);
+  return following ? { quote, attribution: following.text, quoteTag: start, attributionTag: following.tag } : null;
+}
+
+/**
+ * Finds an epigraph opening the chapter: a blockquote right after the
+ * headings, or a short all-italic paragraph followed by a dash attribution
+ * such as "—— 鲁迅". Later quotes are never treated as epigraphs.
+ */
+export function detectEpigraph(html: string): Epigraph | null {
+  const match = findEpigraph(html);
+  if (!match) return null;
+  return match.attribution ? { quote: match.quote, attribution: match.attribution } : { quote: match.quote };
+}
+
+function addClass(html: string, tagStart: number, className: string): string {
+  const tagEnd = html.indexOf(">", tagStart);
+  const tag = html.slice(tagStart, tagEnd);
+  const classAttribute = /\sclass\s*=\s*["']/i.exec(tag);
+  const valueStart = classAttribute ? classAttribute.index + classAttribute[0].length : -1;
+  const marked =
+    valueStart === -1
+      ? tag.replace(/^<[\w:-]+/, name => `${name} class="${className}"`)
+      : `${tag.slice(0, valueStart)}${className} ${tag.slice(valueStart)}`;
+  return html.slice(0, tagStart) + marked + html.slice(tagEnd);
+}
+
+/** Adds the epigraph classes to the chapter's opening epigraph, if it has one. */
+export function markEpigraph(html: string): string {
+  const match = findEpigraph(html);
+  if (!match) return html;
+  // The attribution tag always comes after the quote's, so marking it first keeps the quote offset valid.
+  const withAttribution =
+    match.attributionTag === undefined ? html : addClass(html, match.attributionTag, EPIGRAPH_ATTRIBUTION_CLASS);
+  return addClass(withAttribution, match.quoteTag, EPIGRAPH_CLASS);
+}
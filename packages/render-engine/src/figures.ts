@@ -0,0 +1,56 @@
+import { decodeEntities } from "./headings";
+import { readAttributes, resolveImageAlt } from "./images";
+
+export interface FigureRef {
+  chapterIndex: number;
+  alt: string;
+  caption?: string;
+}
+
+const FIGURE_PATTERN = /<figure\b[^>]*>([\s\S]*?)<\/figure>/gi;
+const FIGCAPTION_PATTERN = /<figcaption\b[^>]*>([\s\S]*?)<\/figcaption>/i;
+const IMG_PATTERN = /<img\b([^>]*?)\/?>/gi;
+// A caption paragraph directly after a bare image, e.g. <p class="caption">.
+const CAPTION_PARAGRAPH_PATTERN = /^\s*(?:<\/[a-z]+>\s*)*<p\b[^>]*class\s*=\s*["'][^"']*caption[^"']*["'][^>]*>([\s\S]*?)<\/p>/i;
+
+function toPlainText(html: string): string {
+  return decodeEntities(html.replace(/<[^>]*>/g, " "))
+    .replace(/\s+/g, " ")
+    .trim();
+}
+
+/** Lists the images of one chapter in document order with their captions. */
+export function extractFigures(html: string, chapterIndex: number): FigureRef[] {
+  const figureRanges: Array<{ start: number; end: number; caption?: string }> = [];
+  for (const match of html.matchAll(FIGURE_PATTERN)) {
+    const captionMatch = FIGCAPTION_PATTERN.exec(match[1]!);
+    figureRanges.push({
+      start: match.index!,
+      end: match.index! + match[0].length,
+      caption: captionMatch ? toPlainText(captionMatch[1]!) || undefined : undefined,
+    });
+  }
+
+  const figures: FigureRef[] = [];
+  for (const match of html.matchAll(IMG_PATTERN)) {
+    const position = match.index!;
+    const figure = figureRanges.find(range => position >= range.start && position < range.end);
+    let caption = figure?.caption;
+    if (!figure) {
+      const following = CAPTION_PARAGRAPH_PATTERN.exec(html.slice(position + match[0].length));
+      caption = following ? toPlainText(following[1]!) || undefined : undefined;
+    }
+
+    figures.push({
+      chapterIndex,
+      alt: resolveImageAlt(readAttributes(match[1]!)),
+      caption,
+    });
+  }
+  return figures;
+}
+
+/** Collects the figures of a whole book, chapter by chapter. */
+export function collectFigures(chapters: Array<{ content: string }>): FigureRef[] {
+  return chapters.flatMap((chapter, index) => extractFigures(chapter.content, index));
+}
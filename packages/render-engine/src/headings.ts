@@ -0,0 +1,168 @@
+export interface HeadingInfo {
+  level: number;
+  text: string;
+}
+
+const HEADING_PATTERN = /<h([1-6])\b[^>]*>([\s\S]*?)<\/h\1>/gi;
+
+const NAMED_ENTITIES: Record<string, string> = {
+  amp: "&",
+  lt: "<",
+  gt: ">",
+  quot: '"',
+  apos: "'",
+  nbsp: " ",
+};
+
+export function decodeEntities(input: string): string {
+  return input.replace(/&(#x[0-9a-f]+|#\d+|[a-z]+);/gi, (match, entity: string) => {
+    if (entity[0] === "#") {
+      const codePoint =
+        entity[1] === "x" || entity[1] === "X" ? parseInt(entity.slice(2), 16) : parseInt(entity.slice(1), 10);
+      return codePoint >= 0 && codePoint <= 0x10ffff ? String.fromCodePoint(codePoint) : match;
+    }
+    return NAMED_ENTITIES[entity.toLowerCase()] ?? match;
+  });
+}
+
+function headingText(inner: string): string {
+  return decodeEntities(inner.replace(/<[^>]*>/g, " "))
+    .replace(/\s+/g, " ")
+    .trim();
+}
+
+export function extractHeadings(html: string): HeadingInfo[] {
+  const headings: HeadingInfo[] = [];
+  for (const match of html.matchAll(HEADING_PATTERN)) {
+    const text = headingText(match[2]!);
+    if (text) {
+      headings.push({ level: Number(match[1]), text });
+    }
+  }
+  return headings;
+}
+
+/**
+ * Hierarchical numbers ("1", "1.1", "1.1.1") for a sequence of heading levels.
+ * Skipped levels do not leave gaps: an h3 directly under an h1 is numbered "1.1".
+ */
+export function numberHeadings(levels: number[]): string[] {
+  const stack: Array<{ level: number; count: number }> = [];
+  return levels.map(level => {
+    let popped: { level: number; count: number } | undefined;
+    while (stack.length > 0 && stack[stack.length - 1]!.level > level) {
+      popped = stack.pop();
+    }
+    const top = stack[stack.length - 1];
+    if (top && top.level === level) {
+      top.count += 1;
+    } else {
+      // A heading shallower than the one before it but deeper than its parent takes that sibling's place.
+      stack.push({ level, count: popped ? popped.count + 1 : 1 });
+    }
+    return stack.map(entry => entry.count).join(".");
+  });
+}
+
+/** Numbers the headings of a whole book; returns one list of numbers per chapter. */
+export function numberBookHeadings(chapters: string[]): string[][] {
+  const perChapter = chapters.map(html => extractHeadings(html).map(heading => heading.level));
+  const numbers = numberHeadings(perChapter.flat());
+  let offset = 0;
+  return perChapter.map(levels => {
+    const slice = numbers.slice(offset, offset + levels.length);
+    offset += levels.length;
+    return slice;
+  });
+}
+
+/**
+ * Tags each non-empty heading with `data-heading-number` so the number can be
+ * shown through CSS while the heading text itself stays untouched.
+ */
+export function applyHeadingNumbers(html: string, numbers: string[]): string {
+  let index = 0;
+  return html.replace(HEADING_PATTERN, (match, _level: string, inner: string) => {
+    if (!headingText(inner)) return match;
+    const number = numbers[index++];
+    return number ? match.replace(/^<h[1-6]\b/i, tag => `${tag} data-heading-number="${number}"`) : match;
+  });
+}
+
+export interface ChapterTitleSource {
+  id: string;
+  html: string;
+  tocLabel?: string | null;
+}
+
+export interface ChapterTitleOptions {
+  /** Let the chapter's first heading win over the TOC label (for books with generic labels). */
+  preferHeading?: boolean;
+}
+
+const PLAIN_TEXT_TITLE_LENGTH = 40;
+const TITLE_LINE_BREAK_PATTERN =
+  /<br\s*\/?>|<\/?(?:p|div|h[1-6]|li|blockquote|pre|section|article|header|figcaption)\b[^>]*>/gi;
+
+export function deriveChapterTitle(source: ChapterTitleSource, options: ChapterTitleOptions = {}): string {
+  const tocLabel = source.tocLabel?.trim() || undefined;
+  const heading = extractHeadings(source.html)[0]?.text;
+  const [first, second] = options.preferHeading ? [heading, tocLabel] : [tocLabel, heading];
+  if (first) return first;
+  if (second) return second;
+
+  // First non-empty block of text. BOMs and &nbsp;-only paragraphs count as whitespace for `\s`.
+  const bodyMarkup = source.html.replace(/<(script|style|head)\b[\s\S]*?<\/\1>/gi, "").replace(/\s+/g, " ");
+  const firstLine =
+    decodeEntities(bodyMarkup.replace(TITLE_LINE_BREAK_PATTERN, "\n").replace(/<[^>]*>/g, " "))
+      .split("\n")
+      .map(line => line.replace(/\s+/g, " ").trim())
+      .find(line => line.length > 0) ?? "";
+  if (firstLine) {
+    return firstLine.length > PLAIN_TEXT_TITLE_LENGTH ? `${firstLine.slice(0, PLAIN_TEXT_TITLE_LENGTH)}…` : firstLine;
+  }
+  return source.id;
+}
+
+/** Returns the last marker at or above `position` (markers must be sorted by offset). */
+export function findActiveHeading<T extends { offset: number }>(markers: T[], position: number): T | undefined {
+  let active: T | undefined;
+  for (const marker of markers) {
+    if (marker.offset > position) break;
+    active = marker;
+  }
+  return active;
+}
+
+/**
+ * The next (`direction` 1) or previous (-1) marker relative to `position`, ignoring
+ * markers within `tolerance` of it so repeated jumps do not stick on the same heading.
+ */
+export function findAdjacentHeading<T extends { offset: number }>(
+  markers: T[],
+  position: number,
+  direction: 1 | -1,
+  tolerance = 4,
+): T | undefined {
+  if (direction === 1) {
+    return markers.find(marker => marker.offset > position + tolerance);
+  }
+  return markers.findLast(marker => marker.offset < position - tolerance);
+}
+
+/**
+ * Index of the nearest page past `start` in `direction` that holds a heading, or -1.
+ * Pass `start` of -1 or `pages.length` to search from either end.
+ */
+export function findPageWithHeading(
+  pages: Array<{ fragments: Array<{ html: string }> }>,
+  start: number,
+  direction: 1 | -1,
+): number {
+  for (let index = start + direction; index >= 0 && index < pages.length; index += direction) {
+    if (pages[index]!.fragments.some(fragment => extractHeadings(fragment.html).length > 0)) {
+      return index;
+    }
+  }
+  return -1;
+}
@@ -0,0 +1,35 @@
+export const INVERTED_IMAGE_CLASS = "reader-inverted-image";
+/** Side of the thumbnail images are scaled down to before sampling. */
+export const IMAGE_SAMPLE_SIZE = 32;
+// Line art uses a handful of flat colours on a light ground; photos spread over many.
+const DIAGRAM_COLOR_COUNT = 6;
+const DIAGRAM_COLOR_COVERAGE = 0.85;
+const LIGHT_IMAGE_LUMINANCE = 0.6;
+
+/**
+ * Whether RGBA pixels (as from `getImageData`) look like a diagram worth
+ * inverting on a dark theme: mostly light, with nearly every pixel in a few
+ * colours. Transparent pixels count as the white page they were drawn for.
+ */
+export function isLikelyDiagram(rgba: ArrayLike<number>): boolean {
+  const buckets = new Map<number, number>();
+  let pixels = 0;
+  let luminance = 0;
+  for (let index = 0; index + 3 < rgba.length; index += 4) {
+    const alpha = rgba[index + 3]! / 255;
+    const [r, g, b] = [rgba[index]!, rgba[index + 1]!, rgba[index + 2]!].map(value =>
+      Math.round(value * alpha + 255 * (1 - alpha)),
+    ) as [number, number, number];
+    pixels += 1;
+    luminance += (0.2126 * r + 0.7152 * g + 0.0722 * b) / 255;
+    // 4 bits per channel, so antialiased edges and JPEG noise fall into their colour's bucket.
+    const key = ((r >> 4) << 8) | ((g >> 4) << 4) | (b >> 4);
+    buckets.set(key, (buckets.get(key) ?? 0) + 1);
+  }
+  if (pixels === 0 || luminance / pixels < LIGHT_IMAGE_LUMINANCE) return false;
+  const dominant = [...buckets.values()]
+    .sort((a, b) => b - a)
+    .slice(0, DIAGRAM_COLOR_COUNT)
+    .reduce((sum, count) => sum + count, 0);
+  return dominant / pixels >= DIAGRAM_COLOR_COVERAGE;
+}
@@ -0,0 +1,41 @@
+import { decodeEntities } from "./headings";
+
+const IMG_TAG_PATTERN = /<img\b([^>]*?)(\/?)>/gi;
+const ATTRIBUTE_PATTERN = /([^\s=/>]+)(?:\s*=\s*("[^"]*"|'[^']*'|[^\s>]+))?/g;
+
+export function readAttributes(source: string): Map<string, string> {
+  const attributes = new Map<string, string>();
+  for (const match of source.matchAll(ATTRIBUTE_PATTERN)) {
+    const raw = match[2] ?? "";
+    const unquoted = /^["']/.test(raw) ? raw.slice(1, -1) : raw;
+    attributes.set(match[1]!.toLowerCase(), decodeEntities(unquoted));
+  }
+  return attributes;
+}
+
+/** Picks `alt`, then `title`, then `aria-label` — the first one with visible text. */
+export function resolveImageAlt(attributes: Map<string, string>): string {
+  for (const name of ["alt", "title", "aria-label"]) {
+    const value = attributes.get(name)?.trim();
+    if (value) return value;
+  }
+  return "";
+}
+
+function escapeAttribute(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/"/g, "&quot;").replace(/</g, "&lt;");
+}
+
+/** Fills empty `alt` attributes from `title` or `aria-label` so fallbacks and screen readers get a description. */
+export function applyImageAltFallback(html: string): string {
+  return html.replace(IMG_TAG_PATTERN, (tag, attributeSource: string, selfClosing: string) => {
+    const attributes = readAttributes(attributeSource);
+    if (attributes.get("alt")?.trim()) return tag;
+
+    const alt = resolveImageAlt(attributes);
+    if (!alt) return tag;
+
+    const withoutAlt = attributeSource.replace(/\salt\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)/i, "").replace(/\salt(?=[\s/]|$)/i, "");
+    return `<img alt="${escapeAttribute(alt)}"${withoutAlt}${selfClosing}>`;
+  });
+}
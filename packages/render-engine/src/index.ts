@@ -3,11 +3,77 @@ export interface Viewport {
   height: number;
 }
 
+import type { SpacingLevel } from "@core-platform";
+import type { CitationStyle } from "@epub-parser/citation";
+import { DEFAULT_CHARS_PER_PAGE } from "./bookPages";
+import type { LinkFocusWrap } from "./links";
+import { domNodes, type MarkupNode, scanMarkup } from "./markup";
+import type { NavOrder } from "./navigation";
+import type { PageLayout } from "./spread";
+import { DEFAULT_THEME_ID } from "./themes";
+
+export type TextAlign = "start" | "justify";
+
 export interface ReadingPrefs {
   fontSize: number;
   lineHeight: number;
   fontFamily: string;
   pageCharLimit: number;
+  textAlign: TextAlign;
+  hyphenate: boolean;
+  themeId: string;
+  respectSourceColors: boolean;
+  /** Tint bold and italic text with the theme's emphasis colour. */
+  emphasisColors: boolean;
+  /** Draw the theme's paper texture behind the text, when it has one. */
+  paperTexture: boolean;
+  /** Title chapters by their first heading instead of the TOC label. */
+  preferHeadingTitle: boolean;
+  /** Fade/slide the content when moving between chapters. */
+  animateTransitions: boolean;
+  /** Turn off every animation and transition, whatever the other settings ask for. */
+  reduceMotion: boolean;
+  /** Two pages side by side when the window is wide enough; otherwise one. */
+  pageLayout: PageLayout;
+  /** Minimum word count per chapter; shorter consecutive chapters are merged. 0 disables. */
+  mergeShortChapters: number;
+  /** Prefix headings with book-wide hierarchical numbers (1, 1.1, …). */
+  numberHeadings: boolean;
+  /** Hide short paragraphs that open or close most chapters (book title, page numbers). */
+  stripRunningHeads: boolean;
+  /** Mouse back/forward side buttons turn pages while over the content. */
+  mouseSideButtons: boolean;
+  /** Wheeling on past the bottom (or top) of the page turns to the next (or previous) one. */
+  scrollEdgeNavigation: boolean;
+  /** First-line paragraph indent in em; 0 disables it. */
+  firstLineIndent: number;
+  /** Keep the vertical gap between paragraphs; turning it off gives classic book setting. */
+  paragraphGap: boolean;
+  /** Clicking an image opens it full-window. */
+  imageLightbox: boolean;
+  /** On dark themes, invert diagrams and line art (never photos) so white backgrounds do not glare. */
+  invertDiagramsInDark: boolean;
+  /** How text split by inline markup is spaced in plain-text mode (see `SpacingLevel`). */
+  spacing: SpacingLevel;
+  /** Select the passage last selected in a chapter again when returning to it. */
+  rememberSelection: boolean;
+  /** Whether Next/Previous follow the spine or the table of contents. */
+  navOrder: NavOrder;
+  /** Leave chapters without text or images out of Next/Previous, the TOC and the chapter count. */
+  hideEmptyChapters: boolean;
+  /** Show the file each TOC entry points to under its label, for checking a book's structure. */
+  showTocHrefs: boolean;
+  /** Whether Tab past the last link of a page moves on to the next page or chapter with links. */
+  linkFocusWrap: LinkFocusWrap;
+  /** How many prepared chapters stay in memory; older ones are rebuilt when revisited. */
+  chapterCacheSize: number;
+  /** Style of the source line appended to copied quotes. */
+  citationStyle: CitationStyle;
+  /** Characters per page for the estimated book-wide page numbers. */
+  charsPerPage: number;
+  /** The reader's measured pace (see `recordReadingSample`); 0 until a chapter has been timed. */
+  readingPaceWpm: number;
+  readingPaceSamples: number;
 }
 
 export interface PaginationOptions {
@@ -34,63 +100,121 @@ export interface PaginationSession {
   readonly totalPages: number;
   page(index: number): PageView | undefined;
   locate(percent: number): PageView | undefined;
+  /** Page holding the element with this `id` (or legacy `name`) attribute. */
+  findAnchor(anchor: string): PageView | undefined;
   all(): PageView[];
 }
 
-const DEFAULT_PREFS: ReadingPrefs = {
+export const DEFAULT_PREFS: ReadingPrefs = {
   fontSize: 18,
   lineHeight: 1.6,
   fontFamily: "system-ui",
   pageCharLimit: 1600,
+  textAlign: "start",
+  hyphenate: false,
+  themeId: DEFAULT_THEME_ID,
+  respectSourceColors: false,
+  emphasisColors: false,
+  paperTexture: false,
+  preferHeadingTitle: false,
+  animateTransitions: false,
+  reduceMotion: false,
+  pageLayout: "single",
+  mergeShortChapters: 0,
+  numberHeadings: false,
+  stripRunningHeads: false,
+  mouseSideButtons: true,
+  scrollEdgeNavigation: false,
+  firstLineIndent: 0,
+  paragraphGap: true,
+  imageLightbox: true,
+  invertDiagramsInDark: false,
+  spacing: "smart",
+  rememberSelection: false,
+  navOrder: "spine",
+  hideEmptyChapters: false,
+  showTocHrefs: false,
+  linkFocusWrap: "adjacent",
+  chapterCacheSize: 8,
+  citationStyle: "apa",
+  charsPerPage: DEFAULT_CHARS_PER_PAGE,
+  readingPaceWpm: 0,
+  readingPaceSamples: 0,
 };
 
 export function createPaginationSession(options: PaginationOptions): PaginationSession {
   const prefs = { ...DEFAULT_PREFS, ...options.prefs };
-  const fragments = splitIntoFragments(options.html);
+  const fragments = splitIntoFragments(options.html, prefs.pageCharLimit);
   const pages = paginateFragments(fragments, prefs.pageCharLimit, options.spineIndex);
   return new BasicPaginationSession(pages);
 }
 
-function splitIntoFragments(html: string): TextFragment[] {
-  const cleaned = html.replace(/\r\n/g, "\n");
+// Wrappers that many books put around an entire chapter. When one of them is larger
+// than a page we page through its children instead, so a long chapter never lands
+// on a single page and only the visible blocks are laid out.
+const SPLITTABLE_WRAPPERS = new Set(["DIV", "SECTION", "ARTICLE", "MAIN", "BODY"]);
+
+function collectNodeFragments(
+  nodes: MarkupNode[],
+  limit: number,
+  fragments: TextFragment[],
+  carriedIds: string[] = [],
+) {
+  // Ids of wrappers we split open are kept as empty anchors on their first fragment,
+  // so links into the wrapper still resolve to a page.
+  const push = (fragment: TextFragment) => {
+    const anchors = carriedIds.splice(0).map(id => `<a id="${escapeHtml(id)}"></a>`).join("");
+    fragments.push(anchors ? { ...fragment, html: anchors + fragment.html } : fragment);
+  };
+
+  for (const node of nodes) {
+    if (node.tag === null) {
+      const trimmed = node.content.trim();
+      if (trimmed.length > 0) {
+        push({
+          html: `<p>${escapeHtml(trimmed)}</p>`,
+          textLength: trimmed.length,
+        });
+      }
+      continue;
+    }
+
+    const length = stripTags(node.content).length;
+    if (length === 0) continue;
+
+    if (length > limit && SPLITTABLE_WRAPPERS.has(node.tag)) {
+      const children = node.children();
+      if (children.filter(child => child.tag !== null).length > 1) {
+        if (node.id) carriedIds.push(node.id);
+        collectNodeFragments(children, limit, fragments, carriedIds);
+        continue;
+      }
+    }
 
+    push({
+      html: node.content,
+      textLength: length,
+    });
+  }
+}
+
+function parseMarkup(html: string): MarkupNode[] {
   if (typeof DOMParser !== "undefined") {
     const parser = new DOMParser();
-    const doc = parser.parseFromString(`<div>${cleaned}</div>`, "text/html");
+    const doc = parser.parseFromString(`<div>${html}</div>`, "text/html");
     const container = doc.body.firstElementChild;
+    if (container) return domNodes(container.childNodes);
+  }
+  return scanMarkup(html);
+}
 
-    if (container) {
-      const fragments: TextFragment[] = [];
-      container.childNodes.forEach(node => {
-        if (node.nodeType === Node.TEXT_NODE) {
-          const text = node.textContent ?? "";
-          const trimmed = text.trim();
-          if (trimmed.length > 0) {
-            const escaped = escapeHtml(trimmed);
-            fragments.push({
-              html: `<p>${escaped}</p>`,
-              textLength: trimmed.length,
-            });
-          }
-          return;
-        }
-
-        if (node instanceof Element) {
-          const content = node.outerHTML;
-          const length = stripTags(content).length;
-          if (length > 0) {
-            fragments.push({
-              html: content,
-              textLength: length,
-            });
-          }
-        }
-      });
-
-      if (fragments.length > 0) {
-        return fragments;
-      }
-    }
+function splitIntoFragments(html: string, limit: number): TextFragment[] {
+  const cleaned = html.replace(/\r\n/g, "\n");
+
+  const fragments: TextFragment[] = [];
+  collectNodeFragments(parseMarkup(cleaned), limit, fragments);
+  if (fragments.length > 0) {
+    return fragments;
   }
 
   const paragraphRegex =
@@ -163,7 +287,13 @@ function paginateFragments(fragments: TextFragment[], limit: number, spineIndex:
 }
 
 function stripTags(input: string): string {
-  return input.replace(/<[^>]*>/g, " ").replace(/\s+/g, " ").trim();
+  // Ruby annotations (<rt>) and their fallback parentheses (<rp>) sit above the base
+  // text and should not count towards the page budget.
+  return input
+    .replace(/<(rt|rp)\b[^>]*>[\s\S]*?<\/\1>/gi, "")
+    .replace(/<[^>]*>/g, " ")
+    .replace(/\s+/g, " ")
+    .trim();
 }
 
 function escapeHtml(value: string): string {
@@ -196,7 +326,44 @@ class BasicPaginationSession implements PaginationSession {
     return this.pages[index];
   }
 
+  findAnchor(anchor: string): PageView | undefined {
+    if (!anchor) return undefined;
+    const escaped = anchor.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+    const pattern = new RegExp(`\\s(?:id|name)\\s*=\\s*["']${escaped}["']`);
+    return this.pages.find(page => page.fragments.some(fragment => pattern.test(fragment.html)));
+  }
+
   all(): PageView[] {
     return [...this.pages];
   }
 }
+
+export * from "./typography";
+export * from "./themes";
+export * from "./headings";
+export * from "./chapterCache";
+export * from "./images";
+export * from "./figures";
+export * from "./plainText";
+export * from "./search";
+export * from "./mergeChapters";
+export * from "./semanticHtml";
+export * from "./runningHeads";
+export * from "./cover";
+export * from "./markers";
+export * from "./debugOutlines";
+export * from "./lightbox";
+export * from "./navigation";
+export * from "./bookPages";
+export * from "./accessibility";
+export * from "./epigraphs";
+export * from "./tocLabels";
+export * from "./links";
+export * from "./readingPace";
+export * from "./printPages";
+export * from "./imageInversion";
+export * from "./edgeScroll";
+export * from "./bookIndex";
+export * from "./selection";
+export * from "./motion";
+export * from "./spread";
@@ -0,0 +1,46 @@
+export interface LightboxImage {
+  /** Raster URL, or a `data:image/svg+xml` URL for inline SVG. */
+  src: string;
+  alt: string;
+}
+
+export type LightboxState = { open: false } | { open: true; image: LightboxImage; zoom: number };
+
+export type LightboxAction =
+  | { type: "imageClick"; image: LightboxImage }
+  | { type: "close" }
+  | {
+      type: "zoom";
+      /** Multiplies the current zoom, e.g. from Ctrl+wheel or a pinch. */
+      factor: number;
+    };
+
+export const LIGHTBOX_CLOSED: LightboxState = { open: false };
+export const LIGHTBOX_MIN_ZOOM = 1;
+export const LIGHTBOX_MAX_ZOOM = 8;
+
+/** State of the full-window image view: an image click opens it at fit-to-window size, Escape or a click closes it. */
+export function lightboxReducer(state: LightboxState, action: LightboxAction): LightboxState {
+  switch (action.type) {
+    case "imageClick":
+      if (!action.image.src) return state;
+      return { open: true, image: action.image, zoom: LIGHTBOX_MIN_ZOOM };
+    case "close":
+      return state.open ? LIGHTBOX_CLOSED : state;
+    case "zoom": {
+      if (!state.open || !Number.isFinite(action.factor) || action.factor <= 0) return state;
+      const zoom = Math.min(Math.max(state.zoom * action.factor, LIGHTBOX_MIN_ZOOM), LIGHTBOX_MAX_ZOOM);
+      return zoom === state.zoom ? state : { ...state, zoom };
+    }
+  }
+}
+
+/** Zoom factor for one wheel step; trackpad pinches arrive as Ctrl+wheel with small deltas. */
+export function wheelZoomFactor(deltaY: number): number {
+  return Math.exp(-deltaY * 0.002);
+}
+
+/** Serialized inline `<svg>` markup as an image URL, so it can be shown like any other picture. */
+export function svgToDataUrl(markup: string): string {
+  return `data:image/svg+xml;charset=utf-8,${encodeURIComponent(markup)}`;
+}
@@ -0,0 +1,31 @@
+/** What Tab does past the last link of a page (or Shift+Tab before the first). */
+export type LinkFocusWrap = "adjacent" | "stop";
+
+const LINK_PATTERN = /<a\b[^>]*?\shref\s*=/i;
+
+/**
+ * The link Tab (1) or Shift+Tab (-1) focuses among `count` links, starting from
+ * the focused one (`current`, -1 when no link has focus). Null past either end,
+ * where the reader decides whether to move on to the adjacent page.
+ */
+export function stepLinkFocus(current: number, count: number, direction: 1 | -1): number | null {
+  const next = current < 0 || current >= count ? (direction === 1 ? 0 : count - 1) : current + direction;
+  return next >= 0 && next < count ? next : null;
+}
+
+/**
+ * Index of the nearest page past `start` in `direction` that holds a link, or -1.
+ * Pass `start` of -1 or `pages.length` to search from either end.
+ */
+export function findPageWithLink(
+  pages: Array<{ fragments: Array<{ html: string }> }>,
+  start: number,
+  direction: 1 | -1,
+): number {
+  for (let index = start + direction; index >= 0 && index < pages.length; index += direction) {
+    if (pages[index]!.fragments.some(fragment => LINK_PATTERN.test(fragment.html))) {
+      return index;
+    }
+  }
+  return -1;
+}
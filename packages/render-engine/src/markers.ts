@@ -0,0 +1,37 @@
+export interface MarkerCluster<T> {
+  /** Position along the chapter, 0 (start) to 1 (end). */
+  fraction: number;
+  items: T[];
+}
+
+/**
+ * Groups markers that would overlap on a rail: consecutive markers closer than
+ * `minGap` (as a fraction of the rail) share one cluster placed at the first.
+ */
+export function clusterMarkers<T extends { fraction: number }>(markers: T[], minGap = 0.02): MarkerCluster<T>[] {
+  const sorted = [...markers].sort((a, b) => a.fraction - b.fraction);
+  const clusters: MarkerCluster<T>[] = [];
+  for (const marker of sorted) {
+    const fraction = Math.min(Math.max(marker.fraction, 0), 1);
+    const last = clusters[clusters.length - 1];
+    if (last && fraction - last.fraction < minGap) {
+      last.items.push(marker);
+    } else {
+      clusters.push({ fraction, items: [marker] });
+    }
+  }
+  return clusters;
+}
+
+/** Page index holding the text at `fraction` of the chapter, weighting pages by their text length. */
+export function pageAtFraction(pages: Array<{ textLength: number }>, fraction: number): number {
+  const total = pages.reduce((sum, page) => sum + page.textLength, 0);
+  if (pages.length === 0 || total === 0) return 0;
+  const target = Math.min(Math.max(fraction, 0), 1) * total;
+  let consumed = 0;
+  for (const [index, page] of pages.entries()) {
+    consumed += page.textLength;
+    if (target < consumed) return index;
+  }
+  return pages.length - 1;
+}
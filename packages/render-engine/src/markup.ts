@@ -0,0 +1,108 @@
+import { decodeEntities } from "./headings";
+
+/** A node of chapter markup, read from the DOM when there is one and scanned from the text otherwise. */
+export interface MarkupNode {
+  /** Upper-case tag name, or null for text. */
+  tag: string | null;
+  /** Outer HTML of an element; the decoded text of a text node. */
+  content: string;
+  id: string;
+  children(): MarkupNode[];
+}
+
+const VOID_ELEMENTS = new Set([
+  "AREA",
+  "BASE",
+  "BR",
+  "COL",
+  "EMBED",
+  "HR",
+  "IMG",
+  "INPUT",
+  "LINK",
+  "META",
+  "SOURCE",
+  "TRACK",
+  "WBR",
+]);
+
+const TAG_PATTERN = /<!--[\s\S]*?-->|<(\/?)([a-zA-Z][\w:-]*)((?:"[^"]*"|'[^']*'|[^'">])*)>/g;
+
+export function domNodes(nodes: NodeListOf<ChildNode>): MarkupNode[] {
+  const result: MarkupNode[] = [];
+  nodes.forEach(node => {
+    if (node.nodeType === Node.TEXT_NODE) {
+      result.push(textNode(node.textContent ?? ""));
+    } else if (node instanceof Element) {
+      result.push({
+        tag: node.tagName.toUpperCase(),
+        content: node.outerHTML,
+        id: node.id,
+        children: () => domNodes(node.childNodes),
+      });
+    }
+  });
+  return result;
+}
+
+/**
+ * The top-level nodes of `html` without a DOM, for workers and tests. Expects the
+ * well-formed markup of EPUB content documents; stray closing tags are ignored and
+ * an element left open runs to the end.
+ */
+export function scanMarkup(html: string): MarkupNode[] {
+  const result: MarkupNode[] = [];
+  let depth = 0;
+  let textStart = 0;
+  let open = { tag: "", attributes: "", start: 0, innerStart: 0 };
+
+  const pushText = (end: number) => {
+    const text = html.slice(textStart, end);
+    if (text) result.push(textNode(decodeEntities(text)));
+  };
+  const pushElement = (innerEnd: number, end: number) => {
+    const inner = html.slice(open.innerStart, innerEnd);
+    result.push({
+      tag: open.tag,
+      content: html.slice(open.start, end),
+      id: attributeId(open.attributes),
+      children: () => scanMarkup(inner),
+    });
+    textStart = end;
+  };
+
+  for (const match of html.matchAll(TAG_PATTERN)) {
+    const [raw, closing, name, attributes = ""] = match;
+    const start = match.index!;
+    const end = start + raw.length;
+    const tag = name?.toUpperCase();
+    const selfClosing = !closing && (VOID_ELEMENTS.has(tag ?? "") || attributes.trimEnd().endsWith("/"));
+
+    if (depth > 0) {
+      if (!tag || selfClosing) continue;
+      depth += closing ? -1 : 1;
+      if (depth === 0) pushElement(start, end);
+      continue;
+    }
+
+    pushText(start);
+    textStart = end;
+    if (!tag || closing) continue;
+    open = { tag, attributes, start, innerStart: end };
+    if (selfClosing) pushElement(end, end);
+    else depth = 1;
+  }
+
+  if (depth > 0) pushElement(html.length, html.length);
+  else pushText(html.length);
+  return result;
+}
+
+function textNode(text: string): MarkupNode {
+  return { tag: null, content: text, id: "", children: () => [] };
+}
+
+function attributeId(attributes: string): string {
+  const match = /(?:^|\s)id\s*=\s*(?:"([^"]*)"|'([^']*)')/i.exec(attributes);
+  return match ? decodeEntities(match[1] ?? match[2] ?? "") : "";
+}
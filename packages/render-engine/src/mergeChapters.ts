@@ -0,0 +1,72 @@
+import { htmlToPlainText } from "./plainText";
+
+export interface MergeableChapter {
+  href: string;
+  content: string;
+  /** Front, body or back matter; a run is never folded across a change of matter. */
+  matter?: string;
+  /** False for chapters outside the reading order; those are never folded into a run. */
+  linear?: boolean;
+}
+
+export type MergedChapter<T extends MergeableChapter> = T & {
+  /** Hrefs of every spine document folded into this chapter, first one included. */
+  mergedHrefs: string[];
+};
+
+const CJK_CHARACTER = /[\u3040-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uf900-\ufaff\uac00-\ud7af]/g;
+const BODY_PATTERN = /<body\b[^>]*>([\s\S]*?)<\/body>/i;
+
+/** Counts words, treating every CJK character as a word of its own. */
+export function countWords(html: string): number {
+  const text = htmlToPlainText(html);
+  const cjk = text.match(CJK_CHARACTER)?.length ?? 0;
+  const words = text.replace(CJK_CHARACTER, " ").split(/\s+/).filter(Boolean).length;
+  return cjk + words;
+}
+
+function bodyOf(content: string): string {
+  return BODY_PATTERN.exec(content)?.[1] ?? content;
+}
+
+function withBody(content: string, body: string): string {
+  const match = BODY_PATTERN.exec(content);
+  if (!match) return body;
+  const openTag = /<body\b[^>]*>/i.exec(match[0])![0];
+  return `${content.slice(0, match.index)}${openTag}${body}</body>${content.slice(match.index + match[0].length)}`;
+}
+
+/**
+ * Folds runs of consecutive chapters shorter than `minWords` into a single
+ * chapter. The run keeps the first chapter's fields (and so its title); the
+ * bodies are concatenated in order. Chapters at or above the threshold are left alone,
+ * and a run ends where the matter changes, so a dedication never absorbs a part title page.
+ * Non-linear chapters stay on their own so links can still open them alone.
+ */
+export function mergeShortChapters<T extends MergeableChapter>(chapters: T[], minWords: number): MergedChapter<T>[] {
+  const result: MergedChapter<T>[] = [];
+  let run: T[] = [];
+
+  const flush = () => {
+    const [first] = run;
+    if (!first) return;
+    result.push({
+      ...first,
+      content: run.length > 1 ? withBody(first.content, run.map(chapter => bodyOf(chapter.content)).join("\n")) : first.content,
+      mergedHrefs: run.map(chapter => chapter.href),
+    });
+    run = [];
+  };
+
+  for (const chapter of chapters) {
+    if (minWords > 0 && chapter.linear !== false && countWords(chapter.content) < minWords) {
+      if (run.length > 0 && run[0]!.matter !== chapter.matter) flush();
+      run.push(chapter);
+      continue;
+    }
+    flush();
+    result.push({ ...chapter, mergedHrefs: [chapter.href] });
+  }
+  flush();
+  return result;
+}
@@ -0,0 +1,21 @@
+import type { ReadingPrefs } from "./index";
+
+export interface MotionContext {
+  /** The active theme is meant for e-paper, where animation only leaves ghosting behind. */
+  eInk?: boolean;
+  /** The system asks for reduced motion (`prefers-reduced-motion: reduce`). */
+  systemReducedMotion?: boolean;
+}
+
+/** Whether anything may animate; features that move on screen check this before they start. */
+export function motionAllowed(prefs: Pick<ReadingPrefs, "reduceMotion">, context: MotionContext = {}): boolean {
+  return !prefs.reduceMotion && !context.eInk && !context.systemReducedMotion;
+}
+
+/** Whether moving between chapters plays the fade/slide transition. */
+export function chapterTransitionsEnabled(
+  prefs: Pick<ReadingPrefs, "animateTransitions" | "reduceMotion">,
+  context: MotionContext = {},
+): boolean {
+  return prefs.animateTransitions && motionAllowed(prefs, context);
+}
@@ -0,0 +1,129 @@
+/** Which sequence Next/Previous walk: the spine (reading order) or the table of contents. */
+export type NavOrder = "spine" | "toc";
+
+/**
+ * Chapter indices in the order the TOC lists them. Entries that don't resolve
+ * to a chapter (`resolve` returns a negative index) are skipped, and a chapter
+ * reached by several entries, e.g. sections within it, keeps its first place.
+ */
+export function tocChapterOrder(tocHrefs: string[], resolve: (href: string) => number): number[] {
+  const order: number[] = [];
+  const seen = new Set<number>();
+  for (const href of tocHrefs) {
+    const index = resolve(href);
+    if (index < 0 || seen.has(index)) continue;
+    seen.add(index);
+    order.push(index);
+  }
+  return order;
+}
+
+/**
+ * The chapter Next (1) or Previous (-1) moves to, or null at the end of the
+ * order. In TOC order a chapter the TOC doesn't list, and a book without a
+ * usable TOC, fall back to stepping through the spine. Chapters in `hidden`
+ * are stepped over.
+ */
+export function adjacentChapter(
+  current: number,
+  direction: 1 | -1,
+  chapterCount: number,
+  order: NavOrder,
+  tocOrder: number[] = [],
+  hidden: ReadonlySet<number> = new Set(),
+): number | null {
+  for (let from = current; ; ) {
+    const position = order === "toc" ? tocOrder.indexOf(from) : -1;
+    const next = position >= 0 ? tocOrder[position + direction] : from + direction;
+    if (next === undefined || next < 0 || next >= chapterCount) return null;
+    if (!hidden.has(next)) return next;
+    from = next;
+  }
+}
+
+/** Indices of the chapters outside the reading order (`linear="no"`), which Next/Previous step over. */
+export function nonLinearChapters(chapters: { linear?: boolean }[]): Set<number> {
+  return new Set(chapters.flatMap((chapter, index) => (chapter.linear === false ? [index] : [])));
+}
+
+/**
+ * Indices of the chapters around `center` to preview, `radius` on each side.
+ * Near either end of the book the window slides inward instead of shrinking, so
+ * it always shows `2 * radius + 1` chapters when the book has that many.
+ */
+export function chapterWindow(center: number, chapterCount: number, radius = 5): number[] {
+  if (chapterCount <= 0) return [];
+  const size = Math.min(chapterCount, radius * 2 + 1);
+  const clamped = Math.min(Math.max(center, 0), chapterCount - 1);
+  const start = Math.min(Math.max(clamped - radius, 0), chapterCount - size);
+  return Array.from({ length: size }, (_, offset) => start + offset);
+}
+
+export interface LinkableChapter {
+  href: string;
+  /** Every document a merged chapter was built from. */
+  mergedHrefs?: string[];
+}
+
+/** Index of the chapter holding the document `href` points into, ignoring any fragment; -1 if none. */
+export function findChapterIndex(chapters: LinkableChapter[], href: string): number {
+  const target = href.split("#")[0];
+  // Placeholders for spine items without a manifest entry have no href to match.
+  if (!target) return -1;
+  return chapters.findIndex(chapter =>
+    (chapter.mergedHrefs ?? [chapter.href]).some(chapterHref => chapterHref.split("#")[0] === target),
+  );
+}
+
+/**
+ * The book path a link in the document at `from` points to, fragment included,
+ * e.g. `notes.xhtml#n2` from `text/ch1.xhtml` is `text/notes.xhtml#n2`. Null
+ * for same-document fragments and links out of the book.
+ */
+export function resolveBookHref(from: string, href: string): string | null {
+  if (!href || href.startsWith("#") || /^[a-z][a-z\d+.-]*:/i.test(href)) return null;
+  const hash = href.indexOf("#");
+  const path = hash === -1 ? href : href.slice(0, hash);
+  const fragment = hash === -1 ? "" : href.slice(hash);
+  const base = path.startsWith("/") ? [] : from.split("/").slice(0, -1);
+  const segments: string[] = [];
+  for (const segment of [...base, ...path.split("/")]) {
+    if (segment === "..") segments.pop();
+    else if (segment && segment !== ".") segments.push(segment);
+  }
+  return `${segments.join("/")}${fragment}`;
+}
+
+export interface HrefTarget {
+  chapter: number;
+  page: number;
+  /** Fragment to scroll to once the chapter has rendered. */
+  anchor?: string;
+}
+
+/** Percent-decodes a link fragment or path; one with a stray "%" is used as written. */
+export function decodeFragment(raw: string): string {
+  try {
+    return decodeURIComponent(raw);
+  } catch {
+    return raw;
+  }
+}
+
+/**
+ * Where following `href` lands. Books that keep several TOC entries in one
+ * document differ only by fragment, so the fragment picks the page (via
+ * `findAnchorPage`) and is kept as a pending scroll for the exact offset.
+ */
+export function resolveHrefTarget(
+  chapters: LinkableChapter[],
+  href: string,
+  findAnchorPage: (chapter: number, anchor: string) => number | undefined = () => undefined,
+): HrefTarget | null {
+  const chapter = findChapterIndex(chapters, href);
+  if (chapter < 0) return null;
+  const hash = href.indexOf("#");
+  const anchor = decodeFragment(hash === -1 ? "" : href.slice(hash + 1));
+  if (!anchor) return { chapter, page: 0 };
+  return { chapter, page: findAnchorPage(chapter, anchor) ?? 0, anchor };
+}
@@ -0,0 +1,256 @@
+import { spaceBetween, spacingRulesFor, type SpacingLevel } from "@core-platform";
+import { decodeEntities } from "./headings";
+import { readAttributes } from "./images";
+
+const NON_CONTENT_PATTERN = /<(head|script|style|rt|rp)\b[^>]*>[\s\S]*?<\/\1>|<!--[\s\S]*?-->|<[!?][^>]*>/gi;
+const TOKEN_PATTERN = /<(\/?)([a-zA-Z][\w:-]*)\b([^>]*)>|([^<]+)/g;
+const BLOCK_TAGS = new Set([
+  "p", "div", "h1", "h2", "h3", "h4", "h5", "h6", "li", "ul", "ol", "dl", "dt", "dd", "blockquote", "pre",
+  "section", "article", "aside", "header", "footer", "table", "tr", "figure", "figcaption", "hr",
+]);
+const VOID_TAGS = new Set(["area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source"]);
+const PREFORMATTED_TAGS = new Set(["pre", "listing", "xmp"]);
+const VERSE_TOKENS = new Set(["verse", "poem", "poetry", "z3998:verse", "z3998:poem", "z3998:song", "z3998:lyrics"]);
+// Bullets by nesting depth, as browsers draw disc, circle and square.
+const BULLETS = ["\u2022", "\u25e6", "\u25aa"];
+const LIST_INDENT_EM = 1.5;
+
+/**
+ * How a block treats the spaces in its source text:
+ * - `collapse`: prose; runs of spaces become one and lines are trimmed.
+ * - `preformatted`: code; every space, tab and line break is kept.
+ * - `verse`: poems; runs of spaces and non-breaking indents are kept, but the
+ *   indentation of the markup source is not.
+ */
+export type WhitespacePolicy = "collapse" | "preformatted" | "verse";
+
+export interface PlainTextBlock {
+  text: string;
+  whitespace: WhitespacePolicy;
+  /** Set for text inside a list; only the first block of an item carries its marker. */
+  list?: PlainTextListItem;
+  /** `<abbr title>` elements in the block, in order; each occurrence of `text` is shown with its expansion. */
+  abbreviations?: PlainTextAbbreviation[];
+}
+
+export interface PlainTextAbbreviation {
+  text: string;
+  title: string;
+}
+
+export interface PlainTextListItem {
+  /** 1 for a top-level list, 2 for a list nested in one of its items, and so on. */
+  depth: number;
+  /** "•", "◦", "▪" by depth for `<ul>`, "1." style for `<ol>`; empty after the item's first block. */
+  marker: string;
+}
+
+export interface PlainTextOptions {
+  /** How text split by inline markup is joined; defaults to `smart`. */
+  spacing?: SpacingLevel;
+  /** Language of the chapter, for French spacing; `lang` attributes inside it take precedence. */
+  language?: string;
+}
+
+/** The policy an element sets for its contents, or null when it inherits its parent's. */
+export function whitespacePolicyFor(tag: string, attributes: Map<string, string>): WhitespacePolicy | null {
+  if (PREFORMATTED_TAGS.has(tag) || /white-space\s*:\s*pre\b/i.test(attributes.get("style") ?? "")) {
+    return "preformatted";
+  }
+  const tokens = `${attributes.get("epub:type") ?? ""} ${attributes.get("class") ?? ""}`.toLowerCase().split(/\s+/);
+  return tokens.some(token => VERSE_TOKENS.has(token)) ? "verse" : null;
+}
+
+function normalizeBlock(text: string, whitespace: WhitespacePolicy): string {
+  switch (whitespace) {
+    case "preformatted":
+      // Like the HTML parser, drop the newline that directly follows `<pre>`.
+      return text.replace(/^\r?\n/, "").replace(/\s+$/, "");
+    case "verse":
+      return text
+        .split("\n")
+        .map(line => line.replace(/^[ \t\r\f\v]+|[ \t\r\f\v]+$/g, ""))
+        .join("\n")
+        .replace(/\n{3,}/g, "\n\n")
+        .replace(/^\n+|\n+$/g, "");
+    default:
+      return text
+        .split("\n")
+        .map(line => line.replace(/[ \t\f\v\u00a0]+/g, " ").trim())
+        .join("\n")
+        .replace(/\n{3,}/g, "\n\n")
+        .trim();
+  }
+}
+
+/**
+ * Splits chapter markup into text blocks at block-level elements, each
+ * normalized by the whitespace policy in effect where it starts.
+ */
+export function htmlToPlainTextBlocks(html: string, options: PlainTextOptions = {}): PlainTextBlock[] {
+  const blocks: PlainTextBlock[] = [];
+  const open: { tag: string; whitespace: WhitespacePolicy; language?: string }[] = [];
+  const lists: { ordered: boolean; next: number }[] = [];
+  const abbrs: { start: number; title: string }[] = [];
+  let pending = "";
+  let pendingPolicy: WhitespacePolicy = "collapse";
+  let pendingMarker = "";
+  let pendingAbbreviations: PlainTextAbbreviation[] = [];
+
+  const current = (): WhitespacePolicy => open[open.length - 1]?.whitespace ?? "collapse";
+  const language = (): string | undefined => open[open.length - 1]?.language ?? options.language;
+  const flush = () => {
+    const text = normalizeBlock(pending, pendingPolicy);
+    const abbreviations = pendingAbbreviations;
+    pending = "";
+    pendingAbbreviations = [];
+    abbrs.length = 0;
+    if (!text) return;
+    const block: PlainTextBlock = { text, whitespace: pendingPolicy };
+    if (abbreviations.length > 0) block.abbreviations = abbreviations;
+    if (lists.length > 0) {
+      block.list = { depth: lists.length, marker: pendingMarker };
+      pendingMarker = "";
+    }
+    blocks.push(block);
+  };
+  const append = (text: string) => {
+    if (!pending) pendingPolicy = current();
+    pending += text;
+  };
+  // In prose the spaces where tags split the text are chosen by the spacing rules.
+  const appendRun = (text: string) => {
+    const before = pending.replace(/[ \t\f\v]+$/, "");
+    const after = text.replace(/^[ \t\f\v]+/, "");
+    if (current() !== "collapse" || !/\S$/.test(before) || !/^\S/.test(after)) {
+      append(text);
+      return;
+    }
+    const rules = spacingRulesFor(options.spacing ?? "smart", language());
+    pending = before + spaceBetween(before, after, before !== pending || after !== text, rules);
+    append(after);
+  };
+
+  for (const match of html.replace(NON_CONTENT_PATTERN, "").matchAll(TOKEN_PATTERN)) {
+    const [, slash, rawName, attributeSource, text] = match;
+    if (text !== undefined) {
+      const value = decodeEntities(text);
+      // Verse lines end at `<br>` or a block, never at a newline in the source.
+      appendRun(current() === "verse" ? value.replace(/[ \t\r\f\v]*\n[ \t\r\f\v]*/g, " ") : value);
+      continue;
+    }
+
+    const tag = rawName!.toLowerCase();
+    if (tag === "br") {
+      append("\n");
+      continue;
+    }
+    if (BLOCK_TAGS.has(tag)) flush();
+    if (VOID_TAGS.has(tag) || /\/\s*$/.test(attributeSource ?? "")) continue;
+
+    if (tag === "ul" || tag === "ol") {
+      if (slash) {
+        lists.pop();
+      } else {
+        const start = Number.parseInt(readAttributes(attributeSource ?? "").get("start") ?? "", 10);
+        lists.push({ ordered: tag === "ol", next: Number.isFinite(start) ? start : 1 });
+      }
+    } else if (tag === "li") {
+      const list = lists[lists.length - 1];
+      if (slash || !list) pendingMarker = "";
+      else pendingMarker = list.ordered ? `${list.next++}.` : BULLETS[(lists.length - 1) % BULLETS.length]!;
+    } else if (tag === "abbr") {
+      if (!slash) {
+        abbrs.push({ start: pending.length, title: readAttributes(attributeSource ?? "").get("title")?.trim() ?? "" });
+      } else {
+        const abbr = abbrs.pop();
+        const text = abbr ? pending.slice(abbr.start).replace(/\s+/g, " ").trim() : "";
+        if (abbr?.title && text) pendingAbbreviations.push({ text, title: abbr.title });
+      }
+    }
+
+    if (slash) {
+      const index = open.map(entry => entry.tag).lastIndexOf(tag);
+      if (index !== -1) open.length = index;
+    } else {
+      const attributes = readAttributes(attributeSource ?? "");
+      const whitespace = whitespacePolicyFor(tag, attributes) ?? current();
+      open.push({ tag, whitespace, language: attributes.get("xml:lang") ?? attributes.get("lang") ?? language() });
+    }
+  }
+  flush();
+  return blocks;
+}
+
+const MEDIA_PATTERN = /<(?:img|svg|image|video|audio|object|embed|iframe|math)\b/i;
+
+/** True for separator documents with neither text nor media, such as a page holding only `<p>&nbsp;</p>`. */
+export function isBlankChapter(html: string): boolean {
+  if (MEDIA_PATTERN.test(html.replace(NON_CONTENT_PATTERN, ""))) return false;
+  return htmlToPlainText(html).replace(/[\s\u200b-\u200d\ufeff]+/g, "") === "";
+}
+
+/** Reduces chapter markup to text, keeping a blank line between block-level elements. */
+export function htmlToPlainText(html: string, options?: PlainTextOptions): string {
+  return htmlToPlainTextBlocks(html, options)
+    .map(block => block.text)
+    .join("\n\n");
+}
+
+function escapeText(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+function escapeAttribute(value: string): string {
+  return escapeText(value).replace(/"/g, "&quot;");
+}
+
+function markAbbreviations(escaped: string, abbreviations: PlainTextAbbreviation[] = []): string {
+  // The first expansion given for a text wins; longer texts are matched before their prefixes, and only as whole words.
+  const titles = new Map<string, string>();
+  for (const { text, title } of abbreviations) {
+    if (!titles.has(escapeText(text))) titles.set(escapeText(text), title);
+  }
+  if (titles.size === 0) return escaped;
+  const alternatives = [...titles.keys()]
+    .sort((a, b) => b.length - a.length)
+    .map(text => text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&"));
+  return escaped.replace(
+    new RegExp(`(?<!\\w)(?:${alternatives.join("|")})(?!\\w)`, "g"),
+    text => `<abbr title="${escapeAttribute(titles.get(text)!)}">${text}</abbr>`,
+  );
+}
+
+function styleAttribute(declarations: string[]): string {
+  return declarations.length > 0 ? ` style="${declarations.join("; ")}"` : "";
+}
+
+/**
+ * Escape hatch for books whose markup cannot be rendered sensibly: the chapter
+ * becomes a sequence of plain paragraphs split on blank lines. Code blocks
+ * stay whole inside `<pre>` so their indentation survives, list items
+ * keep their marker and are indented by nesting depth, and abbreviations
+ * keep their `title` so the expansion still shows on hover.
+ */
+export function renderPlainTextParagraphs(html: string, options?: PlainTextOptions): string {
+  return htmlToPlainTextBlocks(html, options)
+    .flatMap(block => {
+      const indent = block.list ? [`padding-left: ${block.list.depth * LIST_INDENT_EM}em`] : [];
+      if (block.whitespace === "preformatted") {
+        const code = markAbbreviations(escapeText(block.text), block.abbreviations);
+        return [`<pre${styleAttribute(indent)}>${code}</pre>`];
+      }
+      // Spaces the policy kept would collapse again in a normal paragraph.
+      const style = styleAttribute(block.whitespace === "verse" ? ["white-space: pre-wrap", ...indent] : indent);
+      return block.text
+        .split(/\n\s*\n/)
+        .map(paragraph => paragraph.replace(/^\n+|\n+$/g, ""))
+        .filter(paragraph => paragraph.length > 0)
+        .map((paragraph, index) => {
+          const marker = index === 0 && block.list?.marker ? `${block.list.marker} ` : "";
+          const text = markAbbreviations(escapeText(marker + paragraph), block.abbreviations);
+          return `<p${style}>${text.replace(/\n/g, "<br/>")}</p>`;
+        });
+    })
+    .join("\n");
+}
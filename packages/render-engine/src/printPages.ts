@@ -0,0 +1,37 @@
+export interface PrintPageTarget {
+  label: string;
+  href: string;
+}
+
+export interface PrintPagePosition {
+  chapter: number;
+  page: number;
+}
+
+/** The page-list entry for a print page number as typed, e.g. "12" or "XIV". */
+export function findPrintPage<T extends PrintPageTarget>(pageMap: T[], label: string): T | undefined {
+  const wanted = label.trim().toLowerCase();
+  return wanted ? pageMap.find(target => target.label.trim().toLowerCase() === wanted) : undefined;
+}
+
+/**
+ * The print page being read: the last page-list entry that starts at or
+ * before `position`. `locate` places an entry in the book, or returns null
+ * for entries pointing outside it.
+ */
+export function printPageAt(
+  pageMap: PrintPageTarget[],
+  position: PrintPagePosition,
+  locate: (href: string) => PrintPagePosition | null,
+): string | null {
+  let current: string | null = null;
+  for (const target of pageMap) {
+    const place = locate(target.href);
+    if (!place) continue;
+    if (place.chapter > position.chapter || (place.chapter === position.chapter && place.page > position.page)) {
+      continue;
+    }
+    current = target.label;
+  }
+  return current;
+}
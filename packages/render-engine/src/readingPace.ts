@@ -0,0 +1,42 @@
+/** Words per minute (see `countWords`) assumed until the reader's own pace is known. */
+export const DEFAULT_WORDS_PER_MINUTE = 300;
+/** Chapters that must be timed before estimates switch to the reader's pace. */
+export const MIN_PACE_SAMPLES = 3;
+const PACE_WINDOW = 10;
+// Slower than this means the chapter was left open; faster means it was skimmed or skipped.
+const MIN_PLAUSIBLE_WPM = 40;
+const MAX_PLAUSIBLE_WPM = 1500;
+const MIN_SAMPLE_WORDS = 100;
+
+export interface ReadingPace {
+  wordsPerMinute: number;
+  samples: number;
+}
+
+export interface ReadingEstimate {
+  minutes: number;
+  /** Whether the estimate uses the reader's measured pace rather than the default. */
+  personalized: boolean;
+}
+
+/**
+ * Folds the time spent on one chapter into the rolling pace. Short chapters
+ * and implausible speeds (a chapter left open for hours, or skipped through)
+ * return `pace` unchanged.
+ */
+export function recordReadingSample(pace: ReadingPace, words: number, milliseconds: number): ReadingPace {
+  if (words < MIN_SAMPLE_WORDS || milliseconds <= 0) return pace;
+  const sample = words / (milliseconds / 60_000);
+  if (sample < MIN_PLAUSIBLE_WPM || sample > MAX_PLAUSIBLE_WPM) return pace;
+  const samples = pace.samples + 1;
+  // A plain mean over the first chapters, then an exponential average over about the last PACE_WINDOW.
+  const weight = 1 / Math.min(samples, PACE_WINDOW);
+  return { wordsPerMinute: pace.wordsPerMinute + (sample - pace.wordsPerMinute) * weight, samples };
+}
+
+/** Minutes needed to read `words`, at the reader's pace once enough chapters have been timed. */
+export function estimateReadingMinutes(words: number, pace?: ReadingPace): ReadingEstimate {
+  const measured = pace && pace.samples >= MIN_PACE_SAMPLES && pace.wordsPerMinute > 0 ? pace.wordsPerMinute : 0;
+  const wordsPerMinute = measured || DEFAULT_WORDS_PER_MINUTE;
+  return { minutes: words > 0 ? Math.max(1, Math.round(words / wordsPerMinute)) : 0, personalized: measured > 0 };
+}
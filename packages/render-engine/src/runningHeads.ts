@@ -0,0 +1,73 @@
+import { decodeEntities } from "./headings";
+
+export interface RunningHeadOptions {
+  /** Share of chapters a block must open or close before it counts as a running head. */
+  minShare?: number;
+  /** Longer blocks are real content, never a running head. */
+  maxLength?: number;
+}
+
+const TEXT_BLOCK_PATTERN = /<(p|h[1-6])\b[^>]*>([\s\S]*?)<\/\1>/gi;
+
+interface TextBlock {
+  start: number;
+  end: number;
+  text: string;
+}
+
+function textBlocks(html: string): TextBlock[] {
+  const blocks: TextBlock[] = [];
+  for (const match of html.matchAll(TEXT_BLOCK_PATTERN)) {
+    const text = decodeEntities(match[2]!.replace(/<[^>]*>/g, " "))
+      .replace(/\s+/g, " ")
+      .trim();
+    if (text) {
+      blocks.push({ start: match.index!, end: match.index! + match[0].length, text });
+    }
+  }
+  return blocks;
+}
+
+function edgeBlocks(html: string): TextBlock[] {
+  const blocks = textBlocks(html);
+  if (blocks.length === 0) return [];
+  return blocks.length === 1 ? [blocks[0]!] : [blocks[0]!, blocks[blocks.length - 1]!];
+}
+
+/**
+ * Finds short paragraphs that open or close most chapters — a book title or
+ * "Page x" line repeated by the converter — so they can be hidden while reading.
+ */
+export function findRunningHeads(chapters: string[], options: RunningHeadOptions = {}): Set<string> {
+  const minShare = options.minShare ?? 0.6;
+  const maxLength = options.maxLength ?? 80;
+  const counts = new Map<string, number>();
+
+  for (const html of chapters) {
+    const seen = new Set(edgeBlocks(html).map(block => block.text).filter(text => text.length <= maxLength));
+    for (const text of seen) {
+      counts.set(text, (counts.get(text) ?? 0) + 1);
+    }
+  }
+
+  const heads = new Set<string>();
+  if (chapters.length < 2) return heads;
+  for (const [text, count] of counts) {
+    if (count >= 2 && count / chapters.length > minShare) {
+      heads.add(text);
+    }
+  }
+  return heads;
+}
+
+/** Removes the chapter's first and last text block when they are known running heads. */
+export function stripRunningHeads(html: string, heads: Set<string>): string {
+  if (heads.size === 0) return html;
+  const removals = edgeBlocks(html).filter(block => heads.has(block.text));
+  let result = html;
+  // Cut from the end first so earlier offsets stay valid.
+  for (const block of removals.reverse()) {
+    result = result.slice(0, block.start) + result.slice(block.end);
+  }
+  return result;
+}
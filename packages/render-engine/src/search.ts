@@ -0,0 +1,189 @@
+import { htmlToPlainText } from "./plainText";
+
+export interface QueryTerm {
+  text: string;
+  /** Came from a `"quoted phrase"`. */
+  phrase: boolean;
+  /** Leading `-`: chapters containing it are excluded. */
+  exclude: boolean;
+}
+
+export interface SearchQuery {
+  terms: QueryTerm[];
+}
+
+export type HighlightRange = [start: number, end: number];
+
+export interface SearchHit {
+  chapterIndex: number;
+  /** Positions of the positive terms within `text`, sorted by start. */
+  ranges: HighlightRange[];
+  /** Whitespace-normalized chapter text the ranges refer to. */
+  text: string;
+}
+
+const QUERY_TOKEN_PATTERN = /(-?)(?:"([^"]*)"?|(\S+))/g;
+
+/**
+ * Parses `term "exact phrase" -excluded -"excluded phrase"`. Terms are ANDed;
+ * an unterminated quote runs to the end of the input.
+ */
+export function parseSearchQuery(input: string): SearchQuery {
+  const terms: QueryTerm[] = [];
+  for (const match of input.matchAll(QUERY_TOKEN_PATTERN)) {
+    const phrase = match[2] !== undefined;
+    const text = (phrase ? match[2]! : match[3]!).replace(/\s+/g, " ").trim();
+    if (!text || text === "-") continue;
+    terms.push({ text: text.toLowerCase(), phrase, exclude: match[1] === "-" });
+  }
+  return { terms };
+}
+
+function findAll(haystack: string, needle: string): HighlightRange[] {
+  const ranges: HighlightRange[] = [];
+  let from = 0;
+  while (from <= haystack.length) {
+    const index = haystack.indexOf(needle, from);
+    if (index < 0) break;
+    ranges.push([index, index + needle.length]);
+    from = index + needle.length;
+  }
+  return ranges;
+}
+
+/** Returns the ranges of every positive term, or null when the text does not satisfy the query. */
+export function matchSearchQuery(text: string, query: SearchQuery): HighlightRange[] | null {
+  const positives = query.terms.filter(term => !term.exclude);
+  if (positives.length === 0) return null;
+
+  const lower = text.toLowerCase();
+  const ranges: HighlightRange[] = [];
+  for (const term of query.terms) {
+    const found = findAll(lower, term.text);
+    if (term.exclude ? found.length > 0 : found.length === 0) {
+      return null;
+    }
+    if (!term.exclude) ranges.push(...found);
+  }
+  return ranges.sort((a, b) => a[0] - b[0] || a[1] - b[1]);
+}
+
+export function searchChapters(chapters: Array<{ content: string }>, query: SearchQuery | string): SearchHit[] {
+  const parsed = typeof query === "string" ? parseSearchQuery(query) : query;
+  const hits: SearchHit[] = [];
+  chapters.forEach((chapter, chapterIndex) => {
+    const text = htmlToPlainText(chapter.content).replace(/\s+/g, " ");
+    const ranges = matchSearchQuery(text, parsed);
+    if (ranges) {
+      hits.push({ chapterIndex, ranges, text });
+    }
+  });
+  return hits;
+}
+
+export interface NoteHit<T> {
+  item: T;
+  ranges: HighlightRange[];
+  /** Whitespace-normalized note text the ranges refer to. */
+  text: string;
+}
+
+/** Matches the notes attached to bookmarks (or any annotated item) with the chapter search syntax. */
+export function searchNotes<T extends { note?: string }>(items: T[], query: SearchQuery | string): NoteHit<T>[] {
+  const parsed = typeof query === "string" ? parseSearchQuery(query) : query;
+  const hits: NoteHit<T>[] = [];
+  for (const item of items) {
+    if (!item.note) continue;
+    const text = item.note.replace(/\s+/g, " ").trim();
+    const ranges = matchSearchQuery(text, parsed);
+    if (ranges) {
+      hits.push({ item, ranges, text });
+    }
+  }
+  return hits;
+}
+
+/** Cuts a short excerpt around the first highlight, with ranges shifted into it. */
+export function searchSnippet(hit: Pick<SearchHit, "ranges" | "text">, context = 30): { text: string; ranges: HighlightRange[] } {
+  const [firstStart, firstEnd] = hit.ranges[0] ?? [0, 0];
+  const start = Math.max(0, firstStart - context);
+  const end = Math.min(hit.text.length, firstEnd + context);
+  const ranges = hit.ranges
+    .filter(([rangeStart, rangeEnd]) => rangeStart >= start && rangeEnd <= end)
+    .map(([rangeStart, rangeEnd]): HighlightRange => [rangeStart - start, rangeEnd - start]);
+  return { text: hit.text.slice(start, end), ranges };
+}
+
+export const SEARCH_DEBOUNCE_MS = 150;
+
+/** The subset of the timer API the debouncer needs, injectable for tests. */
+export interface TimerClock {
+  setTimeout(callback: () => void, ms: number): unknown;
+  clearTimeout(handle: unknown): void;
+}
+
+const SYSTEM_CLOCK: TimerClock = {
+  setTimeout: (callback, ms) => globalThis.setTimeout(callback, ms),
+  clearTimeout: handle => globalThis.clearTimeout(handle as ReturnType<typeof globalThis.setTimeout>),
+};
+
+export interface DebouncedSearchOptions<T> {
+  /** Receives the query as typed and its result, or null once the query is cleared. */
+  onResult: (query: string, result: T | null) => void;
+  delayMs?: number;
+  clock?: TimerClock;
+}
+
+/**
+ * Search-as-you-type: runs the search once typing pauses for `delayMs`. An empty
+ * query clears the results at once, and repeating the last query reuses its
+ * result instead of searching again.
+ */
+export class DebouncedSearch<T> {
+  private readonly delayMs: number;
+  private readonly clock: TimerClock;
+  private timer: unknown = null;
+  private cached: { query: string; result: T } | null = null;
+
+  constructor(
+    private readonly search: (query: string) => T,
+    private readonly options: DebouncedSearchOptions<T>,
+  ) {
+    this.delayMs = options.delayMs ?? SEARCH_DEBOUNCE_MS;
+    this.clock = options.clock ?? SYSTEM_CLOCK;
+  }
+
+  update(query: string) {
+    this.cancel();
+    const key = query.trim();
+    if (!key || this.cached?.query === key) {
+      this.flush(query);
+      return;
+    }
+    this.timer = this.clock.setTimeout(() => {
+      this.timer = null;
+      this.flush(query);
+    }, this.delayMs);
+  }
+
+  /** Searches right away, e.g. when the query is submitted with Enter. */
+  flush(query: string) {
+    this.cancel();
+    const key = query.trim();
+    if (!key) {
+      this.options.onResult(query, null);
+      return;
+    }
+    if (this.cached?.query !== key) {
+      this.cached = { query: key, result: this.search(key) };
+    }
+    this.options.onResult(query, this.cached.result);
+  }
+
+  cancel() {
+    if (this.timer !== null) {
+      this.clock.clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+}
@@ -0,0 +1,24 @@
+/** A selected passage, told apart from repeats of the same text by its occurrence (0 for the first). */
+export interface TextSelection {
+  text: string;
+  occurrence: number;
+}
+
+/** Which occurrence of `text` in `haystack` is the one starting at `start`. */
+export function occurrenceAt(haystack: string, text: string, start: number): number {
+  let occurrence = 0;
+  for (let index = haystack.indexOf(text); index !== -1 && index < start; index = haystack.indexOf(text, index + 1)) {
+    occurrence += 1;
+  }
+  return occurrence;
+}
+
+/** Where the `occurrence`-th `text` starts in `haystack`, or -1 when there are fewer. */
+export function findOccurrence(haystack: string, text: string, occurrence: number): number {
+  if (!text) return -1;
+  let index = haystack.indexOf(text);
+  for (let seen = 0; seen < occurrence && index !== -1; seen += 1) {
+    index = haystack.indexOf(text, index + 1);
+  }
+  return index;
+}
@@ -0,0 +1,110 @@
+import { decodeEntities } from "./headings";
+import { readAttributes, resolveImageAlt } from "./images";
+
+const NON_CONTENT_PATTERN = /<(head|script|style|rt|rp)\b[^>]*>[\s\S]*?<\/\1>|<!--[\s\S]*?-->|<[!?][^>]*>/gi;
+const TOKEN_PATTERN = /<(\/?)([a-zA-Z][\w:-]*)\b([^>]*)>|([^<]+)/g;
+
+// Source tag → tag emitted in the export. Everything else is unwrapped to its text.
+const SEMANTIC_TAGS: Record<string, string> = {
+  h1: "h1",
+  h2: "h2",
+  h3: "h3",
+  h4: "h4",
+  h5: "h5",
+  h6: "h6",
+  p: "p",
+  ul: "ul",
+  ol: "ol",
+  li: "li",
+  blockquote: "blockquote",
+  strong: "strong",
+  b: "strong",
+  em: "em",
+  i: "em",
+  code: "code",
+  kbd: "code",
+  samp: "code",
+};
+
+const BLOCK_TAGS = new Set(["h1", "h2", "h3", "h4", "h5", "h6", "p", "ul", "ol", "li", "blockquote"]);
+const CONTAINER_TAGS = new Set(["ul", "ol", "blockquote"]);
+
+function escapeText(value: string): string {
+  return value.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+function escapeAttribute(value: string): string {
+  return escapeText(value).replace(/"/g, "&quot;");
+}
+
+/**
+ * Re-renders chapter markup as plain semantic HTML for pasting into other tools:
+ * headings, paragraphs, lists, quotes, emphasis, inline code and images (`src`
+ * and `alt` only) survive, every other element and attribute is dropped. The
+ * result is always well-formed.
+ */
+export function toSemanticHtml(html: string): string {
+  const output: string[] = [];
+  const open: string[] = [];
+
+  const close = (tag: string) => {
+    output.push(`</${tag}>`);
+    if (BLOCK_TAGS.has(tag)) output.push("\n");
+  };
+
+  for (const match of html.replace(NON_CONTENT_PATTERN, "").matchAll(TOKEN_PATTERN)) {
+    const [, slash, rawName, attributeSource, text] = match;
+
+    if (text !== undefined) {
+      const top = open[open.length - 1];
+      const value = decodeEntities(text).replace(/\s+/g, " ");
+      if (value.trim().length === 0 && (!top || CONTAINER_TAGS.has(top))) continue;
+      output.push(escapeText(value));
+      continue;
+    }
+
+    const name = rawName!.toLowerCase();
+    if (name === "br") {
+      if (!slash) output.push("<br/>");
+      continue;
+    }
+    if (name === "img") {
+      const attributes = readAttributes(attributeSource ?? "");
+      const src = attributes.get("src");
+      if (!slash && src) {
+        output.push(`<img src="${escapeAttribute(src)}" alt="${escapeAttribute(resolveImageAlt(attributes))}"/>`);
+      }
+      continue;
+    }
+
+    const tag = SEMANTIC_TAGS[name];
+    if (!tag) continue;
+
+    if (slash) {
+      const index = open.lastIndexOf(tag);
+      if (index === -1) continue;
+      while (open.length > index) close(open.pop()!);
+      continue;
+    }
+
+    // Paragraphs and headings cannot nest; a new block closes the previous one.
+    if (BLOCK_TAGS.has(tag) && !CONTAINER_TAGS.has(tag) && tag !== "li") {
+      const index = open.findIndex(item => item === "p" || /^h[1-6]$/.test(item));
+      if (index !== -1) {
+        while (open.length > index) close(open.pop()!);
+      }
+    }
+
+    open.push(tag);
+    output.push(`<${tag}>`);
+  }
+
+  while (open.length > 0) close(open.pop()!);
+
+  return output
+    .join("")
+    .split("\n")
+    .map(line => line.trim())
+    .filter(line => line.length > 0)
+    .join("\n");
+}
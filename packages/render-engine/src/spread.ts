@@ -0,0 +1,77 @@
+/** `single` shows one page at a time; `twoPage` lays consecutive pages side by side like an open book. */
+export type PageLayout = "single" | "twoPage";
+
+/** Narrowest readable column, in ems of the body font: about 50 characters of Latin text. */
+export const MIN_SPREAD_COLUMN_EM = 24;
+/** Space between the two pages of a spread, in pixels. */
+export const SPREAD_GUTTER_PX = 48;
+
+export const SPREAD_CSS = `
+  .reader-spread {
+    display: grid;
+    grid-template-columns: repeat(2, minmax(0, 1fr));
+    column-gap: ${SPREAD_GUTTER_PX}px;
+    align-items: start;
+  }
+  .reader-spread-page + .reader-spread-page {
+    position: relative;
+  }
+  .reader-spread-page + .reader-spread-page::before {
+    content: "";
+    position: absolute;
+    inset: 0 auto 0 -${SPREAD_GUTTER_PX / 2}px;
+    border-left: 1px solid color-mix(in srgb, var(--reader-text, #0f172a) 15%, transparent);
+  }
+`;
+
+/**
+ * The layout to use in a content area `width` pixels wide. A spread needs two
+ * columns of at least `MIN_SPREAD_COLUMN_EM` plus the gutter; anything
+ * narrower falls back to a single page.
+ */
+export function resolvePageLayout(requested: PageLayout, width: number, fontSize: number): PageLayout {
+  if (requested !== "twoPage") return "single";
+  return width >= 2 * MIN_SPREAD_COLUMN_EM * fontSize + SPREAD_GUTTER_PX ? "twoPage" : "single";
+}
+
+function pagesPerView(layout: PageLayout): number {
+  return layout === "twoPage" ? 2 : 1;
+}
+
+/** The first page of the view holding `page`; spreads always start on an even page. */
+export function spreadStart(page: number, layout: PageLayout): number {
+  const step = pagesPerView(layout);
+  return page - (page % step);
+}
+
+/** Indices of the pages shown together with `page`, left to right. */
+export function spreadPages(page: number, totalPages: number, layout: PageLayout): number[] {
+  const start = spreadStart(page, layout);
+  const end = Math.min(start + pagesPerView(layout), totalPages);
+  return Array.from({ length: Math.max(end - start, 0) }, (_, offset) => start + offset);
+}
+
+/** One-based page number for the indicator: "3" for a single page, "3\u20134" for a spread. */
+export function spreadLabel(pages: number[]): string {
+  const first = pages[0];
+  const last = pages.at(-1);
+  if (first === undefined || last === undefined) return "";
+  return first === last ? String(first + 1) : `${first + 1}\u2013${last + 1}`;
+}
+
+/** The page Next (1) or Previous (-1) shows within the chapter, or null when the move leaves it. */
+export function adjacentSpreadPage(
+  page: number,
+  direction: 1 | -1,
+  totalPages: number,
+  layout: PageLayout,
+): number | null {
+  const next = spreadStart(page, layout) + direction * pagesPerView(layout);
+  return next >= 0 && next < totalPages ? next : null;
+}
+
+/** Markup for a spread: each page's markup in its own column. A lone last page keeps the left column. */
+export function spreadMarkup(pages: string[]): string {
+  const columns = pages.map(html => `<div class="reader-spread-page">${html}</div>`);
+  return `<div class="reader-spread">${columns.join("")}</div>`;
+}
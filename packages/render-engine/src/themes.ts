@@ -0,0 +1,346 @@
+import { AppError } from "@core-platform";
+
+export interface ReaderTheme {
+  id: string;
+  label: string;
+  background: string;
+  text: string;
+  muted: string;
+  accent: string;
+  focusRing: string;
+  focusWidth: number;
+  /** Colour for bold and italic text; without one, emphasis is carried by weight and style alone. */
+  emphasisColor?: string;
+  /** Background of selected text; without one, the browser's default highlight is used. */
+  selectionColor?: string;
+  /** Image drawn behind the text when textures are enabled. */
+  backgroundTexture?: ReaderTexture;
+  /** For e-paper screens: the whole reader goes black on white, without greys, motion or dimming. */
+  eInk?: boolean;
+}
+
+export interface ReaderTexture {
+  /** Image URL, usually a `data:` URL so themes stay self-contained. */
+  url: string;
+  /** `tile` repeats the image at its own size; `stretch` scales one copy over the whole panel. */
+  fit: "tile" | "stretch";
+}
+
+// Warm fractal noise whose darkest specks stay below 10% opacity, so text contrast barely moves.
+const PAPER_NOISE_SVG =
+  '<svg xmlns="http://www.w3.org/2000/svg" width="240" height="240">' +
+  '<filter id="n"><feTurbulence type="fractalNoise" baseFrequency="0.8" numOctaves="3" stitchTiles="stitch"/>' +
+  '<feColorMatrix values="0 0 0 0 0.42 0 0 0 0 0.33 0 0 0 0 0.2 0 0 0 0.09 0"/></filter>' +
+  '<rect width="100%" height="100%" filter="url(#n)"/></svg>';
+
+export const PAPER_TEXTURE: ReaderTexture = {
+  url: `data:image/svg+xml;charset=utf-8,${encodeURIComponent(PAPER_NOISE_SVG)}`,
+  fit: "tile",
+};
+
+export const BUILT_IN_THEMES: ReaderTheme[] = [
+  {
+    id: "light",
+    label: "浅色",
+    background: "#f8fafc",
+    text: "#0f172a",
+    muted: "#475569",
+    accent: "#0369a1",
+    focusRing: "#0284c7",
+    focusWidth: 2,
+    emphasisColor: "#9a3412",
+    selectionColor: "#bae6fd",
+  },
+  {
+    id: "dark",
+    label: "深色",
+    background: "#0f172a",
+    text: "#e2e8f0",
+    muted: "#94a3b8",
+    accent: "#7dd3fc",
+    focusRing: "#38bdf8",
+    focusWidth: 2,
+    emphasisColor: "#fcd34d",
+    selectionColor: "#1e40af",
+  },
+  {
+    id: "sepia",
+    label: "护眼",
+    background: "#f4ecd8",
+    text: "#433422",
+    muted: "#6b5a45",
+    accent: "#8a4b12",
+    focusRing: "#8a4b12",
+    focusWidth: 2,
+    emphasisColor: "#7c2d12",
+    selectionColor: "#e8cf98",
+    backgroundTexture: PAPER_TEXTURE,
+  },
+  {
+    id: "high-contrast",
+    label: "高对比度",
+    background: "#000000",
+    text: "#ffffff",
+    muted: "#ffff00",
+    accent: "#ffff00",
+    focusRing: "#ffff00",
+    focusWidth: 4,
+    emphasisColor: "#00ffff",
+    selectionColor: "#0000cc",
+  },
+  {
+    id: "e-ink",
+    label: "墨水屏",
+    background: "#ffffff",
+    text: "#000000",
+    muted: "#000000",
+    accent: "#000000",
+    focusRing: "#000000",
+    focusWidth: 3,
+    emphasisColor: "#000000",
+    eInk: true,
+  },
+];
+
+export const DEFAULT_THEME_ID = "light";
+
+const THEME_COLOR_FIELDS = ["background", "text", "muted", "accent", "focusRing"] as const;
+const DEFAULT_FOCUS_WIDTH = 2;
+
+export function themeToJson(theme: ReaderTheme): string {
+  return JSON.stringify(theme, null, 2);
+}
+
+/**
+ * Parses a shared theme file. Every colour field must be present and parseable;
+ * anything else is rejected with an AppError instead of producing a half-styled theme.
+ */
+export function parseThemeJson(input: string): ReaderTheme {
+  const invalid = (message: string) =>
+    new AppError(message, {
+      code: "THEME_INVALID",
+      source: "theme-json",
+      userMessage: "主题文件格式不正确，请检查后重试。",
+    });
+
+  let raw: unknown;
+  try {
+    raw = JSON.parse(input);
+  } catch (error) {
+    throw new AppError("Theme file is not valid JSON", {
+      code: "THEME_INVALID",
+      source: "theme-json",
+      cause: error,
+      userMessage: "主题文件格式不正确，请检查后重试。",
+    });
+  }
+  if (!raw || typeof raw !== "object") {
+    throw invalid("Theme file must contain an object");
+  }
+
+  const record = raw as Record<string, unknown>;
+  const id = typeof record["id"] === "string" ? record["id"].trim() : "";
+  if (!id) {
+    throw invalid("Theme is missing an id");
+  }
+
+  const colors = {} as Record<(typeof THEME_COLOR_FIELDS)[number], string>;
+  for (const field of THEME_COLOR_FIELDS) {
+    const value = record[field];
+    if (typeof value !== "string" || !parseCssColor(value)) {
+      throw invalid(`Theme field "${field}" is missing or not a colour`);
+    }
+    colors[field] = value;
+  }
+
+  const optionalColor = (field: "emphasisColor" | "selectionColor") => {
+    const value = record[field];
+    if (value !== undefined && (typeof value !== "string" || !parseCssColor(value))) {
+      throw invalid(`Theme field "${field}" is not a colour`);
+    }
+    return value as string | undefined;
+  };
+  const emphasisColor = optionalColor("emphasisColor");
+  const selectionColor = optionalColor("selectionColor");
+
+  const texture = record["backgroundTexture"];
+  let backgroundTexture: ReaderTexture | undefined;
+  if (texture !== undefined) {
+    const { url, fit } = (texture ?? {}) as Record<string, unknown>;
+    if (typeof url !== "string" || !url.trim() || (fit !== undefined && fit !== "tile" && fit !== "stretch")) {
+      throw invalid('Theme field "backgroundTexture" needs a url and a fit of "tile" or "stretch"');
+    }
+    backgroundTexture = { url: url.trim(), fit: fit ?? "tile" };
+  }
+
+  const focusWidth = record["focusWidth"];
+  const eInk = record["eInk"] === true;
+  return {
+    id,
+    label: typeof record["label"] === "string" && record["label"].trim() ? record["label"].trim() : id,
+    ...colors,
+    focusWidth: typeof focusWidth === "number" && focusWidth > 0 ? focusWidth : DEFAULT_FOCUS_WIDTH,
+    ...(emphasisColor ? { emphasisColor } : {}),
+    ...(selectionColor ? { selectionColor } : {}),
+    ...(backgroundTexture ? { backgroundTexture } : {}),
+    ...(eInk ? { eInk } : {}),
+  };
+}
+
+export function findTheme(themes: ReaderTheme[], id: string): ReaderTheme {
+  return themes.find(theme => theme.id === id) ?? themes[0] ?? BUILT_IN_THEMES[0]!;
+}
+
+export const MIN_SOURCE_COLOR_CONTRAST = 3;
+
+export function parseHexColor(input: string): [number, number, number] | null {
+  const match = /^#?([0-9a-f]{3}|[0-9a-f]{6})$/i.exec(input.trim());
+  if (!match) return null;
+
+  let hex = match[1]!;
+  if (hex.length === 3) {
+    hex = hex
+      .split("")
+      .map(char => char + char)
+      .join("");
+  }
+
+  return [
+    parseInt(hex.slice(0, 2), 16),
+    parseInt(hex.slice(2, 4), 16),
+    parseInt(hex.slice(4, 6), 16),
+  ];
+}
+
+const RGB_COLOR_PATTERN =
+  /^rgba?\(\s*(\d+(?:\.\d+)?)[\s,]+(\d+(?:\.\d+)?)[\s,]+(\d+(?:\.\d+)?)(?:[\s,/]+(?:\d*\.)?\d+%?)?\s*\)$/i;
+
+/** Parses `#rgb`, `#rrggbb` and `rgb()/rgba()` notations (alpha is ignored). */
+export function parseCssColor(input: string): [number, number, number] | null {
+  const rgb = RGB_COLOR_PATTERN.exec(input.trim());
+  if (rgb) {
+    const channels: [number, number, number] = [Number(rgb[1]), Number(rgb[2]), Number(rgb[3])];
+    return channels.every(channel => channel <= 255) ? channels : null;
+  }
+  return parseHexColor(input);
+}
+
+export function relativeLuminance([r, g, b]: [number, number, number]): number {
+  const channel = (value: number) => {
+    const normalized = value / 255;
+    return normalized <= 0.03928 ? normalized / 12.92 : Math.pow((normalized + 0.055) / 1.055, 2.4);
+  };
+  return 0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b);
+}
+
+/** Whether the theme puts text on a dark background. */
+export function isDarkTheme(theme: Pick<ReaderTheme, "background">): boolean {
+  const background = parseCssColor(theme.background);
+  return background ? relativeLuminance(background) < 0.2 : false;
+}
+
+/** WCAG 2.x contrast ratio, from 1 (identical) to 21 (black on white). */
+export function contrastRatio(foreground: string, background: string): number {
+  const fg = parseCssColor(foreground);
+  const bg = parseCssColor(background);
+  if (!fg || !bg) return 1;
+
+  const lighter = Math.max(relativeLuminance(fg), relativeLuminance(bg));
+  const darker = Math.min(relativeLuminance(fg), relativeLuminance(bg));
+  return (lighter + 0.05) / (darker + 0.05);
+}
+
+/**
+ * Keeps a colour authored by the book only if it stays legible on the theme
+ * background; returns null when the reader should fall back to the theme text.
+ */
+export function resolveSourceColor(
+  color: string,
+  background: string,
+  minRatio = MIN_SOURCE_COLOR_CONTRAST,
+): string | null {
+  if (!parseCssColor(color)) return null;
+  return contrastRatio(color, background) >= minRatio ? color : null;
+}
+
+export interface ThemeCssOptions {
+  respectSourceColors?: boolean;
+  /** Tint bold and italic text with the theme's emphasis colour, when it has one. */
+  emphasisColors?: boolean;
+  /** Draw the theme's background texture, when it has one. */
+  texture?: boolean;
+}
+
+function cssUrl(url: string): string {
+  return `url("${url.replace(/["\\\n]/g, char => `\\${char === "\n" ? "a " : char}`)}")`;
+}
+
+export function buildThemeCss(theme: ReaderTheme, options: ThemeCssOptions = {}): string {
+  // By default the theme owns text colour; book colours are only honoured on request.
+  const colorOverride = options.respectSourceColors
+    ? ""
+    : `
+    .reader-scope * {
+      color: inherit !important;
+    }
+    .reader-scope rt,
+    .reader-scope figcaption {
+      color: var(--reader-muted) !important;
+    }`;
+  const emphasisVariable = theme.emphasisColor
+    ? `
+      --reader-emphasis: ${theme.emphasisColor};`
+    : "";
+  const selectionVariable = theme.selectionColor
+    ? `
+      --reader-selection: ${theme.selectionColor};`
+    : "";
+  // More specific than the override above, so emphasis keeps its colour either way.
+  const emphasis =
+    options.emphasisColors && theme.emphasisColor
+      ? `
+    .reader-scope :is(b, strong, i, em) {
+      color: var(--reader-emphasis) !important;
+    }`
+      : "";
+  // E-paper shows a tinted highlight as grey, so selections there are inverted instead.
+  const selection = theme.eInk
+    ? `
+    .reader-scope ::selection {
+      color: var(--reader-bg);
+      background: var(--reader-text);
+    }`
+    : theme.selectionColor
+      ? `
+    .reader-scope ::selection {
+      background: var(--reader-selection);
+    }`
+      : "";
+  // The texture sits on the scrolling host, so it stays put while the text scrolls over it.
+  const texture = options.texture ? theme.backgroundTexture : undefined;
+  const textureVariables = texture
+    ? `
+      --reader-texture: ${cssUrl(texture.url)};
+      --reader-texture-size: ${texture.fit === "stretch" ? "cover" : "auto"};
+      --reader-texture-repeat: ${texture.fit === "stretch" ? "no-repeat" : "repeat"};`
+    : "";
+  const transparentScope = texture
+    ? `
+    :host .reader-scope {
+      background: transparent;
+    }`
+    : "";
+
+  return `
+    :host {
+      --reader-bg: ${theme.background};
+      --reader-text: ${theme.text};
+      --reader-muted: ${theme.muted};
+      --reader-accent: ${theme.accent};${emphasisVariable}${selectionVariable}${textureVariables}
+    }
+    .reader-scope a:focus-visible {
+      outline: ${theme.focusWidth}px solid ${theme.focusRing};
+      outline-offset: 2px;
+    }${colorOverride}${emphasis}${selection}${transparentScope}
+  `;
+}
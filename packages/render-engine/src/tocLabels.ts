@@ -0,0 +1,67 @@
+export interface TocLabelNode {
+  label: string;
+  href: string;
+  children?: TocLabelNode[];
+}
+
+interface FlatEntry {
+  label: string;
+  level: number;
+  parent?: string;
+  chapter: number;
+}
+
+function flatten(
+  nodes: TocLabelNode[],
+  resolveChapter: (href: string) => number,
+  level = 0,
+  parent?: string,
+): FlatEntry[] {
+  const entries: FlatEntry[] = [];
+  for (const node of nodes) {
+    entries.push({ label: node.label, level, parent, chapter: resolveChapter(node.href) });
+    if (node.children) entries.push(...flatten(node.children, resolveChapter, level + 1, node.label));
+  }
+  return entries;
+}
+
+function allDistinct(values: Array<string | undefined>): boolean {
+  return values.every(Boolean) && new Set(values).size === values.length;
+}
+
+/**
+ * Display labels for a TOC, in depth-first order. Entries sharing a label at
+ * the same level get context appended, "练习（第 4 章）" style: the parent
+ * entry's label when that tells them apart, else the chapter they open,
+ * else their ordinal among the duplicates. The TOC itself is not changed.
+ */
+export function disambiguateTocLabels(
+  toc: TocLabelNode[],
+  resolveChapter: (href: string) => number = () => -1,
+): string[] {
+  const entries = flatten(toc, resolveChapter);
+  const groups = new Map<string, number[]>();
+  entries.forEach((entry, index) => {
+    const key = `${entry.level}\u0000${entry.label.trim().toLowerCase()}`;
+    groups.set(key, [...(groups.get(key) ?? []), index]);
+  });
+
+  const labels = entries.map(entry => entry.label);
+  for (const members of groups.values()) {
+    if (members.length < 2) continue;
+    const parents = members.map(index => entries[index]!.parent?.trim());
+    const chapters = members.map(index => {
+      const chapter = entries[index]!.chapter;
+      return chapter >= 0 ? `第 ${chapter + 1} 章` : undefined;
+    });
+    const contexts = allDistinct(parents)
+      ? parents
+      : allDistinct(chapters)
+        ? chapters
+        : members.map((_index, ordinal) => String(ordinal + 1));
+    members.forEach((index, position) => {
+      labels[index] = `${entries[index]!.label.trim()}（${contexts[position]}）`;
+    });
+  }
+  return labels;
+}
@@ -0,0 +1,80 @@
+import type { ReadingPrefs } from "./index";
+
+// Languages for which mainstream browsers ship hyphenation dictionaries.
+const HYPHENATION_LANGUAGES = new Set([
+  "da",
+  "de",
+  "en",
+  "es",
+  "fi",
+  "fr",
+  "hu",
+  "it",
+  "nb",
+  "nl",
+  "nn",
+  "pl",
+  "pt",
+  "ru",
+  "sv",
+]);
+
+export interface TypographyContext {
+  language?: string;
+}
+
+export function resolveHyphenationLanguage(language?: string): string | null {
+  if (!language) return null;
+  const primary = language.trim().toLowerCase().split(/[-_]/)[0];
+  return primary && HYPHENATION_LANGUAGES.has(primary) ? primary : null;
+}
+
+const RTL_LANGUAGES = new Set(["ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi"]);
+
+export function isRtlLanguage(language?: string): boolean {
+  if (!language) return false;
+  const primary = language.trim().toLowerCase().split(/[-_]/)[0];
+  return primary ? RTL_LANGUAGES.has(primary) : false;
+}
+
+/**
+ * The page direction for a chapter: the book's declared page progression when
+ * it has one, else a guess from the chapter's language.
+ */
+export function resolveTextDirection(
+  pageProgression: "ltr" | "rtl" | undefined,
+  language?: string,
+): "ltr" | "rtl" | undefined {
+  if (pageProgression) return pageProgression;
+  return isRtlLanguage(language) ? "rtl" : undefined;
+}
+
+export function buildTypographyCss(
+  prefs: Pick<ReadingPrefs, "textAlign" | "hyphenate"> &
+    Partial<Pick<ReadingPrefs, "firstLineIndent" | "paragraphGap">>,
+  context: TypographyContext = {},
+): string {
+  const rules: string[] = [];
+
+  if (prefs.textAlign === "justify") {
+    const declarations = ["text-align: justify;"];
+    // The browser inserts the break points itself once `hyphens: auto` is set and
+    // the container carries a `lang` it has a dictionary for.
+    if (prefs.hyphenate && resolveHyphenationLanguage(context.language)) {
+      declarations.push("-webkit-hyphens: auto;", "hyphens: auto;");
+    }
+    rules.push(`.reader-scope p,\n.reader-scope blockquote {\n  ${declarations.join("\n  ")}\n}`);
+  }
+
+  const indent = prefs.firstLineIndent ?? 0;
+  if (indent > 0) {
+    rules.push(`.reader-scope p {\n  text-indent: ${indent}em;\n}`);
+    // As in print, the paragraph right after a heading starts flush.
+    rules.push(`.reader-scope :is(h1, h2, h3, h4, h5, h6) + p {\n  text-indent: 0;\n}`);
+    if (prefs.paragraphGap === false) {
+      rules.push(`.reader-scope p {\n  margin-top: 0;\n  margin-bottom: 0;\n}`);
+    }
+  }
+
+  return rules.join("\n");
+}
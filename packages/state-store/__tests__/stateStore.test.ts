@@ -1,5 +1,24 @@
 import { describe, expect, it } from "bun:test";
-import { InMemoryStateStore } from "@state-store";
+import {
+  CLIPPING_SEPARATOR,
+  ClippingsStore,
+  CORRUPT_SETTINGS_SUFFIX,
+  createBrowserStateStore,
+  createSyncFile,
+  decideResume,
+  DEFAULT_PROFILE,
+  InMemoryStateStore,
+  mergeSnapshots,
+  normalizeProfileName,
+  parseSyncFile,
+  positionAfterReload,
+  profileKey,
+  ProfileStore,
+  SelectionStore,
+  SettingsStore,
+  shouldOfferResume,
+  SYNC_FILE_VERSION,
+} from "@state-store";
 import type { StateSnapshot, StateStoreBackend } from "@state-store";
 
 class MemoryBackend implements StateStoreBackend {
@@ -18,6 +37,15 @@ class MemoryBackend implements StateStoreBackend {
   }
 }
 
+function createStorage(initial: Record<string, string> = {}) {
+  const values = new Map(Object.entries(initial));
+  return {
+    values,
+    getItem: (key: string) => values.get(key) ?? null,
+    setItem: (key: string, value: string) => void values.set(key, value),
+  };
+}
+
 describe("InMemoryStateStore", () => {
   it("hydrates from snapshot and persists changes", async () => {
     const initial: StateSnapshot = {
@@ -28,7 +56,7 @@ describe("InMemoryStateStore", () => {
     };
 
     const backend = new MemoryBackend(initial);
-    const store = new InMemoryStateStore({ backend });
+    const store = new InMemoryStateStore({ backend, now: () => 1_000 });
     await store.hydrate();
 
     const progress = await store.loadProgress("book_a");
@@ -38,6 +66,294 @@ describe("InMemoryStateStore", () => {
     expect((await backend.load())?.book_a?.lastLocation).toEqual({ spineIndex: 3, offset: 10 });
 
     await store.addBookmark("book_b", { spineIndex: 0, offset: 1 });
-    expect((await backend.load())?.book_b?.bookmarks).toEqual([{ spineIndex: 0, offset: 1 }]);
+    expect((await backend.load())?.book_b?.bookmarks).toEqual([{ spineIndex: 0, offset: 1, updatedAt: 1_000 }]);
+  });
+
+  it("round-trips bookmark notes through the backend", async () => {
+    const backend = new MemoryBackend({});
+    const store = new InMemoryStateStore({ backend, now: () => 1_000 });
+    await store.addBookmark("book_a", { spineIndex: 2, offset: 3 }, "  Ahab's first appearance ");
+    await store.addBookmark("book_a", { spineIndex: 4, offset: 0 });
+    expect(await store.updateBookmarkNote("book_a", { spineIndex: 4, offset: 0 }, "Storm")).toBe(true);
+    expect(await store.updateBookmarkNote("book_a", { spineIndex: 9, offset: 0 }, "Missing")).toBe(false);
+
+    const serialized = JSON.stringify(await backend.load());
+    const restored = new InMemoryStateStore({ backend: new MemoryBackend(JSON.parse(serialized)), now: () => 2_000 });
+
+    expect(await restored.listBookmarks("book_a")).toEqual([
+      { spineIndex: 2, offset: 3, note: "Ahab's first appearance", updatedAt: 1_000 },
+      { spineIndex: 4, offset: 0, note: "Storm", updatedAt: 1_000 },
+    ]);
+
+    await restored.updateBookmarkNote("book_a", { spineIndex: 4, offset: 0 }, "");
+    expect((await restored.listBookmarks("book_a"))[1]).toEqual({ spineIndex: 4, offset: 0, updatedAt: 2_000 });
+  });
+});
+
+describe("sync files", () => {
+  it("adopts the newer entry per book when merging", async () => {
+    let clock = 1_000;
+    const store = new InMemoryStateStore({ now: () => clock });
+    await store.saveProgress("book_a", { spineIndex: 1, offset: 0 });
+    await store.saveProgress("book_b", { spineIndex: 4, offset: 2 });
+
+    const adopted = await store.mergeSync({
+      version: SYNC_FILE_VERSION,
+      books: {
+        book_a: { spineIndex: 7, offset: 3, updatedAt: 2_000 },
+        book_b: { spineIndex: 0, offset: 0, updatedAt: 500 },
+        book_c: { spineIndex: 2, offset: 1, updatedAt: 1_500 },
+      },
+    });
+
+    expect(adopted.sort()).toEqual(["book_a", "book_c"]);
+    expect(await store.loadProgress("book_a")).toEqual({ spineIndex: 7, offset: 3 });
+    expect(await store.loadProgress("book_b")).toEqual({ spineIndex: 4, offset: 2 });
+    expect(await store.loadProgress("book_c")).toEqual({ spineIndex: 2, offset: 1 });
+
+    clock = 3_000;
+    await store.saveProgress("book_a", { spineIndex: 8, offset: 0 });
+    expect(createSyncFile(store.snapshot()).books.book_a).toEqual({ spineIndex: 8, offset: 0, updatedAt: 3_000 });
+  });
+
+  it("ignores sync files from an unknown future version", async () => {
+    const raw = { version: SYNC_FILE_VERSION + 1, books: { book_a: { spineIndex: 9, offset: 0, updatedAt: 9_999 } } };
+    expect(parseSyncFile(raw)).toBeNull();
+
+    const store = new InMemoryStateStore({ now: () => 1_000 });
+    await store.saveProgress("book_a", { spineIndex: 1, offset: 0 });
+    const adopted = await store.mergeSync(raw);
+
+    expect(adopted).toEqual([]);
+    expect(await store.loadProgress("book_a")).toEqual({ spineIndex: 1, offset: 0 });
+  });
+
+  it("keeps the newer copy of a bookmark with an edited note", () => {
+    const base: StateSnapshot = { book_a: { bookmarks: [{ spineIndex: 2, offset: 0, note: "A", updatedAt: 1_000 }] } };
+    const edited: StateSnapshot = {
+      book_a: { bookmarks: [{ spineIndex: 2, offset: 0, note: "B", updatedAt: 2_000 }] },
+    };
+
+    expect(mergeSnapshots(base, edited).book_a?.bookmarks).toEqual([
+      { spineIndex: 2, offset: 0, note: "B", updatedAt: 2_000 },
+    ]);
+    expect(mergeSnapshots(edited, base).book_a?.bookmarks).toEqual([
+      { spineIndex: 2, offset: 0, note: "B", updatedAt: 2_000 },
+    ]);
+  });
+
+  it("keeps a cleared note when the clearing is newer", () => {
+    const base: StateSnapshot = { book_a: { bookmarks: [{ spineIndex: 2, offset: 0, note: "A", updatedAt: 1_000 }] } };
+    const cleared: StateSnapshot = { book_a: { bookmarks: [{ spineIndex: 2, offset: 0, updatedAt: 2_000 }] } };
+
+    expect(mergeSnapshots(base, cleared).book_a?.bookmarks).toEqual([{ spineIndex: 2, offset: 0, updatedAt: 2_000 }]);
+  });
+});
+
+describe("recent books", () => {
+  it("puts the most recently read book first", async () => {
+    let now = 1_000;
+    const store = new InMemoryStateStore({ now: () => now });
+
+    await store.markRead("book_a");
+    now += 5_000;
+    await store.markRead("book_b");
+
+    expect(store.recentBooks().map(entry => entry.bookId)).toEqual(["book_b", "book_a"]);
+
+    now += 5_000;
+    await store.markRead("book_a");
+    expect(store.recentBooks().map(entry => entry.bookId)).toEqual(["book_a", "book_b"]);
+  });
+
+  it("clamps timestamps from the future to the current time", async () => {
+    const backend = new MemoryBackend({
+      book_a: { lastReadAt: 50_000, bookmarks: [] },
+      book_b: { lastReadAt: 9_000, bookmarks: [] },
+    });
+    const store = new InMemoryStateStore({ backend, now: () => 10_000 });
+    await store.hydrate();
+
+    expect(store.recentBooks()).toEqual([
+      { bookId: "book_a", lastReadAt: 10_000 },
+      { bookId: "book_b", lastReadAt: 9_000 },
+    ]);
+  });
+});
+
+describe("resume prompt", () => {
+  const saved = { spineIndex: 4, offset: 2 };
+
+  it("only offers to resume past the first chapter", () => {
+    expect(shouldOfferResume(undefined)).toBe(false);
+    expect(shouldOfferResume({ spineIndex: 0, offset: 3 })).toBe(false);
+    expect(shouldOfferResume(saved)).toBe(true);
+  });
+
+  it("resumes on request or when the prompt times out", () => {
+    expect(decideResume(saved, "resume")).toEqual({ location: saved, clearSaved: false });
+    expect(decideResume(saved, "timeout")).toEqual({ location: saved, clearSaved: false });
+  });
+
+  it("starts over from the beginning and clears the saved position", async () => {
+    expect(decideResume(saved, "startOver")).toEqual({ location: { spineIndex: 0, offset: 0 }, clearSaved: true });
+
+    const store = new InMemoryStateStore();
+    await store.saveProgress("book_a", saved);
+    await store.clearProgress("book_a");
+    expect(await store.loadProgress("book_a")).toBeUndefined();
+  });
+});
+
+describe("reloading a book", () => {
+  it("keeps a chapter that still exists", () => {
+    expect(positionAfterReload({ chapter: 3, page: 2 }, 10)).toEqual({ chapter: 3, page: 2 });
+  });
+
+  it("clamps a chapter the new version no longer has", () => {
+    expect(positionAfterReload({ chapter: 12, page: 4 }, 10)).toEqual({ chapter: 9, page: 0 });
+    expect(positionAfterReload({ chapter: 2, page: 1 }, 0)).toEqual({ chapter: 0, page: 0 });
+  });
+});
+
+describe("SettingsStore", () => {
+  const defaults = { themeId: "light", fontSize: 18, hyphenate: false };
+
+  it("uses the defaults when nothing is stored", () => {
+    const settings = new SettingsStore({ key: "prefs", defaults, storage: createStorage() });
+    expect(settings.load()).toEqual(defaults);
+  });
+
+  it("backs up a corrupt value and falls back to the defaults", () => {
+    for (const raw of ["{not json", "[1, 2]", "42"]) {
+      const storage = createStorage({ prefs: raw });
+      const settings = new SettingsStore({ key: "prefs", defaults, storage });
+
+      expect(settings.load()).toEqual(defaults);
+      expect(storage.values.get(`prefs${CORRUPT_SETTINGS_SUFFIX}`)).toBe(raw);
+    }
+  });
+
+  it("keeps valid fields and drops unknown or mistyped ones", () => {
+    const storage = createStorage({ prefs: JSON.stringify({ themeId: "dark", fontSize: "huge", legacy: true }) });
+    const settings = new SettingsStore({ key: "prefs", defaults, storage });
+
+    expect(settings.load()).toEqual({ themeId: "dark", fontSize: 18, hyphenate: false });
+    settings.save({ ...defaults, fontSize: 20 });
+    expect(JSON.parse(storage.values.get("prefs")!)).toEqual({ ...defaults, fontSize: 20 });
+  });
+});
+
+describe("reader profiles", () => {
+  it("keeps independent progress for the same book", async () => {
+    const backends = new Map<string, MemoryBackend>();
+    const storeFor = (profile: string) => {
+      const key = profileKey("bkai.reader.state.v1", profile);
+      if (!backends.has(key)) backends.set(key, new MemoryBackend({}));
+      return new InMemoryStateStore({ backend: backends.get(key) });
+    };
+
+    await storeFor("alice").saveProgress("book_a", { spineIndex: 3, offset: 2 });
+    await storeFor("bob").saveProgress("book_a", { spineIndex: 9, offset: 0 });
+
+    expect(await storeFor("alice").loadProgress("book_a")).toEqual({ spineIndex: 3, offset: 2 });
+    expect(await storeFor("bob").loadProgress("book_a")).toEqual({ spineIndex: 9, offset: 0 });
+    expect(await storeFor(DEFAULT_PROFILE).loadProgress("book_a")).toBeUndefined();
+  });
+
+  it("keeps signed-in profiles apart in local storage and the cloud", async () => {
+    const documents = new Map<string, string>();
+    const originalFetch = globalThis.fetch;
+    const globals = globalThis as { window?: unknown };
+    const originalWindow = globals.window;
+    globalThis.fetch = (async (input: RequestInfo | URL, init?: RequestInit) => {
+      const url = String(input);
+      if (init?.method === "PUT") {
+        documents.set(url, String(init.body));
+        return Response.json({ ok: true });
+      }
+      const stored = documents.get(url);
+      return stored ? new Response(stored) : new Response(null, { status: 404 });
+    }) as unknown as typeof fetch;
+    globals.window = { localStorage: createStorage() };
+
+    try {
+      const storeFor = (profile: string) =>
+        createBrowserStateStore({ authToken: "token", preferIndexedDb: false, profile });
+      await (await storeFor("alice")).saveProgress("book_a", { spineIndex: 3, offset: 2 });
+      await (await storeFor(DEFAULT_PROFILE)).saveProgress("book_a", { spineIndex: 9, offset: 0 });
+
+      expect(await (await storeFor("alice")).loadProgress("book_a")).toEqual({ spineIndex: 3, offset: 2 });
+      expect(await (await storeFor(DEFAULT_PROFILE)).loadProgress("book_a")).toEqual({ spineIndex: 9, offset: 0 });
+      expect(await (await storeFor("bob")).loadProgress("book_a")).toBeUndefined();
+      expect([...documents.keys()].sort()).toEqual(["/api/reading-state", "/api/reading-state?profile=alice"]);
+    } finally {
+      globalThis.fetch = originalFetch;
+      globals.window = originalWindow;
+    }
+  });
+
+  it("leaves the default profile on the original keys", () => {
+    expect(profileKey("bkai.prefs", DEFAULT_PROFILE)).toBe("bkai.prefs");
+    expect(profileKey("bkai.prefs", "  ")).toBe("bkai.prefs");
+    expect(profileKey("bkai.prefs", "小明")).toBe(`bkai.prefs.profile.${encodeURIComponent("小明")}`);
+    expect(normalizeProfileName("  kids  ")).toBe("kids");
+  });
+
+  it("remembers the profiles and which one is active", () => {
+    const storage = createStorage();
+    expect(new ProfileStore({ storage }).load()).toEqual({ active: DEFAULT_PROFILE, profiles: [DEFAULT_PROFILE] });
+
+    new ProfileStore({ storage }).activate(" alice ");
+    new ProfileStore({ storage }).activate("bob");
+    expect(new ProfileStore({ storage }).activate("alice")).toEqual({
+      active: "alice",
+      profiles: [DEFAULT_PROFILE, "alice", "bob"],
+    });
+    expect(new ProfileStore({ storage }).load().active).toBe("alice");
+  });
+});
+
+describe("clippings", () => {
+  it("creates the book's document on the first clipping and appends after that", () => {
+    const storage = createStorage();
+    const clippings = new ClippingsStore({ storage });
+    const citation = "Eliot, G. (1871). Middlemarch.";
+
+    expect(clippings.read("book_a")).toBe("");
+    clippings.append("book_a", { text: "It is a narrow mind", citation, clippedAt: new Date(2026, 9, 15, 9, 5) });
+    const document = new ClippingsStore({ storage }).append("book_a", {
+      text: "First line\n\nSecond line",
+      citation,
+      clippedAt: new Date(2026, 9, 15, 21, 30),
+    });
+
+    expect(document).toBe(
+      `> It is a narrow mind\n\n— ${citation}\n\n*2026-10-15 09:05*` +
+        CLIPPING_SEPARATOR +
+        `> First line\n>\n> Second line\n\n— ${citation}\n\n*2026-10-15 21:30*\n`,
+    );
+    expect(document.split(CLIPPING_SEPARATOR)).toHaveLength(2);
+    expect(clippings.read("book_a")).toBe(document);
+    expect(clippings.read("book_b")).toBe("");
+  });
+});
+
+describe("last selections", () => {
+  it("round-trips the last selection of each chapter", () => {
+    const storage = createStorage();
+    const selections = new SelectionStore({ storage });
+    const passage = { text: "a narrow mind", occurrence: 1, page: 2 };
+
+    selections.save("book_a", 3, passage);
+    selections.save("book_a", 4, { text: "Middlemarch", occurrence: 0, page: 0 });
+    expect(new SelectionStore({ storage }).load("book_a", 3)).toEqual(passage);
+    expect(new SelectionStore({ storage }).load("book_a", 5)).toBeUndefined();
+    expect(new SelectionStore({ storage }).load("book_b", 3)).toBeUndefined();
+    expect(new SelectionStore({ storage, profile: "alice" }).load("book_a", 3)).toBeUndefined();
+
+    selections.save("book_a", 3, null);
+    expect(selections.load("book_a", 3)).toBeUndefined();
+    expect(selections.load("book_a", 4)?.text).toBe("Middlemarch");
   });
 });
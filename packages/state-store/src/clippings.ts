@@ -0,0 +1,79 @@
+import { AppError } from "@core-platform";
+import { profileKey } from "./profiles";
+import { browserStorage, type SettingsStorage } from "./settings";
+
+export const CLIPPINGS_KEY = "bkai.clippings";
+/** Markdown rule placed between two clippings. */
+export const CLIPPING_SEPARATOR = "\n\n---\n\n";
+
+export interface Clipping {
+  text: string;
+  /** Source line, e.g. the book's formatted attribution. */
+  citation: string;
+  clippedAt: Date;
+}
+
+export interface ClippingsStoreOptions {
+  key?: string;
+  /** Defaults to `window.localStorage` when it is available. */
+  storage?: SettingsStorage;
+  profile?: string;
+}
+
+function pad(value: number): string {
+  return String(value).padStart(2, "0");
+}
+
+/** `2026-10-15 09:05` in local time, the way a reader would write it down. */
+function formatTimestamp(date: Date): string {
+  const day = `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+  return `${day} ${pad(date.getHours())}:${pad(date.getMinutes())}`;
+}
+
+/** One clipping as Markdown: the passage as a blockquote, then its source and when it was taken. */
+export function formatClipping(clipping: Clipping): string {
+  const quote = clipping.text
+    .trim()
+    .split("\n")
+    .map(line => (line.trim() ? `> ${line.trimEnd()}` : ">"))
+    .join("\n");
+  return `${quote}\n\n— ${clipping.citation}\n\n*${formatTimestamp(clipping.clippedAt)}*`;
+}
+
+/** A Markdown document of passages clipped from each book, added to and never rewritten. */
+export class ClippingsStore {
+  private readonly key: string;
+  private readonly storage: SettingsStorage | undefined;
+
+  constructor(options: ClippingsStoreOptions = {}) {
+    this.key = profileKey(options.key ?? CLIPPINGS_KEY, options.profile ?? "");
+    this.storage = options.storage ?? browserStorage();
+  }
+
+  /** The book's clippings; empty until the first one is added. */
+  read(bookId: string): string {
+    return this.storage?.getItem(this.documentKey(bookId)) ?? "";
+  }
+
+  /** Appends `clipping` to the book's document, starting the document if there is none, and returns it. */
+  append(bookId: string, clipping: Clipping): string {
+    const existing = this.read(bookId).trimEnd();
+    const document = `${existing ? existing + CLIPPING_SEPARATOR : ""}${formatClipping(clipping)}\n`;
+    try {
+      if (!this.storage) throw new Error("No storage is available");
+      this.storage.setItem(this.documentKey(bookId), document);
+    } catch (error) {
+      throw new AppError("Failed to save clipping", {
+        code: "CLIPPING_SAVE_FAILED",
+        source: "state-store",
+        userMessage: "摘录保存失败，浏览器存储空间可能已满。",
+        cause: error,
+      });
+    }
+    return document;
+  }
+
+  private documentKey(bookId: string): string {
+    return `${this.key}.${encodeURIComponent(bookId)}`;
+  }
+}
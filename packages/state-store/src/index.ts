@@ -3,9 +3,13 @@ import { createIndexedDbBackend, IndexedDbBackend } from "./storage/indexeddb";
 import { createLocalStorageBackend, LocalStorageBackend } from "./storage/localStorage";
 import { createCloudBackend, CloudBackend } from "./storage/cloud";
 import { createCompositeBackend, CompositeBackend } from "./storage/composite";
+import { SYNC_FILE_VERSION, type SyncFile } from "./sync";
+import { profileKey } from "./profiles";
 import {
+  Bookmark,
   PageLocator,
   ReadingSessionState,
+  RecentBook,
   StateSnapshot,
   StateStoreBackend,
 } from "./types";
@@ -13,21 +17,28 @@ import {
 export type StateEvents = {
   "progress:updated": { bookId: string; location: PageLocator };
   "bookmark:added": { bookId: string; location: PageLocator };
+  "bookmark:updated": { bookId: string; bookmark: Bookmark };
 };
 
+/** Repeated reads of the same book within this window are not persisted again. */
+export const LAST_READ_PERSIST_INTERVAL_MS = 60_000;
+
 export interface StateStoreOptions {
   backend?: StateStoreBackend;
+  now?: () => number;
 }
 
 export class InMemoryStateStore {
   private readonly sessions = new Map<string, ReadingSessionState>();
   private readonly events = new EventBus<StateEvents>();
   private readonly backend?: StateStoreBackend;
+  private readonly now: () => number;
   private hydrated = false;
   private pendingPersist?: Promise<void>;
 
   constructor(options: StateStoreOptions = {}) {
     this.backend = options.backend;
+    this.now = options.now ?? Date.now;
   }
 
   on = this.events.on.bind(this.events);
@@ -46,7 +57,9 @@ export class InMemoryStateStore {
           this.sessions.set(bookId, {
             bookId,
             lastLocation: data.lastLocation,
-            bookmarks: Array.isArray(data.bookmarks) ? [...data.bookmarks] : [],
+            updatedAt: data.updatedAt,
+            lastReadAt: data.lastReadAt,
+            bookmarks: Array.isArray(data.bookmarks) ? data.bookmarks.map(normalizeBookmark) : [],
           });
         }
       }
@@ -61,27 +74,109 @@ export class InMemoryStateStore {
     await this.ensureHydrated();
     const session = this.ensureSession(bookId);
     session.lastLocation = location;
+    session.updatedAt = this.now();
     this.events.emit("progress:updated", { bookId, location });
     await this.persist();
   }
 
+  /**
+   * Adopts progress from a sync file for every book whose entry is newer than
+   * the local one. Files with an unknown version are ignored. Returns the ids
+   * of the books that changed.
+   */
+  async mergeSync(file: SyncFile): Promise<string[]> {
+    await this.ensureHydrated();
+    if (file.version !== SYNC_FILE_VERSION) {
+      return [];
+    }
+
+    const adopted: string[] = [];
+    for (const [bookId, entry] of Object.entries(file.books)) {
+      const session = this.ensureSession(bookId);
+      if (session.updatedAt !== undefined && session.updatedAt >= entry.updatedAt) {
+        continue;
+      }
+      const location = { spineIndex: entry.spineIndex, offset: entry.offset };
+      session.lastLocation = location;
+      session.updatedAt = entry.updatedAt;
+      adopted.push(bookId);
+      this.events.emit("progress:updated", { bookId, location });
+    }
+
+    if (adopted.length > 0) {
+      await this.persist();
+    }
+    return adopted;
+  }
+
+  /**
+   * Records that the book is being read. The in-memory timestamp always moves so
+   * ordering stays correct; persisting is throttled per book.
+   */
+  async markRead(bookId: string) {
+    await this.ensureHydrated();
+    const session = this.ensureSession(bookId);
+    const now = this.now();
+    const previous = session.lastReadAt;
+    session.lastReadAt = now;
+    if (previous === undefined || Math.abs(now - previous) >= LAST_READ_PERSIST_INTERVAL_MS) {
+      await this.persist();
+    }
+  }
+
+  /** Books ordered by last read, newest first. Timestamps from the future are clamped to now. */
+  recentBooks(): RecentBook[] {
+    const now = this.now();
+    const recents: RecentBook[] = [];
+    for (const session of this.sessions.values()) {
+      if (session.lastReadAt === undefined) continue;
+      recents.push({ bookId: session.bookId, lastReadAt: Math.min(session.lastReadAt, now) });
+    }
+    return recents.sort((a, b) => b.lastReadAt - a.lastReadAt);
+  }
+
   async loadProgress(bookId: string): Promise<PageLocator | undefined> {
     await this.ensureHydrated();
     return this.sessions.get(bookId)?.lastLocation;
   }
 
-  async addBookmark(bookId: string, location: PageLocator) {
+  /** Forgets the reading position of a book, keeping its bookmarks. */
+  async clearProgress(bookId: string) {
+    await this.ensureHydrated();
+    const session = this.sessions.get(bookId);
+    if (!session?.lastLocation) return;
+    session.lastLocation = undefined;
+    session.updatedAt = this.now();
+    await this.persist();
+  }
+
+  async addBookmark(bookId: string, location: PageLocator, note?: string) {
     await this.ensureHydrated();
     const session = this.ensureSession(bookId);
-    session.bookmarks.push(location);
+    session.bookmarks.push(normalizeBookmark({ ...location, note, updatedAt: this.now() }));
     this.events.emit("bookmark:added", { bookId, location });
     await this.persist();
   }
 
-  async listBookmarks(bookId: string): Promise<PageLocator[]> {
+  /** Sets or clears (with an empty string) the note of the bookmark at `location`. */
+  async updateBookmarkNote(bookId: string, location: PageLocator, note: string): Promise<boolean> {
+    await this.ensureHydrated();
+    const bookmarks = this.sessions.get(bookId)?.bookmarks ?? [];
+    const index = bookmarks.findIndex(
+      bookmark => bookmark.spineIndex === location.spineIndex && bookmark.offset === location.offset,
+    );
+    if (index < 0) return false;
+    const bookmark = normalizeBookmark({ ...bookmarks[index]!, note, updatedAt: this.now() });
+    bookmarks[index] = bookmark;
+    this.events.emit("bookmark:updated", { bookId, bookmark });
+    await this.persist();
+    return true;
+  }
+
+  async listBookmarks(bookId: string): Promise<Bookmark[]> {
     await this.ensureHydrated();
     const session = this.sessions.get(bookId);
-    return session ? [...session.bookmarks] : [];
+    return session ? session.bookmarks.map(bookmark => ({ ...bookmark })) : [];
   }
 
   snapshot(): StateSnapshot {
@@ -89,7 +184,9 @@ export class InMemoryStateStore {
     for (const [bookId, session] of this.sessions.entries()) {
       output[bookId] = {
         lastLocation: session.lastLocation,
-        bookmarks: [...session.bookmarks],
+        updatedAt: session.updatedAt,
+        lastReadAt: session.lastReadAt,
+        bookmarks: session.bookmarks.map(bookmark => ({ ...bookmark })),
       };
     }
     return output;
@@ -129,12 +226,23 @@ export class InMemoryStateStore {
   }
 }
 
-const DEFAULT_STORAGE_KEY = "bkai.reader.state.v1";
+/** Drops blank or non-string notes so stored bookmarks stay plain locators unless annotated. */
+function normalizeBookmark(bookmark: Bookmark): Bookmark {
+  const note = typeof bookmark.note === "string" ? bookmark.note.trim() : "";
+  const normalized: Bookmark = { spineIndex: bookmark.spineIndex, offset: bookmark.offset };
+  if (note) normalized.note = note;
+  if (typeof bookmark.updatedAt === "number") normalized.updatedAt = bookmark.updatedAt;
+  return normalized;
+}
+
+export const STATE_STORAGE_KEY = "bkai.reader.state.v1";
 
 export interface BrowserStateStoreOptions {
   storageKey?: string;
   preferIndexedDb?: boolean;
   authToken?: string | null;
+  /** Keeps this reader profile's progress apart from the others on the machine. */
+  profile?: string;
 }
 
 export async function createBrowserStateStore(options: BrowserStateStoreOptions = {}) {
@@ -142,7 +250,7 @@ export async function createBrowserStateStore(options: BrowserStateStoreOptions
     return new InMemoryStateStore();
   }
 
-  const storageKey = options.storageKey ?? DEFAULT_STORAGE_KEY;
+  const storageKey = profileKey(options.storageKey ?? STATE_STORAGE_KEY, options.profile ?? "");
   const preferIndexedDb = options.preferIndexedDb ?? true;
   let backend: StateStoreBackend | undefined;
 
@@ -161,7 +269,7 @@ export async function createBrowserStateStore(options: BrowserStateStoreOptions
 
   if (options.authToken) {
     try {
-      const cloudBackend = createCloudBackend({ token: options.authToken });
+      const cloudBackend = createCloudBackend({ token: options.authToken, profile: options.profile });
       backend = createCompositeBackend({ primary: cloudBackend, fallback: localBackend });
     } catch (error) {
       console.warn("[state-store] Failed to initialize cloud backend", error);
@@ -174,6 +282,13 @@ export async function createBrowserStateStore(options: BrowserStateStoreOptions
   return new InMemoryStateStore({ backend });
 }
 
+export * from "./sync";
+export * from "./resume";
+export * from "./settings";
+export * from "./profiles";
+export * from "./clippings";
+export * from "./selections";
+
 export {
   IndexedDbBackend,
   LocalStorageBackend,
@@ -185,4 +300,4 @@ export {
   createCompositeBackend,
 };
 
-export type { StateStoreBackend, PageLocator, ReadingSessionState, StateSnapshot };
+export type { Bookmark, StateStoreBackend, PageLocator, ReadingSessionState, RecentBook, StateSnapshot };
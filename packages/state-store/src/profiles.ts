@@ -0,0 +1,69 @@
+import { browserStorage, type SettingsStorage } from "./settings";
+
+export const DEFAULT_PROFILE = "default";
+export const PROFILES_KEY = "bkai.profiles";
+const MAX_PROFILE_NAME_LENGTH = 32;
+
+export interface ProfileList {
+  active: string;
+  /** Every known profile, `DEFAULT_PROFILE` first. */
+  profiles: string[];
+}
+
+/** Trims and shortens a profile name; a blank name means the default profile. */
+export function normalizeProfileName(name: string | null | undefined): string {
+  const trimmed = (name ?? "").trim().slice(0, MAX_PROFILE_NAME_LENGTH).trim();
+  return trimmed || DEFAULT_PROFILE;
+}
+
+/**
+ * The key `profile` keeps its copy of `baseKey` under. The default profile
+ * uses the bare key, so data saved before profiles existed stays where it is.
+ */
+export function profileKey(baseKey: string, profile: string): string {
+  const name = normalizeProfileName(profile);
+  return name === DEFAULT_PROFILE ? baseKey : `${baseKey}.profile.${encodeURIComponent(name)}`;
+}
+
+export interface ProfileStoreOptions {
+  key?: string;
+  /** Defaults to `window.localStorage` when it is available. */
+  storage?: SettingsStorage;
+}
+
+/** The list of reader profiles on this machine and which one is in use. */
+export class ProfileStore {
+  private readonly key: string;
+  private readonly storage: SettingsStorage | undefined;
+
+  constructor(options: ProfileStoreOptions = {}) {
+    this.key = options.key ?? PROFILES_KEY;
+    this.storage = options.storage ?? browserStorage();
+  }
+
+  load(): ProfileList {
+    let stored: Partial<ProfileList> = {};
+    try {
+      stored = JSON.parse(this.storage?.getItem(this.key) ?? "{}") ?? {};
+    } catch {
+      // An unreadable list only loses the names; each profile's data is stored separately.
+    }
+    const names = Array.isArray(stored.profiles) ? stored.profiles.filter(name => typeof name === "string") : [];
+    const profiles = [...new Set([DEFAULT_PROFILE, ...names.map(normalizeProfileName)])];
+    const active = normalizeProfileName(typeof stored.active === "string" ? stored.active : undefined);
+    return { active: profiles.includes(active) ? active : DEFAULT_PROFILE, profiles };
+  }
+
+  /** Makes `name` the active profile, adding it to the list if it is new. */
+  activate(name: string): ProfileList {
+    const active = normalizeProfileName(name);
+    const { profiles } = this.load();
+    const list = { active, profiles: profiles.includes(active) ? profiles : [...profiles, active] };
+    try {
+      this.storage?.setItem(this.key, JSON.stringify(list));
+    } catch (error) {
+      console.warn("[state-store] profile list save failed", error);
+    }
+    return list;
+  }
+}
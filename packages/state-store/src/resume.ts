@@ -0,0 +1,41 @@
+import type { PageLocator } from "./types";
+
+export type ResumeChoice = "resume" | "startOver" | "timeout";
+
+export interface ResumeDecision {
+  location: PageLocator;
+  /** Whether the saved progress should be discarded. */
+  clearSaved: boolean;
+}
+
+/** How long the resume prompt stays up before it settles on resuming. */
+export const RESUME_PROMPT_TIMEOUT_MS = 8000;
+
+const START: PageLocator = { spineIndex: 0, offset: 0 };
+
+/** Only progress past the first chapter is worth asking about. */
+export function shouldOfferResume(saved: PageLocator | undefined): saved is PageLocator {
+  return saved !== undefined && saved.spineIndex > 0;
+}
+
+export function decideResume(saved: PageLocator, choice: ResumeChoice): ResumeDecision {
+  if (choice === "startOver") {
+    return { location: START, clearSaved: true };
+  }
+  return { location: saved, clearSaved: false };
+}
+
+export interface ReadingPosition {
+  chapter: number;
+  page: number;
+}
+
+/**
+ * Where to continue after the open book is re-read from disk: the same chapter
+ * when it still exists, otherwise the start of the last one.
+ */
+export function positionAfterReload(position: ReadingPosition, chapterCount: number): ReadingPosition {
+  if (chapterCount <= 0) return { chapter: 0, page: 0 };
+  if (position.chapter >= 0 && position.chapter < chapterCount) return position;
+  return { chapter: Math.min(Math.max(position.chapter, 0), chapterCount - 1), page: 0 };
+}
@@ -0,0 +1,64 @@
+import { profileKey } from "./profiles";
+import { browserStorage, type SettingsStorage } from "./settings";
+
+export const SELECTIONS_KEY = "bkai.selections";
+
+/** A selected passage, found again by its text and which occurrence of it on the page it was. */
+export interface SavedSelection {
+  text: string;
+  occurrence: number;
+  page: number;
+}
+
+export interface SelectionStoreOptions {
+  key?: string;
+  /** Defaults to `window.localStorage` when it is available. */
+  storage?: SettingsStorage;
+  profile?: string;
+}
+
+function isSavedSelection(value: unknown): value is SavedSelection {
+  const { text, occurrence, page } = (value ?? {}) as Record<string, unknown>;
+  return typeof text === "string" && text.length > 0 && Number.isInteger(occurrence) && Number.isInteger(page);
+}
+
+/** The last passage selected in each chapter of a book, so returning to the chapter selects it again. */
+export class SelectionStore {
+  private readonly key: string;
+  private readonly storage: SettingsStorage | undefined;
+
+  constructor(options: SelectionStoreOptions = {}) {
+    this.key = profileKey(options.key ?? SELECTIONS_KEY, options.profile ?? "");
+    this.storage = options.storage ?? browserStorage();
+  }
+
+  load(bookId: string, chapter: number): SavedSelection | undefined {
+    const saved = this.read(bookId)[chapter];
+    return isSavedSelection(saved) ? saved : undefined;
+  }
+
+  /** Replaces the chapter's selection; `null` forgets it. */
+  save(bookId: string, chapter: number, selection: SavedSelection | null) {
+    const selections = this.read(bookId);
+    if (selection) selections[chapter] = selection;
+    else delete selections[chapter];
+    try {
+      this.storage?.setItem(this.bookKey(bookId), JSON.stringify(selections));
+    } catch (error) {
+      console.warn("[state-store] selection save failed", error);
+    }
+  }
+
+  private read(bookId: string): Record<number, unknown> {
+    try {
+      const parsed: unknown = JSON.parse(this.storage?.getItem(this.bookKey(bookId)) ?? "{}");
+      return parsed && typeof parsed === "object" && !Array.isArray(parsed) ? (parsed as Record<number, unknown>) : {};
+    } catch {
+      return {};
+    }
+  }
+
+  private bookKey(bookId: string): string {
+    return `${this.key}.${encodeURIComponent(bookId)}`;
+  }
+}
@@ -0,0 +1,74 @@
+export type SettingsStorage = Pick<Storage, "getItem" | "setItem">;
+
+export interface SettingsStoreOptions<T> {
+  key: string;
+  defaults: T;
+  /** Defaults to `window.localStorage` when it is available. */
+  storage?: SettingsStorage;
+}
+
+/** Appended to the key a malformed value is copied to before defaults take over. */
+export const CORRUPT_SETTINGS_SUFFIX = ".corrupt";
+
+export function browserStorage(): SettingsStorage | undefined {
+  try {
+    return typeof window === "undefined" ? undefined : window.localStorage;
+  } catch {
+    // Sandboxed frames throw on merely reading localStorage.
+    return undefined;
+  }
+}
+
+/**
+ * Loads and saves a flat settings object. Loading never throws: a missing value
+ * gives the defaults, a value that isn't a JSON object is backed up under
+ * `key + CORRUPT_SETTINGS_SUFFIX` and replaced by the defaults, and fields that
+ * are unknown or of the wrong type fall back individually.
+ */
+export class SettingsStore<T extends object> {
+  private readonly key: string;
+  private readonly defaults: T;
+  private readonly storage: SettingsStorage | undefined;
+
+  constructor(options: SettingsStoreOptions<T>) {
+    this.key = options.key;
+    this.defaults = options.defaults;
+    this.storage = options.storage ?? browserStorage();
+  }
+
+  load(): T {
+    const raw = this.storage?.getItem(this.key);
+    if (raw == null) return { ...this.defaults };
+
+    let stored: unknown;
+    try {
+      stored = JSON.parse(raw);
+    } catch {
+      stored = undefined;
+    }
+    if (!stored || typeof stored !== "object" || Array.isArray(stored)) {
+      console.warn(`[state-store] settings "${this.key}" are malformed; backed up and reset to defaults`);
+      this.write(this.key + CORRUPT_SETTINGS_SUFFIX, raw);
+      return { ...this.defaults };
+    }
+
+    const settings: Record<string, unknown> = { ...this.defaults };
+    for (const [field, fallback] of Object.entries(this.defaults)) {
+      const value = (stored as Record<string, unknown>)[field];
+      if (typeof value === typeof fallback) settings[field] = value;
+    }
+    return settings as T;
+  }
+
+  save(settings: T) {
+    this.write(this.key, JSON.stringify(settings));
+  }
+
+  private write(key: string, value: string) {
+    try {
+      this.storage?.setItem(key, value);
+    } catch (error) {
+      console.warn("[state-store] settings save failed", error);
+    }
+  }
+}
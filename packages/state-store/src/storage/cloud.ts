@@ -1,17 +1,22 @@
+import { DEFAULT_PROFILE, normalizeProfileName } from "../profiles";
 import type { StateSnapshot, StateStoreBackend } from "../types";
 
 export interface CloudBackendOptions {
   token: string;
   baseUrl?: string;
+  /** Reader profile whose state the server keeps; the default profile uses the plain document. */
+  profile?: string;
 }
 
 export class CloudBackend implements StateStoreBackend {
   private readonly token: string;
   private readonly baseUrl: string;
+  private readonly profile: string;
 
   constructor(options: CloudBackendOptions) {
     this.token = options.token;
     this.baseUrl = options.baseUrl ?? "";
+    this.profile = normalizeProfileName(options.profile);
   }
 
   async load(): Promise<StateSnapshot | undefined> {
@@ -66,8 +71,9 @@ export class CloudBackend implements StateStoreBackend {
   }
 
   private resolve(path: string) {
-    if (!this.baseUrl) return path;
-    return `${this.baseUrl.replace(/\/$/, "")}${path}`;
+    const query = this.profile === DEFAULT_PROFILE ? "" : `?profile=${encodeURIComponent(this.profile)}`;
+    if (!this.baseUrl) return `${path}${query}`;
+    return `${this.baseUrl.replace(/\/$/, "")}${path}${query}`;
   }
 
   private headers(): HeadersInit {
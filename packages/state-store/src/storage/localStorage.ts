@@ -1,24 +1,30 @@
+import { browserStorage, type SettingsStorage } from "../settings";
 import { StateSnapshot, StateStoreBackend } from "../types";
 
 export interface LocalStorageOptions {
   storageKey?: string;
+  /** Defaults to `window.localStorage`; the CLI passes a file-backed storage. */
+  storage?: SettingsStorage;
 }
 
 const DEFAULT_STORAGE_KEY = "bkai.reader.state.v1";
 
 export class LocalStorageBackend implements StateStoreBackend {
   private readonly storageKey: string;
+  private readonly storage?: SettingsStorage;
 
   constructor(options: LocalStorageOptions = {}) {
     this.storageKey = options.storageKey ?? DEFAULT_STORAGE_KEY;
+    this.storage = options.storage;
   }
 
   async load(): Promise<StateSnapshot | undefined> {
-    if (typeof window === "undefined") {
+    const storage = this.storage ?? browserStorage();
+    if (!storage) {
       return undefined;
     }
     try {
-      const raw = window.localStorage.getItem(this.storageKey);
+      const raw = storage.getItem(this.storageKey);
       if (!raw) return undefined;
       const parsed = JSON.parse(raw) as StateSnapshot;
       if (parsed && typeof parsed === "object") {
@@ -31,12 +37,13 @@ export class LocalStorageBackend implements StateStoreBackend {
   }
 
   async save(snapshot: StateSnapshot): Promise<void> {
-    if (typeof window === "undefined") {
+    const storage = this.storage ?? browserStorage();
+    if (!storage) {
       return;
     }
     try {
       const serialized = JSON.stringify(snapshot);
-      window.localStorage.setItem(this.storageKey, serialized);
+      storage.setItem(this.storageKey, serialized);
     } catch (error) {
       console.warn("[state-store] localStorage save failed", error);
     }
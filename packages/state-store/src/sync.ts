@@ -0,0 +1,97 @@
+import type { PageLocator, StateSnapshot } from "./types";
+
+/**
+ * Version of the portable progress file. Readers must ignore files with a
+ * version they do not understand rather than guessing at their shape.
+ */
+export const SYNC_FILE_VERSION = 1;
+
+export interface SyncEntry extends PageLocator {
+  /** Milliseconds since the Unix epoch of the last progress change. */
+  updatedAt: number;
+}
+
+export interface SyncFile {
+  version: number;
+  books: Record<string, SyncEntry>;
+}
+
+export function createSyncFile(snapshot: StateSnapshot): SyncFile {
+  const books: Record<string, SyncEntry> = {};
+  for (const [bookId, data] of Object.entries(snapshot)) {
+    if (!data.lastLocation) continue;
+    books[bookId] = {
+      spineIndex: data.lastLocation.spineIndex,
+      offset: data.lastLocation.offset,
+      updatedAt: data.updatedAt ?? 0,
+    };
+  }
+  return { version: SYNC_FILE_VERSION, books };
+}
+
+export function parseSyncFile(input: unknown): SyncFile | null {
+  if (!input || typeof input !== "object") return null;
+  const candidate = input as Partial<SyncFile>;
+  if (candidate.version !== SYNC_FILE_VERSION) return null;
+  if (!candidate.books || typeof candidate.books !== "object") return null;
+
+  const books: Record<string, SyncEntry> = {};
+  for (const [bookId, entry] of Object.entries(candidate.books)) {
+    if (!entry || typeof entry !== "object") continue;
+    const { spineIndex, offset, updatedAt } = entry as Partial<SyncEntry>;
+    if (typeof spineIndex !== "number" || typeof offset !== "number" || typeof updatedAt !== "number") {
+      continue;
+    }
+    books[bookId] = { spineIndex, offset, updatedAt };
+  }
+  return { version: SYNC_FILE_VERSION, books };
+}
+
+/** Last writer wins per book: the entry with the newer `updatedAt` is kept. */
+export function mergeSyncFiles(base: SyncFile, incoming: SyncFile): SyncFile {
+  const books: Record<string, SyncEntry> = { ...base.books };
+  for (const [bookId, entry] of Object.entries(incoming.books)) {
+    const existing = books[bookId];
+    if (!existing || entry.updatedAt > existing.updatedAt) {
+      books[bookId] = entry;
+    }
+  }
+  return { version: SYNC_FILE_VERSION, books };
+}
+
+/**
+ * Merges two full snapshots: progress follows the newer `updatedAt`, bookmarks
+ * from both sides are kept. When both sides have the same bookmark, the copy
+ * changed last wins, so edited and cleared notes carry over.
+ */
+export function mergeSnapshots(base: StateSnapshot, incoming: StateSnapshot): StateSnapshot {
+  const merged: StateSnapshot = { ...base };
+  for (const [bookId, data] of Object.entries(incoming)) {
+    const existing = merged[bookId];
+    if (!existing) {
+      merged[bookId] = data;
+      continue;
+    }
+
+    const incomingIsNewer = (data.updatedAt ?? 0) >= (existing.updatedAt ?? 0);
+    const bookmarks = [...existing.bookmarks];
+    for (const bookmark of data.bookmarks ?? []) {
+      const index = bookmarks.findIndex(
+        item => item.spineIndex === bookmark.spineIndex && item.offset === bookmark.offset,
+      );
+      if (index < 0) {
+        bookmarks.push(bookmark);
+      } else if ((bookmark.updatedAt ?? 0) >= (bookmarks[index]!.updatedAt ?? 0)) {
+        bookmarks[index] = bookmark;
+      }
+    }
+
+    const lastReadAt = Math.max(existing.lastReadAt ?? 0, data.lastReadAt ?? 0);
+    merged[bookId] = {
+      ...(incomingIsNewer ? data : existing),
+      lastReadAt: lastReadAt > 0 ? lastReadAt : undefined,
+      bookmarks,
+    };
+  }
+  return merged;
+}
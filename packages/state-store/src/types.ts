@@ -3,10 +3,21 @@ export interface PageLocator {
   offset: number;
 }
 
+export interface Bookmark extends PageLocator {
+  /** Short note the reader attached to the bookmark. */
+  note?: string;
+  /** Milliseconds since the Unix epoch the bookmark or its note last changed. */
+  updatedAt?: number;
+}
+
 export interface ReadingSessionState {
   bookId: string;
   lastLocation?: PageLocator;
-  bookmarks: PageLocator[];
+  /** Milliseconds since the Unix epoch of the last progress change. */
+  updatedAt?: number;
+  /** Milliseconds since the Unix epoch the book was last read, used to order recents. */
+  lastReadAt?: number;
+  bookmarks: Bookmark[];
 }
 
 export type StateSnapshot = Record<string, Omit<ReadingSessionState, "bookId">>;
@@ -15,3 +26,8 @@ export interface StateStoreBackend {
   load(): Promise<StateSnapshot | undefined>;
   save(snapshot: StateSnapshot): Promise<void>;
 }
+
+export interface RecentBook {
+  bookId: string;
+  lastReadAt: number;
+}
@@ -1,7 +1,142 @@
-import { useCallback, useEffect, useMemo, useRef, useState } from "react";
+import { useCallback, useEffect, useMemo, useReducer, useRef, useState } from "react";
 import "./index.css";
-import { createPaginationSession, PageView, PaginationSession } from "@render-engine";
-import { createBrowserStateStore, InMemoryStateStore } from "@state-store";
+import {
+  adjacentChapter,
+  applyHeadingNumbers,
+  applyEpubTypeRoles,
+  applyImageAltFallback,
+  BUILT_IN_THEMES,
+  buildThemeCss,
+  chapterTransitionsEnabled,
+  ChapterCache,
+  buildDebugOutlineCss,
+  buildSyntheticPages,
+  chapterWindow,
+  collectFigures,
+  clusterMarkers,
+  generateCoverDataUrl,
+  buildTypographyCss,
+  createPaginationSession,
+  DEFAULT_PREFS,
+  deriveChapterTitle,
+  disambiguateTocLabels,
+  extractHeadings,
+  findActiveHeading,
+  findAdjacentHeading,
+  findChapterIndex,
+  findPageWithHeading,
+  findPageWithLink,
+  findRunningHeads,
+  stripRunningHeads,
+  stepLinkFocus,
+  findTheme,
+  htmlToPlainText,
+  isBlankChapter,
+  nonLinearChapters,
+  resolveTextDirection,
+  accumulateOverscroll,
+  NO_OVERSCROLL,
+  IMAGE_SAMPLE_SIZE,
+  INVERTED_IMAGE_CLASS,
+  isDarkTheme,
+  isLikelyDiagram,
+  countWords,
+  DEFAULT_WORDS_PER_MINUTE,
+  estimateReadingMinutes,
+  recordReadingSample,
+  LIGHTBOX_CLOSED,
+  type LightboxAction,
+  type LightboxImage,
+  lightboxReducer,
+  markEpigraph,
+  type LightboxState,
+  type LinkFocusWrap,
+  type NavOrder,
+  PageView,
+  pageAtFraction,
+  parseThemeJson,
+  type ReaderTheme,
+  themeToJson,
+  resolveSourceColor,
+  PaginationSession,
+  ReadingPrefs,
+  renderPlainTextParagraphs,
+  resolvePageLayout,
+  type PageLayout,
+  SPREAD_CSS,
+  spreadLabel,
+  spreadMarkup,
+  spreadPages,
+  adjacentSpreadPage,
+  resolveBookHref,
+  resolveHrefTarget,
+  decodeFragment,
+  findOccurrence,
+  findPrintPage,
+  isIndexChapter,
+  linkIndexEntries,
+  occurrenceAt,
+  printPageAt,
+  type PrintPageTarget,
+  type TextSelection,
+  mergeShortChapters,
+  motionAllowed,
+  numberBookHeadings,
+  searchChapters,
+  DebouncedSearch,
+  type HighlightRange,
+  searchNotes,
+  searchSnippet,
+  type SearchHit,
+  svgToDataUrl,
+  syntheticPageAt,
+  tocChapterOrder,
+  toSemanticHtml,
+  wheelZoomFactor,
+} from "@render-engine";
+import {
+  createBrowserStateStore,
+  createSyncFile,
+  decideResume,
+  DEFAULT_PROFILE,
+  InMemoryStateStore,
+  parseSyncFile,
+  positionAfterReload,
+  ProfileStore,
+  ClippingsStore,
+  SelectionStore,
+  profileKey,
+  RESUME_PROMPT_TIMEOUT_MS,
+  SettingsStore,
+  shouldOfferResume,
+  type Bookmark,
+  type PageLocator,
+  type ResumeChoice,
+} from "@state-store";
+import {
+  type Command,
+  CommandRegistry,
+  classifyClipboardText,
+  createKeyMap,
+  createWebSpeechEngine,
+  filterCommands,
+  isAppError,
+  matchKeyAction,
+  type KeyAction,
+  type KeyMap,
+  type KeyMapConfig,
+  ReadAloudController,
+  type ReadAloudPosition,
+  type ReadAloudState,
+  siblingBookPath,
+  type SpacingLevel,
+} from "@core-platform";
+import { CITATION_STYLES, composeQuote, formatAttribution, toBibtex, type CitationStyle } from "@epub-parser/citation";
+import { hasEpubType, listImageSources, NOTE_TYPES } from "@epub-parser/content";
+import { formatPublicationDate, formatSeriesIndex } from "@epub-parser/metadataFormat";
+import { writeEpub } from "@epub-parser/writer";
+import { LibraryView, relativePath } from "./LibraryView";
+import { formatRelativeTime } from "./lib/utils";
 
 interface TocItem {
   id: string;
@@ -16,27 +151,55 @@ interface ChapterPayload {
   href: string;
   mediaType: string;
   content: string;
+  /** Set when short spine documents were folded into this chapter. */
+  mergedHrefs?: string[];
+  /** False for `linear="no"` spine items, which open from links rather than in the reading order. */
+  linear?: boolean;
+  /** Placeholder for a spine document missing from the archive. */
+  missing?: boolean;
+  /** `epub:type` of the chapter root, e.g. `bodymatter chapter`. */
+  semanticType?: string;
+  matter?: "front" | "body" | "back";
+  /** Language declared by the chapter itself; falls back to the book language. */
+  language?: string;
   styles: string[];
 }
 
 interface BookMetadata {
   title?: string;
+  subtitle?: string;
+  alternateTitles?: string[];
   creator?: string;
+  authors?: string[];
   language?: string;
   publisher?: string;
   description?: string;
-  [key: string]: string | undefined;
+  identifier?: string;
+  date?: string;
+  rights?: string;
+  series?: string;
+  seriesIndex?: string;
+  [key: string]: string | string[] | undefined;
 }
 
 interface BookPayload {
   id: string;
   metadata: BookMetadata;
+  /** Embedded cover as a data URL; null when the book declares none. */
+  cover?: string | null;
+  /** Spine idrefs whose documents are missing; they appear as placeholder chapters. */
+  missingChapters?: string[];
+  pageProgression?: "ltr" | "rtl";
   toc: TocItem[];
+  /** Print page numbers from the book's page list; empty when it has none. */
+  pageMap?: PrintPageTarget[];
   chapters: ChapterPayload[];
 }
 
 interface LoadedBook extends BookPayload {
   sourceName: string;
+  /** The file the book was opened from, kept so it can be re-read after an edit. */
+  sourceFile?: File;
   lastUpdated: number;
 }
 
@@ -45,6 +208,56 @@ interface ShadowPageProps {
   styles: string[];
   className?: string;
   inlineStyle?: string;
+  lang?: string;
+  dir?: "ltr" | "rtl";
+  /** When set, book-authored colours that are illegible on this background are reset. */
+  sourceColorBackground?: string;
+  onActiveHeadingChange?: (heading: string | null) => void;
+  keyMap: KeyMap;
+  /** Spine position of the content; a change triggers the chapter transition. */
+  chapterIndex?: number;
+  animateTransitions?: boolean;
+  /** Tab past the last link (or Shift+Tab before the first) goes to `onAction` instead of leaving the reader. */
+  wrapLinkFocus?: boolean;
+  /** Key actions other than scrolling, received while the content has focus. */
+  onAction?: (action: Exclude<KeyAction, "scrollUp" | "scrollDown" | "pageUp" | "pageDown">) => void;
+  /** Pixels per second to scroll on its own; null or undefined leaves scrolling to the reader. */
+  autoScrollSpeed?: number | null;
+  /** Auto-scroll hit the bottom of the content. */
+  onAutoScrollEnd?: () => void;
+  /** The reader scrolled or pressed a key while auto-scrolling. */
+  onAutoScrollInterrupt?: () => void;
+  /** Mouse back (-1) / forward (1) side buttons pressed over the content; unset leaves them to the browser. */
+  onSideButton?: (direction: 1 | -1) => void;
+  /** Position to bring into view once the content has rendered. */
+  scrollTarget?: ScrollTarget | null;
+  onScrollTargetReached?: () => void;
+  /** Clicked links to another place in the book (`data-book-href`, e.g. index page numbers) are followed here. */
+  onBookLink?: (href: string) => void;
+  /** Markup to show in a popover for a link to another document, e.g. a non-linear note; null lets the link be. */
+  linkedNote?: (href: string) => string | null;
+  /** Passage to select once the page has rendered. */
+  restoreSelection?: TextSelection | null;
+  /** A passage was selected in the content; unset leaves selections unreported. */
+  onSelect?: (selection: TextSelection) => void;
+  /** Clicked images (and inline SVG) are reported here instead of doing nothing; unset leaves them inert. */
+  onImageClick?: (image: LightboxImage) => void;
+  /** Invert images that look like diagrams, for dark themes. */
+  invertDiagrams?: boolean;
+  /** The wheel kept pushing past the bottom (1) or top (-1) edge; unset leaves the edges inert. */
+  onEdgeOverscroll?: (direction: 1 | -1) => void;
+}
+
+/** An element id, the first/last heading of the rendered page, or its first/last link to focus. */
+type ScrollTarget = { anchor: string } | { heading: "first" | "last" } | { link: "first" | "last" };
+
+interface PreparedChapter {
+  inlineStyles: string[];
+  contentMarkup: string;
+  externalStyles: string[];
+  bodyClassName: string;
+  bodyInlineStyle: string;
+  session: PaginationSession | null;
 }
 
 interface UserProfile {
@@ -60,9 +273,196 @@ type AuthState =
   | { status: "error"; message: string };
 
 const AUTH_TOKEN_KEY = "bkai.auth.token";
+const CUSTOM_THEMES_KEY = "bkai.themes.custom";
+const KEY_MAP_KEY = "bkai.keymap";
+const PLAIN_TEXT_BOOKS_KEY = "bkai.plainTextBooks";
+const PREFS_KEY = "bkai.prefs";
+const FALLBACK_LINE_HEIGHT = 24;
+const CHAPTER_TRANSITION_MS = 150;
+// Applied to the app root when motion is off; the content's own transition is skipped in ShadowPage.
+const REDUCED_MOTION_CSS = `
+  [data-reduce-motion] *,
+  [data-reduce-motion] *::before,
+  [data-reduce-motion] *::after {
+    transition: none !important;
+    animation: none !important;
+    scroll-behavior: auto !important;
+  }
+`;
+// E-paper renders the chrome's greys, translucent overlays and anti-aliasing as smears, so under an
+// e-ink theme every fill becomes white, every line black, and the current item is shown inverted.
+const E_INK_CHROME_CSS = `
+  [data-eink],
+  [data-eink] [class*="bg-"] {
+    background-color: #ffffff !important;
+    background-image: none !important;
+  }
+  [data-eink],
+  [data-eink] * {
+    color: #000000 !important;
+    box-shadow: none !important;
+    text-shadow: none !important;
+    backdrop-filter: none !important;
+    -webkit-font-smoothing: none;
+    font-smooth: never;
+  }
+  [data-eink] [class*="border"]:not(.border-transparent) {
+    border-color: #000000 !important;
+  }
+  [data-eink] :is(button, input, select, textarea, [role="dialog"]) {
+    border: 2px solid #000000 !important;
+  }
+  [data-eink] :disabled {
+    opacity: 1 !important;
+    border-style: dashed !important;
+  }
+  [data-eink] :is([aria-current], [aria-pressed="true"]) {
+    background-color: #000000 !important;
+  }
+  [data-eink] :is([aria-current], [aria-pressed="true"]),
+  [data-eink] :is([aria-current], [aria-pressed="true"]) * {
+    color: #ffffff !important;
+  }
+  [data-eink] .reader-dim-overlay {
+    background-color: transparent !important;
+  }
+  [data-eink] :focus-visible {
+    outline: 3px solid #000000 !important;
+    outline-offset: 2px;
+  }
+`;
+const MERGE_SHORT_CHAPTER_OPTIONS = [0, 50, 100, 200];
+// At least the current chapter plus the neighbours prefetched around it.
+const CHAPTER_CACHE_SIZE_OPTIONS = [4, 8, 16, 32];
+const CHARS_PER_PAGE_OPTIONS = [1000, 1800, 2500];
+const CITATION_STYLE_LABELS: Record<CitationStyle, string> = { apa: "APA", mla: "MLA", chicago: "芝加哥" };
+const FIRST_LINE_INDENT_OPTIONS = [0, 1, 2];
+const CHAPTER_TRANSITION_OFFSET = 24;
+const AUTO_SCROLL_DEFAULT_SPEED = 40;
+const AUTO_SCROLL_SPEED_STEP = 10;
+const AUTO_SCROLL_MIN_SPEED = 10;
+const AUTO_SCROLL_MAX_SPEED = 400;
+const COMMAND_PALETTE_LIMIT = 50;
+const NOTICE_DURATION_MS = 2500;
+// Startup flag (`?eager`): build every chapter and decode every image when a book opens, for kiosks.
+const EAGER_LOAD = typeof window !== "undefined" && new URLSearchParams(window.location.search).has("eager");
+
+/** The selection's first range; Chromium exposes ranges inside a shadow tree only through its root. */
+function selectionRange(shadow: ShadowRoot): Range | null {
+  const root = shadow as ShadowRoot & { getSelection?: () => Selection | null };
+  const selection = root.getSelection?.() ?? window.getSelection();
+  return selection && selection.rangeCount > 0 ? selection.getRangeAt(0) : null;
+}
+
+/** A range over the `occurrence`-th `text` in the element's text, or null when it is not there. */
+function rangeOfText(root: Element, { text, occurrence }: TextSelection): Range | null {
+  const start = findOccurrence(root.textContent ?? "", text, occurrence);
+  if (start < 0) return null;
+  const end = start + text.length;
+  const range = document.createRange();
+  const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT);
+  let offset = 0;
+  for (let node = walker.nextNode(); node; node = walker.nextNode()) {
+    const length = node.textContent?.length ?? 0;
+    if (start >= offset && start < offset + length) range.setStart(node, start - offset);
+    if (end > offset && end <= offset + length) {
+      range.setEnd(node, end - offset);
+      return range;
+    }
+    offset += length;
+  }
+  return null;
+}
+
+function visibleLinks(root: ShadowRoot | null | undefined): HTMLElement[] {
+  const links = Array.from(root?.querySelectorAll<HTMLElement>("a[href]") ?? []);
+  // Links inside hidden footnotes are left out, since they cannot take focus.
+  return links.filter(link => link.getClientRects().length > 0);
+}
+
+function focusLink(link: HTMLElement) {
+  link.focus({ preventScroll: true });
+  link.scrollIntoView({ block: "nearest" });
+}
+
+// Cross-origin images taint the canvas and come back empty, which leaves them uninverted.
+function sampleImagePixels(image: HTMLImageElement): Uint8ClampedArray {
+  const canvas = document.createElement("canvas");
+  canvas.width = IMAGE_SAMPLE_SIZE;
+  canvas.height = IMAGE_SAMPLE_SIZE;
+  const context = canvas.getContext("2d");
+  if (!context || !image.naturalWidth) return new Uint8ClampedArray();
+  try {
+    context.drawImage(image, 0, 0, IMAGE_SAMPLE_SIZE, IMAGE_SAMPLE_SIZE);
+    return context.getImageData(0, 0, IMAGE_SAMPLE_SIZE, IMAGE_SAMPLE_SIZE).data;
+  } catch {
+    return new Uint8ClampedArray();
+  }
+}
 
-function ShadowPage({ html, styles, className, inlineStyle }: ShadowPageProps) {
+function ShadowPage({
+  html,
+  styles,
+  className,
+  inlineStyle,
+  lang,
+  dir,
+  sourceColorBackground,
+  onActiveHeadingChange,
+  keyMap,
+  chapterIndex,
+  animateTransitions = false,
+  wrapLinkFocus = false,
+  onAction,
+  autoScrollSpeed,
+  onAutoScrollEnd,
+  onAutoScrollInterrupt,
+  scrollTarget,
+  onScrollTargetReached,
+  onSideButton,
+  onBookLink,
+  linkedNote,
+  restoreSelection,
+  onSelect,
+  onImageClick,
+  invertDiagrams = false,
+  onEdgeOverscroll,
+}: ShadowPageProps) {
   const hostRef = useRef<HTMLDivElement | null>(null);
+  const previousHtmlRef = useRef(html);
+  const autoScrollSpeedRef = useRef(autoScrollSpeed);
+  autoScrollSpeedRef.current = autoScrollSpeed;
+  const onAutoScrollEndRef = useRef(onAutoScrollEnd);
+  onAutoScrollEndRef.current = onAutoScrollEnd;
+  const onScrollTargetReachedRef = useRef(onScrollTargetReached);
+  onScrollTargetReachedRef.current = onScrollTargetReached;
+  const onImageClickRef = useRef(onImageClick);
+  onImageClickRef.current = onImageClick;
+  const onBookLinkRef = useRef(onBookLink);
+  onBookLinkRef.current = onBookLink;
+  const linkedNoteRef = useRef(linkedNote);
+  linkedNoteRef.current = linkedNote;
+  const onSelectRef = useRef(onSelect);
+  onSelectRef.current = onSelect;
+  const overscrollRef = useRef(NO_OVERSCROLL);
+  const edgeTurnRef = useRef<1 | -1 | null>(null);
+  const imagesZoomable = Boolean(onImageClick);
+  const previousChapterRef = useRef(chapterIndex);
+  const transitionRef = useRef<Animation | null>(null);
+  const headingElementsRef = useRef<HTMLElement[]>([]);
+  const onActiveHeadingChangeRef = useRef(onActiveHeadingChange);
+  onActiveHeadingChangeRef.current = onActiveHeadingChange;
+
+  const reportActiveHeading = useCallback(() => {
+    const host = hostRef.current;
+    if (!host) return;
+    const hostTop = host.getBoundingClientRect().top;
+    const markers = headingElementsRef.current.map(element => ({
+      offset: element.getBoundingClientRect().top - hostTop + host.scrollTop,
+      text: element.textContent?.trim() ?? "",
+    }));
+    onActiveHeadingChangeRef.current?.(findActiveHeading(markers, host.scrollTop + 8)?.text || null);
+  }, []);
 
   useEffect(() => {
     const host = hostRef.current;
@@ -96,16 +496,121 @@ function ShadowPage({ html, styles, className, inlineStyle }: ShadowPageProps) {
         margin: 1.5rem 0 1rem;
         font-weight: 600;
       }
+      .reader-scope .reader-epigraph {
+        margin: 0 0 0.5rem 20%;
+        font-style: italic;
+      }
+      .reader-scope .reader-epigraph-attribution {
+        margin: 0 0 2rem 20%;
+        text-align: right;
+        font-style: normal;
+      }
+      .reader-scope .reader-epigraph .reader-epigraph-attribution {
+        margin: 0.5rem 0 0;
+      }
+      .reader-scope .reader-epigraph + :not(.reader-epigraph-attribution) {
+        margin-top: 2rem;
+      }
+      .reader-scope [data-heading-number]::before {
+        content: attr(data-heading-number) "\\00a0\\00a0";
+        color: var(--reader-muted, inherit);
+        font-variant-numeric: tabular-nums;
+      }
       .reader-scope img {
         max-width: 100%;
         height: auto;
         display: block;
         margin: 1.5rem auto;
       }
+      .reader-scope .reader-image-alt {
+        display: block;
+        margin: 1.5rem auto;
+        padding: 0.75rem 1rem;
+        border: 1px dashed var(--reader-muted, currentColor);
+        color: var(--reader-muted, inherit);
+        font-style: italic;
+        text-align: center;
+      }
+      .reader-scope figure {
+        margin: 1.5rem auto;
+        break-inside: avoid;
+        text-align: center;
+      }
+      .reader-scope figure img {
+        margin: 0 auto 0.5rem;
+      }
+      .reader-scope figcaption {
+        font-size: 0.875em;
+        line-height: 1.5;
+        text-align: center;
+        color: var(--reader-muted, inherit);
+      }
       .reader-scope a {
         color: inherit;
         text-decoration: underline;
       }
+      .reader-scope a:focus-visible {
+        outline: 2px solid currentColor;
+        outline-offset: 2px;
+        border-radius: 2px;
+      }
+      .reader-scope abbr[title] {
+        text-decoration: underline dotted;
+        cursor: help;
+      }
+      .reader-scope {
+        position: relative;
+      }
+      .reader-scope aside:is([epub\\:type~="footnote"], [epub\\:type~="note"]) {
+        display: none;
+      }
+      .reader-scope .reader-note-popover {
+        position: absolute;
+        z-index: 10;
+        max-width: min(24rem, 90%);
+        padding: 0.75rem 1rem;
+        border-radius: 0.5rem;
+        font-size: 0.875em;
+        line-height: 1.5;
+        color: var(--reader-text, #0f172a);
+        background: var(--reader-bg, #f8fafc);
+        border: 1px solid color-mix(in srgb, var(--reader-text, #0f172a) 20%, transparent);
+        box-shadow: 0 8px 24px rgb(0 0 0 / 0.2);
+      }
+      .reader-scope .reader-note-popover > :last-child {
+        margin-bottom: 0;
+      }
+      .reader-images-zoomable :is(img, svg) {
+        cursor: zoom-in;
+      }
+      .reader-scope code,
+      .reader-scope kbd,
+      .reader-scope samp {
+        font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace;
+        font-size: 0.9em;
+        padding: 0.1em 0.3em;
+        border-radius: 0.25rem;
+        background: color-mix(in srgb, var(--reader-text, #0f172a) 8%, transparent);
+      }
+      .reader-scope pre {
+        font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace;
+        white-space: pre-wrap;
+      }
+      .reader-scope pre code {
+        padding: 0;
+        background: none;
+      }
+      .reader-scope ruby {
+        ruby-position: over;
+      }
+      .reader-scope rt {
+        font-size: 0.5em;
+        line-height: 1;
+        color: var(--reader-muted, inherit);
+      }
+      .reader-scope img.${INVERTED_IMAGE_CLASS} {
+        filter: invert(1) hue-rotate(180deg);
+      }
     `;
     shadow.appendChild(baseStyle);
 
@@ -117,26 +622,575 @@ function ShadowPage({ html, styles, className, inlineStyle }: ShadowPageProps) {
     });
 
     const container = document.createElement("div");
-    container.className = ["reader-scope", className].filter(Boolean).join(" ");
+    container.className = ["reader-scope", imagesZoomable && "reader-images-zoomable", className]
+      .filter(Boolean)
+      .join(" ");
     if (inlineStyle && inlineStyle.trim().length > 0) {
       container.setAttribute("style", inlineStyle);
     }
+    if (lang) {
+      container.setAttribute("lang", lang);
+    }
+    if (dir) {
+      container.setAttribute("dir", dir);
+    }
     container.innerHTML = html;
     shadow.appendChild(container);
 
+    // Note references open their footnote in a popover next to the link instead of jumping to it.
+    container.addEventListener("click", event => {
+      container.querySelector(".reader-note-popover")?.remove();
+      const image = (event.target as Element).closest?.("img, svg");
+      if (image && onImageClickRef.current && !image.closest("a")) {
+        const isImg = image instanceof HTMLImageElement;
+        onImageClickRef.current({
+          src: isImg ? image.currentSrc || image.src : svgToDataUrl(new XMLSerializer().serializeToString(image)),
+          alt: isImg ? image.alt : image.getAttribute("aria-label") ?? image.querySelector("title")?.textContent ?? "",
+        });
+        return;
+      }
+      const bookLink = (event.target as Element).closest?.("a[data-book-href]");
+      if (bookLink && onBookLinkRef.current) {
+        event.preventDefault();
+        onBookLinkRef.current(bookLink.getAttribute("data-book-href")!);
+        return;
+      }
+      const link = (event.target as Element).closest?.("a[href]");
+      const href = link?.getAttribute("href") ?? "";
+      let noteMarkup: string | null = null;
+      if (link && href.startsWith("#")) {
+        const note = shadow.getElementById(decodeFragment(href.slice(1)));
+        const isNote =
+          !!note &&
+          (hasEpubType(link.getAttribute("epub:type"), ["noteref"]) ||
+            hasEpubType(note.getAttribute("epub:type"), NOTE_TYPES));
+        if (isNote) noteMarkup = note.innerHTML;
+      } else if (href) {
+        // Non-linear documents such as pop-up notes and answers open in the same popover.
+        noteMarkup = linkedNoteRef.current?.(href) ?? null;
+      }
+      if (!link || noteMarkup === null) return;
+      event.preventDefault();
+      const popover = document.createElement("div");
+      popover.className = "reader-note-popover";
+      popover.setAttribute("role", "note");
+      popover.innerHTML = noteMarkup;
+      const linkRect = link.getBoundingClientRect();
+      const containerRect = container.getBoundingClientRect();
+      popover.style.top = `${linkRect.bottom - containerRect.top + 4}px`;
+      popover.style.left = `${Math.max(0, Math.min(linkRect.left - containerRect.left, containerRect.width - 384))}px`;
+      container.appendChild(popover);
+    });
+
+    // Selections are reported once made, as their text and which repeat of that text on the page they are.
+    const reportSelection = () => {
+      const range = selectionRange(shadow);
+      if (!onSelectRef.current || !range || range.collapsed || !container.contains(range.startContainer)) return;
+      const text = range.toString();
+      if (!text.trim()) return;
+      const before = document.createRange();
+      before.setStart(container, 0);
+      before.setEnd(range.startContainer, range.startOffset);
+      const occurrence = occurrenceAt(container.textContent ?? "", text, before.toString().length);
+      onSelectRef.current({ text, occurrence });
+    };
+    container.addEventListener("mouseup", reportSelection);
+    container.addEventListener("keyup", reportSelection);
+
+    // Auto-scroll continues from the top of the next page or chapter; scrolling past an edge
+    // continues from the matching edge of the page it turned to.
+    if (previousHtmlRef.current !== html) {
+      if (autoScrollSpeedRef.current || edgeTurnRef.current === 1) host.scrollTop = 0;
+      else if (edgeTurnRef.current === -1) host.scrollTop = host.scrollHeight;
+    }
+    edgeTurnRef.current = null;
+    previousHtmlRef.current = html;
+
+    if (sourceColorBackground) {
+      container.querySelectorAll<HTMLElement>("*").forEach(element => {
+        const color = getComputedStyle(element).color;
+        if (!resolveSourceColor(color, sourceColorBackground)) {
+          element.style.setProperty("color", "inherit");
+        }
+      });
+    }
+
+    if (invertDiagrams) {
+      container.querySelectorAll("img").forEach(image => {
+        const classify = () => {
+          if (isLikelyDiagram(sampleImagePixels(image))) image.classList.add(INVERTED_IMAGE_CLASS);
+        };
+        if (image.complete) classify();
+        else image.addEventListener("load", classify, { once: true });
+      });
+    }
+
+    headingElementsRef.current = Array.from(container.querySelectorAll<HTMLElement>("h1, h2, h3, h4, h5, h6"));
+    reportActiveHeading();
+
+    const previousChapter = previousChapterRef.current;
+    previousChapterRef.current = chapterIndex;
+    // A new navigation finishes the running transition immediately, so rapid paging never queues animations.
+    transitionRef.current?.finish();
+    transitionRef.current = null;
+    if (
+      animateTransitions &&
+      chapterIndex !== undefined &&
+      previousChapter !== undefined &&
+      previousChapter !== chapterIndex &&
+      typeof host.animate === "function"
+    ) {
+      const offset = chapterIndex > previousChapter ? CHAPTER_TRANSITION_OFFSET : -CHAPTER_TRANSITION_OFFSET;
+      transitionRef.current = host.animate(
+        [
+          { opacity: 0, transform: `translateX(${offset}px)` },
+          { opacity: 1, transform: "translateX(0)" },
+        ],
+        { duration: CHAPTER_TRANSITION_MS, easing: "ease-out" },
+      );
+    }
+
     return () => {
       shadow.innerHTML = "";
     };
-  }, [html, styles, className, inlineStyle]);
+  }, [
+    html,
+    styles,
+    className,
+    inlineStyle,
+    lang,
+    dir,
+    sourceColorBackground,
+    reportActiveHeading,
+    chapterIndex,
+    animateTransitions,
+    imagesZoomable,
+    invertDiagrams,
+  ]);
+
+  useEffect(() => {
+    const shadow = hostRef.current?.shadowRoot;
+    if (!shadow || !scrollTarget) return;
+    let target: Element | null | undefined;
+    if ("anchor" in scrollTarget) {
+      target =
+        shadow.getElementById(scrollTarget.anchor) ??
+        shadow.querySelector(`[name="${CSS.escape(scrollTarget.anchor)}"]`);
+    } else if ("link" in scrollTarget) {
+      const links = visibleLinks(shadow);
+      const link = scrollTarget.link === "first" ? links[0] : links.at(-1);
+      if (link) focusLink(link);
+    } else {
+      const headings = headingElementsRef.current;
+      target = scrollTarget.heading === "first" ? headings[0] : headings.at(-1);
+    }
+    target?.scrollIntoView({ block: "start" });
+    onScrollTargetReachedRef.current?.();
+  }, [html, scrollTarget]);
+
+  useEffect(() => {
+    const container = hostRef.current?.shadowRoot?.querySelector(".reader-scope");
+    const range = container && restoreSelection ? rangeOfText(container, restoreSelection) : null;
+    if (!range) return;
+    const selection = window.getSelection();
+    selection?.removeAllRanges();
+    selection?.addRange(range);
+  }, [html, restoreSelection]);
+
+  useEffect(() => {
+    const host = hostRef.current;
+    if (!host || !autoScrollSpeed) return;
+
+    let frame = 0;
+    let lastTime: number | null = null;
+    let pending = 0;
+    const step = (time: number) => {
+      if (lastTime !== null) {
+        // scrollTop only takes whole pixels on most engines, so carry the remainder.
+        pending += ((time - lastTime) / 1000) * autoScrollSpeed;
+        const whole = Math.floor(pending);
+        if (whole > 0) {
+          host.scrollTop += whole;
+          pending -= whole;
+        }
+      }
+      lastTime = time;
+
+      if (host.scrollTop + host.clientHeight >= host.scrollHeight - 1) {
+        onAutoScrollEndRef.current?.();
+        return;
+      }
+      frame = requestAnimationFrame(step);
+    };
+
+    frame = requestAnimationFrame(step);
+    return () => cancelAnimationFrame(frame);
+  }, [autoScrollSpeed, html]);
+
+  // Buttons 3 and 4 are the mouse back/forward side buttons. Browsers navigate history on
+  // these, so both press and release are swallowed; other buttons keep selection untouched.
+  const handleSideButton = (event: React.MouseEvent<HTMLDivElement>) => {
+    if (!onSideButton || (event.button !== 3 && event.button !== 4)) return;
+    event.preventDefault();
+    if (event.type === "mouseup") {
+      onSideButton(event.button === 3 ? -1 : 1);
+    }
+  };
+
+  const interruptAutoScroll = () => {
+    if (autoScrollSpeedRef.current) onAutoScrollInterrupt?.();
+  };
+
+  const handleWheel = (event: React.WheelEvent<HTMLDivElement>) => {
+    interruptAutoScroll();
+    const host = hostRef.current;
+    if (!host || !onEdgeOverscroll) return;
+    const pixelsPerUnit = event.deltaMode === 1 ? FALLBACK_LINE_HEIGHT : event.deltaMode === 2 ? host.clientHeight : 1;
+    const { overscroll, turn } = accumulateOverscroll(
+      overscrollRef.current,
+      event.deltaY * pixelsPerUnit,
+      { atTop: host.scrollTop <= 0, atBottom: host.scrollTop + host.clientHeight >= host.scrollHeight - 1 },
+      event.timeStamp,
+    );
+    overscrollRef.current = overscroll;
+    if (turn) {
+      edgeTurnRef.current = turn;
+      onEdgeOverscroll(turn);
+    }
+  };
+
+  const handleKeyDown = (event: React.KeyboardEvent<HTMLDivElement>) => {
+    const host = hostRef.current;
+    const action = matchKeyAction(keyMap, event);
+    if (
+      action !== "toggleAutoScroll" &&
+      action !== "autoScrollFaster" &&
+      action !== "autoScrollSlower" &&
+      !["Shift", "Control", "Alt", "Meta"].includes(event.key)
+    ) {
+      interruptAutoScroll();
+    }
+    if (!host || !action) return;
+
+    const lineHeight = Number.parseFloat(getComputedStyle(host).lineHeight) || FALLBACK_LINE_HEIGHT;
+    const pageHeight = Math.max(lineHeight, host.clientHeight - lineHeight * 2);
+
+    switch (action) {
+      case "scrollDown":
+        host.scrollBy({ top: lineHeight });
+        break;
+      case "scrollUp":
+        host.scrollBy({ top: -lineHeight });
+        break;
+      case "pageDown":
+        host.scrollBy({ top: pageHeight });
+        break;
+      case "pageUp":
+        host.scrollBy({ top: -pageHeight });
+        break;
+      case "nextHeading":
+      case "previousHeading": {
+        const hostTop = host.getBoundingClientRect().top;
+        const markers = headingElementsRef.current.map(element => ({
+          offset: element.getBoundingClientRect().top - hostTop + host.scrollTop,
+        }));
+        const target = findAdjacentHeading(markers, host.scrollTop, action === "nextHeading" ? 1 : -1);
+        if (target) {
+          host.scrollTo({ top: target.offset });
+        } else {
+          // No further heading on this page; the reader moves on to the adjacent page or chapter.
+          onAction?.(action);
+        }
+        break;
+      }
+      case "focusNextLink":
+      case "focusPreviousLink": {
+        const links = visibleLinks(host.shadowRoot);
+        const focused = links.findIndex(link => link === host.shadowRoot?.activeElement);
+        const next = stepLinkFocus(focused, links.length, action === "focusNextLink" ? 1 : -1);
+        if (next !== null) {
+          focusLink(links[next]!);
+        } else if (wrapLinkFocus) {
+          onAction?.(action);
+        } else {
+          // Past the last link Tab leaves the reader as usual.
+          return;
+        }
+        break;
+      }
+      default:
+        onAction?.(action);
+    }
+    event.preventDefault();
+  };
 
   return (
     <div
       ref={hostRef}
-      className="reader-shadow flex-1 overflow-auto rounded-b-xl bg-slate-100 px-6 py-6 text-slate-900"
+      tabIndex={0}
+      role="document"
+      aria-label="正文"
+      onScroll={reportActiveHeading}
+      onKeyDown={handleKeyDown}
+      onWheel={handleWheel}
+      onTouchMove={interruptAutoScroll}
+      onPointerDown={interruptAutoScroll}
+      onMouseDown={handleSideButton}
+      onMouseUp={handleSideButton}
+      className="reader-shadow flex-1 overflow-auto rounded-b-xl px-6 py-6 focus:outline-none focus-visible:ring-2 focus-visible:ring-sky-500"
+      style={{
+        backgroundColor: "var(--reader-bg, #f8fafc)",
+        backgroundImage: "var(--reader-texture, none)",
+        backgroundSize: "var(--reader-texture-size, auto)",
+        backgroundRepeat: "var(--reader-texture-repeat, repeat)",
+        color: "var(--reader-text, #0f172a)",
+      }}
     />
   );
 }
 
+function CommandPalette({ commands, onClose }: { commands: Command[]; onClose: () => void }) {
+  const [query, setQuery] = useState("");
+  const [selected, setSelected] = useState(0);
+  const matches = useMemo(
+    () => filterCommands(commands, query).slice(0, COMMAND_PALETTE_LIMIT),
+    [commands, query],
+  );
+  const activeIndex = Math.min(selected, Math.max(0, matches.length - 1));
+
+  const runCommand = (command: Command | undefined) => {
+    if (!command) return;
+    onClose();
+    void command.run();
+  };
+
+  const handleKeyDown = (event: React.KeyboardEvent<HTMLInputElement>) => {
+    if (event.key === "ArrowDown" || event.key === "ArrowUp") {
+      event.preventDefault();
+      const step = event.key === "ArrowDown" ? 1 : -1;
+      setSelected((activeIndex + step + matches.length) % Math.max(1, matches.length));
+    } else if (event.key === "Enter") {
+      event.preventDefault();
+      runCommand(matches[activeIndex]);
+    } else if (event.key === "Escape") {
+      event.preventDefault();
+      onClose();
+    }
+  };
+
+  return (
+    <div
+      className="reader-dim-overlay fixed inset-0 z-50 flex items-start justify-center bg-slate-950/60 pt-[15vh]"
+      onMouseDown={onClose}
+    >
+      <div
+        role="dialog"
+        aria-label="命令面板"
+        className="w-full max-w-lg overflow-hidden rounded-xl border border-slate-700 bg-slate-900 shadow-2xl"
+        onMouseDown={event => event.stopPropagation()}
+      >
+        <input
+          autoFocus
+          value={query}
+          onChange={event => {
+            setQuery(event.target.value);
+            setSelected(0);
+          }}
+          onKeyDown={handleKeyDown}
+          placeholder="输入命令，例如“跳到章节”或“主题”"
+          aria-label="搜索命令"
+          className="w-full border-b border-slate-700 bg-transparent px-4 py-3 text-sm text-slate-100 focus:outline-none"
+        />
+        <ul role="listbox" className="max-h-[50vh] overflow-auto py-1 text-sm">
+          {matches.length === 0 && <li className="px-4 py-2 text-slate-500">没有匹配的命令。</li>}
+          {matches.map((command, index) => (
+            <li
+              key={command.id}
+              role="option"
+              aria-selected={index === activeIndex}
+              onMouseEnter={() => setSelected(index)}
+              onClick={() => runCommand(command)}
+              className={`cursor-pointer px-4 py-2 ${
+                index === activeIndex ? "bg-slate-800 text-sky-200" : "text-slate-300"
+              }`}
+            >
+              {command.title}
+            </li>
+          ))}
+        </ul>
+      </div>
+    </div>
+  );
+}
+
+/**
+ * Hovering the chapter position shows the titles of nearby chapters; the wheel
+ * scrubs through them without moving the reader, and a click jumps there.
+ */
+function ChapterFilmstrip({
+  current,
+  count,
+  titleAt,
+  onSelect,
+  children,
+}: {
+  current: number;
+  count: number;
+  titleAt: (index: number) => string;
+  onSelect: (index: number) => void;
+  children: React.ReactNode;
+}) {
+  const [center, setCenter] = useState<number | null>(null);
+
+  return (
+    <span
+      className="relative"
+      onMouseEnter={() => setCenter(current)}
+      onMouseLeave={() => setCenter(null)}
+      onWheel={event =>
+        setCenter(value => (value === null ? value : Math.min(Math.max(value + Math.sign(event.deltaY), 0), count - 1)))
+      }
+    >
+      <span className="cursor-default underline decoration-slate-600 decoration-dotted underline-offset-4">
+        {children}
+      </span>
+      {center !== null && (
+        // Padding rather than margin, so moving onto the list doesn't count as leaving the label.
+        <span className="absolute left-0 top-full z-20 block pt-1">
+          <ol className="w-72 overflow-hidden rounded-lg border border-slate-700 bg-slate-900 py-1 text-xs shadow-xl">
+            {chapterWindow(center, count).map(index => (
+              <li key={index}>
+                <button
+                  type="button"
+                  onClick={() => {
+                    setCenter(null);
+                    onSelect(index);
+                  }}
+                  aria-current={index === current ? "true" : undefined}
+                  className={`flex w-full gap-2 px-3 py-1 text-left hover:bg-slate-800 ${
+                    index === current ? "text-sky-200" : "text-slate-300"
+                  }`}
+                >
+                  <span className="w-8 shrink-0 text-right tabular-nums text-slate-500">{index + 1}</span>
+                  <span className="truncate">{titleAt(index)}</span>
+                </button>
+              </li>
+            ))}
+          </ol>
+        </span>
+      )}
+    </span>
+  );
+}
+
+function ImageLightbox({
+  state,
+  dispatch,
+}: {
+  state: Extract<LightboxState, { open: true }>;
+  dispatch: (action: LightboxAction) => void;
+}) {
+  const overlayRef = useRef<HTMLDivElement | null>(null);
+
+  useEffect(() => {
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.key !== "Escape") return;
+      event.preventDefault();
+      dispatch({ type: "close" });
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [dispatch]);
+
+  // React's wheel listeners are passive, so Ctrl+wheel (which is also how trackpad pinches arrive)
+  // needs a native listener to stop the page itself from zooming. Touch pinches follow two pointers.
+  useEffect(() => {
+    const overlay = overlayRef.current;
+    if (!overlay) return;
+    const pointers = new Map<number, { x: number; y: number }>();
+    let lastDistance = 0;
+    const spread = () => {
+      const [a, b] = [...pointers.values()];
+      return a && b ? Math.hypot(a.x - b.x, a.y - b.y) : 0;
+    };
+
+    const handleWheel = (event: WheelEvent) => {
+      if (!event.ctrlKey) return;
+      event.preventDefault();
+      dispatch({ type: "zoom", factor: wheelZoomFactor(event.deltaY) });
+    };
+    const handlePointerDown = (event: PointerEvent) => {
+      pointers.set(event.pointerId, { x: event.clientX, y: event.clientY });
+      lastDistance = spread();
+    };
+    const handlePointerMove = (event: PointerEvent) => {
+      if (!pointers.has(event.pointerId)) return;
+      pointers.set(event.pointerId, { x: event.clientX, y: event.clientY });
+      const distance = spread();
+      if (lastDistance > 0 && distance > 0) dispatch({ type: "zoom", factor: distance / lastDistance });
+      lastDistance = distance;
+    };
+    const handlePointerUp = (event: PointerEvent) => {
+      pointers.delete(event.pointerId);
+      lastDistance = spread();
+    };
+
+    overlay.addEventListener("wheel", handleWheel, { passive: false });
+    overlay.addEventListener("pointerdown", handlePointerDown);
+    overlay.addEventListener("pointermove", handlePointerMove);
+    overlay.addEventListener("pointerup", handlePointerUp);
+    overlay.addEventListener("pointercancel", handlePointerUp);
+    return () => {
+      overlay.removeEventListener("wheel", handleWheel);
+      overlay.removeEventListener("pointerdown", handlePointerDown);
+      overlay.removeEventListener("pointermove", handlePointerMove);
+      overlay.removeEventListener("pointerup", handlePointerUp);
+      overlay.removeEventListener("pointercancel", handlePointerUp);
+    };
+  }, [dispatch]);
+
+  return (
+    <div
+      ref={overlayRef}
+      role="dialog"
+      aria-modal="true"
+      aria-label={state.image.alt || "图片"}
+      className="fixed inset-0 z-50 flex cursor-zoom-out touch-none items-center justify-center overflow-hidden bg-slate-950/90 p-4"
+      onClick={() => dispatch({ type: "close" })}
+    >
+      {/* Fills the window while keeping its aspect ratio, which also gives size-less SVG a sensible size. */}
+      <img
+        src={state.image.src}
+        alt={state.image.alt}
+        draggable={false}
+        className="h-full w-full object-contain transition-transform duration-75"
+        style={{ transform: `scale(${state.zoom})` }}
+      />
+    </div>
+  );
+}
+
+function highlightSnippet(snippet: { text: string; ranges: HighlightRange[] }): React.ReactNode[] {
+  let cursor = 0;
+  const parts: React.ReactNode[] = [];
+  snippet.ranges.forEach(([start, end], index) => {
+    if (start < cursor) return;
+    parts.push(snippet.text.slice(cursor, start));
+    parts.push(
+      <mark key={index} className="rounded bg-amber-300/80 px-0.5 text-slate-900">
+        {snippet.text.slice(start, end)}
+      </mark>,
+    );
+    cursor = end;
+  });
+  parts.push(snippet.text.slice(cursor));
+  return parts;
+}
+
+function bookmarkKey(bookmark: PageLocator): string {
+  return `${bookmark.spineIndex}:${bookmark.offset}`;
+}
+
 function sanitizeMarkup(content: string) {
   return content
     .replace(/<\?xml[^>]*>/g, "")
@@ -144,9 +1198,197 @@ function sanitizeMarkup(content: string) {
     .replace(/xmlns(:\w+)?="[^"]*"/gi, "");
 }
 
-function findChapterIndex(chapters: ChapterPayload[], href: string) {
-  const target = href.split("#")[0];
-  return chapters.findIndex(chapter => chapter.href.split("#")[0] === target);
+/** The element `anchor` names in a chapter document, or its whole body, as markup for a popover. */
+function linkedDocumentMarkup(content: string, anchor: string): string {
+  const doc = new DOMParser().parseFromString(sanitizeMarkup(content), "text/html");
+  doc.querySelectorAll("style, link, script").forEach(node => node.remove());
+  const target = anchor ? doc.getElementById(anchor) : null;
+  return (target ?? doc.body).innerHTML;
+}
+
+function toLastReadTimes(store: InMemoryStateStore): Record<string, number> {
+  return Object.fromEntries(store.recentBooks().map(entry => [entry.bookId, entry.lastReadAt]));
+}
+
+function loadKeyMap(): KeyMap {
+  let config: KeyMapConfig = {};
+  if (typeof window !== "undefined") {
+    try {
+      const stored = JSON.parse(window.localStorage.getItem(KEY_MAP_KEY) ?? "{}");
+      if (stored && typeof stored === "object" && !Array.isArray(stored)) {
+        config = stored;
+      }
+    } catch {
+      console.warn("[keymap] Ignoring unreadable key map config");
+    }
+  }
+  const { keyMap, warnings } = createKeyMap(config);
+  warnings.forEach(warning => console.warn(`[keymap] ${warning}`));
+  return keyMap;
+}
+
+const profileStore = new ProfileStore();
+const NEW_PROFILE_OPTION = "\u0000new";
+
+// `?profile=NAME` opens the reader as that profile, creating it if needed.
+function loadActiveProfile(): string {
+  const requested = typeof window !== "undefined" ? new URLSearchParams(window.location.search).get("profile") : null;
+  return requested ? profileStore.activate(requested).active : profileStore.load().active;
+}
+
+// Only known keys with the expected type survive, so old or hand-edited values cannot break rendering.
+function prefsStoreFor(profile: string): SettingsStore<ReadingPrefs> {
+  return new SettingsStore<ReadingPrefs>({ key: profileKey(PREFS_KEY, profile), defaults: DEFAULT_PREFS });
+}
+
+function loadPlainTextBooks(): Record<string, boolean> {
+  if (typeof window === "undefined") return {};
+  try {
+    const stored = JSON.parse(window.localStorage.getItem(PLAIN_TEXT_BOOKS_KEY) ?? "{}");
+    return stored && typeof stored === "object" && !Array.isArray(stored) ? stored : {};
+  } catch {
+    return {};
+  }
+}
+
+async function uploadEpub(file: File): Promise<LoadedBook> {
+  const formData = new FormData();
+  formData.append("file", file);
+
+  const response = await fetch("/api/epub", {
+    method: "POST",
+    body: formData,
+  });
+
+  if (!response.ok) {
+    const data = await response.json().catch(() => ({}));
+    throw new Error(data.error ?? `上传 ${file.name} 失败`);
+  }
+
+  const payload = (await response.json()) as BookPayload;
+  return {
+    ...payload,
+    sourceName: file.name,
+    sourceFile: file,
+    lastUpdated: Date.now(),
+  };
+}
+
+function loadCustomThemes(): ReaderTheme[] {
+  if (typeof window === "undefined") return [];
+  try {
+    const stored = JSON.parse(window.localStorage.getItem(CUSTOM_THEMES_KEY) ?? "[]");
+    if (!Array.isArray(stored)) return [];
+    return stored.flatMap(entry => {
+      try {
+        return [parseThemeJson(JSON.stringify(entry))];
+      } catch {
+        return [];
+      }
+    });
+  } catch {
+    return [];
+  }
+}
+
+interface ChapterVariant {
+  plainText: boolean;
+  spacing: SpacingLevel;
+  numberHeadings: boolean;
+  stripRunningHeads: boolean;
+}
+
+interface PrepareOptions {
+  plainText?: boolean;
+  spacing?: SpacingLevel;
+  language?: string;
+  headingNumbers?: string[];
+  /** Links the page numbers of an index chapter to these print pages. */
+  pageMap?: PrintPageTarget[];
+}
+
+function prepareChapter(chapter: ChapterPayload, options: PrepareOptions = {}): PreparedChapter {
+  if (options.plainText) {
+    const markup = renderPlainTextParagraphs(sanitizeMarkup(chapter.content), {
+      spacing: options.spacing,
+      language: options.language,
+    });
+    return {
+      inlineStyles: [],
+      contentMarkup: markup,
+      externalStyles: [],
+      bodyClassName: "",
+      bodyInlineStyle: "",
+      session: markup ? createPaginationSession({ spineIndex: chapter.index, html: markup }) : null,
+    };
+  }
+
+  let sanitized = markEpigraph(applyEpubTypeRoles(applyImageAltFallback(sanitizeMarkup(chapter.content))));
+  if (options.headingNumbers) {
+    sanitized = applyHeadingNumbers(sanitized, options.headingNumbers);
+  }
+  if (options.pageMap?.length && isIndexChapter(sanitized, { semanticType: chapter.semanticType })) {
+    sanitized = linkIndexEntries(sanitized, options.pageMap);
+  }
+  const inlineStyleBlocks: string[] = [];
+  let bodyClassName = "";
+  let bodyInlineStyle = "";
+  let bodyContent = sanitized;
+
+  if (typeof DOMParser !== "undefined") {
+    try {
+      const parser = new DOMParser();
+      const doc = parser.parseFromString(sanitized, "text/html");
+
+      const styleNodes = Array.from(doc.querySelectorAll("style"));
+      styleNodes.forEach(node => {
+        if (node.textContent) {
+          inlineStyleBlocks.push(node.textContent);
+        }
+        node.remove();
+      });
+
+      const linkNodes = Array.from(doc.querySelectorAll("link[rel='stylesheet']"));
+      linkNodes.forEach(node => node.remove());
+
+      const docBody = doc.body;
+      if (docBody) {
+        bodyClassName = docBody.className ?? "";
+        bodyInlineStyle = docBody.getAttribute("style") ?? "";
+        bodyContent = docBody.innerHTML;
+      } else {
+        bodyContent = doc.documentElement?.innerHTML ?? sanitized;
+      }
+    } catch {
+      bodyContent = sanitized.replace(/<style[^>]*>[\s\S]*?<\/style>/gi, "");
+    }
+  } else {
+    bodyContent = sanitized.replace(/<style[^>]*>([\s\S]*?)<\/style>/gi, (_match, css) => {
+      inlineStyleBlocks.push(css);
+      return "";
+    });
+  }
+
+  return {
+    inlineStyles: inlineStyleBlocks,
+    contentMarkup: bodyContent,
+    externalStyles: chapter.styles ?? [],
+    bodyClassName,
+    bodyInlineStyle,
+    session: bodyContent
+      ? createPaginationSession({
+          spineIndex: chapter.index,
+          html: bodyContent,
+        })
+      : null,
+  };
+}
+
+function formatAuthors(metadata: BookMetadata) {
+  if (metadata.authors && metadata.authors.length > 0) {
+    return metadata.authors.join("、");
+  }
+  return metadata.creator ?? "未知作者";
 }
 
 function flattenToc(toc: TocItem[]): TocItem[] {
@@ -168,12 +1410,51 @@ export function App() {
     Record<string, { chapter: number; page: number }>
   >({});
   const [uploading, setUploading] = useState(false);
+  // Books of the folder last opened from the shelf, for stepping to the next or previous one.
+  const [shelfFolder, setShelfFolder] = useState<File[]>([]);
   const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [profile, setProfile] = useState(loadActiveProfile);
+  const [profiles, setProfiles] = useState(() => profileStore.load().profiles);
+  const [prefs, setPrefs] = useState<ReadingPrefs>(() => prefsStoreFor(profile).load());
+  const [viewMode, setViewMode] = useState<"reader" | "library">("reader");
+  const [customThemes, setCustomThemes] = useState<ReaderTheme[]>(loadCustomThemes);
+  const [keyMap] = useState<KeyMap>(loadKeyMap);
+  const [commandRegistry] = useState(() => new CommandRegistry());
+  const [commandPaletteOpen, setCommandPaletteOpen] = useState(false);
+  const [lightbox, dispatchLightbox] = useReducer(lightboxReducer, LIGHTBOX_CLOSED);
+  // Developer aid, off unless the page is opened with `?debug` or toggled with its hidden shortcut.
+  const [debugOutlines, setDebugOutlines] = useState(
+    () => typeof window !== "undefined" && new URLSearchParams(window.location.search).has("debug"),
+  );
+  const searchInputRef = useRef<HTMLInputElement | null>(null);
+  const [speechState, setSpeechState] = useState<ReadAloudState>("idle");
+  const [speechPosition, setSpeechPosition] = useState<ReadAloudPosition | null>(null);
+  const readAloudRef = useRef<ReadAloudController | null>(null);
+  const [rightsExpanded, setRightsExpanded] = useState(false);
+  const [autoScrolling, setAutoScrolling] = useState(false);
+  const [scrollTarget, setScrollTarget] = useState<ScrollTarget | null>(null);
+  const [autoScrollSpeed, setAutoScrollSpeed] = useState(AUTO_SCROLL_DEFAULT_SPEED);
+  const [resumePrompt, setResumePrompt] = useState<{ bookId: string; saved: PageLocator } | null>(null);
+  const [searchQuery, setSearchQuery] = useState("");
+  const [printPageQuery, setPrintPageQuery] = useState("");
+  const [searchHits, setSearchHits] = useState<SearchHit[] | null>(null);
+  // The query the current results belong to; lags `searchQuery` while typing.
+  const [activeSearchQuery, setActiveSearchQuery] = useState("");
+  const [bookmarks, setBookmarks] = useState<Bookmark[]>([]);
+  const [expandedBookmark, setExpandedBookmark] = useState<string | null>(null);
+  const [plainTextBooks, setPlainTextBooks] = useState<Record<string, boolean>>(loadPlainTextBooks);
 
   const storeRef = useRef<InMemoryStateStore | null>(null);
-  const paginationCacheRef = useRef<Map<string, Map<number, PaginationSession>>>(new Map());
+  const chapterCacheRef = useRef(new WeakMap<LoadedBook, Map<string, ChapterCache<PreparedChapter>>>());
+  const [eagerProgress, setEagerProgress] = useState<{ loaded: number; total: number } | null>(null);
+  // Decoded images are held on to so the browser keeps them resident.
+  const eagerImagesRef = useRef<HTMLImageElement[]>([]);
+  const chapterCacheSizeRef = useRef(prefs.chapterCacheSize);
+  chapterCacheSizeRef.current = prefs.chapterCacheSize;
   const loadedProgressRef = useRef<Set<string>>(new Set());
   const [storeReady, setStoreReady] = useState(false);
+  const [lastReadTimes, setLastReadTimes] = useState<Record<string, number>>({});
 
   useEffect(() => {
     let cancelled = false;
@@ -183,7 +1464,7 @@ export function App() {
     loadedProgressRef.current = new Set();
 
     (async () => {
-      const storeInstance = await createBrowserStateStore({ authToken });
+      const storeInstance = await createBrowserStateStore({ authToken, profile });
       if (cancelled) return;
       storeRef.current = storeInstance;
       await storeInstance.hydrate();
@@ -198,13 +1479,17 @@ export function App() {
         };
       }
       setReadingPositions(initialPositions);
+      setLastReadTimes(toLastReadTimes(storeInstance));
+      // Each profile reopens the book it read last.
+      const lastRead = storeInstance.recentBooks()[0]?.bookId;
+      if (lastRead) setCurrentBookId(lastRead);
       setStoreReady(true);
     })();
 
     return () => {
       cancelled = true;
     };
-  }, [authToken]);
+  }, [authToken, profile]);
 
   const authToken = authState.status === "authenticated" ? authState.token : undefined;
   const store = storeRef.current;
@@ -273,35 +1558,73 @@ export function App() {
     };
   }, []);
 
-  const getPaginationSession = useCallback(
-    (bookId: string, chapter: ChapterPayload, html: string) => {
-      let bookCache = paginationCacheRef.current.get(bookId);
-      if (!bookCache) {
-        bookCache = new Map();
-        paginationCacheRef.current.set(bookId, bookCache);
-      }
+  const getChapterCache = useCallback((book: LoadedBook, variant: ChapterVariant) => {
+    // Keyed by the book object, so a re-merged chapter list gets a fresh cache.
+    let caches = chapterCacheRef.current.get(book);
+    if (!caches) {
+      caches = new Map();
+      chapterCacheRef.current.set(book, caches);
+    }
+    const { plainText, spacing, numberHeadings, stripRunningHeads: stripHeads } = variant;
+    const key = [
+      plainText ? `text-${spacing}` : "html",
+      numberHeadings ? "numbered" : "unnumbered",
+      stripHeads ? "stripped" : "full",
+    ].join(":");
+    let cache = caches.get(key);
+    if (!cache) {
+      // Running heads are hidden only when rendering; the loaded chapters stay untouched.
+      const runningHeads = stripHeads ? findRunningHeads(book.chapters.map(chapter => chapter.content)) : null;
+      const chapters = runningHeads?.size
+        ? book.chapters.map(chapter => ({ ...chapter, content: stripRunningHeads(chapter.content, runningHeads) }))
+        : book.chapters;
+      const headingNumbers = numberHeadings && !plainText
+        ? numberBookHeadings(chapters.map(chapter => chapter.content))
+        : undefined;
+      cache = new ChapterCache({
+        capacity: EAGER_LOAD ? Number.POSITIVE_INFINITY : chapterCacheSizeRef.current,
+        build: index =>
+          prepareChapter(chapters[index]!, {
+            plainText,
+            spacing,
+            language: chapters[index]!.language ?? book.metadata.language,
+            headingNumbers: headingNumbers?.[index],
+            pageMap: book.pageMap,
+          }),
+      });
+      caches.set(key, cache);
+    }
+    // Picks up a changed setting the next time the cache is used.
+    if (!EAGER_LOAD) cache.resize(chapterCacheSizeRef.current);
+    return cache;
+  }, []);
 
-      let session = bookCache.get(chapter.index);
-      if (!session) {
-        session = createPaginationSession({
-          spineIndex: chapter.index,
-          html,
-        });
-        bookCache.set(chapter.index, session);
+  const togglePlainText = useCallback((bookId: string) => {
+    setPlainTextBooks(prev => {
+      const next = { ...prev };
+      if (next[bookId]) {
+        delete next[bookId];
+      } else {
+        next[bookId] = true;
       }
+      window.localStorage.setItem(PLAIN_TEXT_BOOKS_KEY, JSON.stringify(next));
+      return next;
+    });
+  }, []);
 
-      return session;
-    },
-    [],
-  );
+  // Books as the reader navigates them: chapter indices in reading positions refer to these lists.
+  const readerBooks = useMemo(() => {
+    if (prefs.mergeShortChapters <= 0) return library;
+    return library.map(book => ({ ...book, chapters: mergeShortChapters(book.chapters, prefs.mergeShortChapters) }));
+  }, [library, prefs.mergeShortChapters]);
 
   const currentBook: LoadedBook | null = useMemo(() => {
-    if (library.length === 0) return null;
+    if (readerBooks.length === 0) return null;
     if (currentBookId) {
-      return library.find(item => item.id === currentBookId) ?? library[0];
+      return readerBooks.find(item => item.id === currentBookId) ?? readerBooks[0]!;
     }
-    return library[0];
-  }, [library, currentBookId]);
+    return readerBooks[0]!;
+  }, [readerBooks, currentBookId]);
 
   const currentPosition = currentBook
     ? readingPositions[currentBook.id] ?? { chapter: 0, page: 0 }
@@ -310,79 +1633,236 @@ export function App() {
   const currentPageIndex = currentPosition.page;
 
   const chapter = currentBook?.chapters[currentChapterIndex];
-  const { inlineStyles, contentMarkup, externalStyles, bodyClassName, bodyInlineStyle } = useMemo(() => {
-    if (!chapter) {
-      return {
-        inlineStyles: [] as string[],
-        contentMarkup: "",
-        externalStyles: [] as string[],
-        bodyClassName: "",
-        bodyInlineStyle: "",
-      };
-    }
-
-    const sanitized = sanitizeMarkup(chapter.content);
-    const inlineStyleBlocks: string[] = [];
-    let bodyClassName = "";
-    let bodyInlineStyle = "";
-    let bodyContent = sanitized;
+  const plainTextMode = currentBook ? Boolean(plainTextBooks[currentBook.id]) : false;
+  const chapterVariant = useMemo<ChapterVariant>(
+    () => ({
+      plainText: plainTextMode,
+      spacing: prefs.spacing,
+      numberHeadings: prefs.numberHeadings,
+      stripRunningHeads: prefs.stripRunningHeads,
+    }),
+    [plainTextMode, prefs.spacing, prefs.numberHeadings, prefs.stripRunningHeads],
+  );
+  const preparedChapter = useMemo(() => {
+    if (!currentBook || !chapter) return null;
+    return getChapterCache(currentBook, chapterVariant).get(currentChapterIndex);
+  }, [currentBook, chapter, currentChapterIndex, getChapterCache, chapterVariant]);
 
-    if (typeof DOMParser !== "undefined") {
-      try {
-        const parser = new DOMParser();
-        const doc = parser.parseFromString(sanitized, "text/html");
+  useEffect(() => {
+    if (EAGER_LOAD || !currentBook || !preparedChapter) return;
+    const cache = getChapterCache(currentBook, chapterVariant);
+    cache.prefetchAround(currentChapterIndex, currentBook.chapters.length);
+    return () => cache.cancelPrefetch();
+  }, [currentBook, preparedChapter, currentChapterIndex, getChapterCache, chapterVariant]);
 
-        const styleNodes = Array.from(doc.querySelectorAll("style"));
-        styleNodes.forEach(node => {
-          if (node.textContent) {
-            inlineStyleBlocks.push(node.textContent);
-          }
-          node.remove();
-        });
+  useEffect(() => {
+    if (!EAGER_LOAD || !currentBook) return;
+    const controller = new AbortController();
+    const chapterCount = currentBook.chapters.length;
+    const images = [
+      ...new Set([currentBook.cover ?? "", ...currentBook.chapters.flatMap(item => listImageSources(item.content))]),
+    ].filter(src => src.startsWith("data:"));
+    const total = chapterCount + images.length;
+    setEagerProgress({ loaded: 0, total });
+
+    void (async () => {
+      await getChapterCache(currentBook, chapterVariant).preloadAll(chapterCount, {
+        signal: controller.signal,
+        onProgress: loaded => setEagerProgress({ loaded, total }),
+      });
+      const decoded: HTMLImageElement[] = [];
+      for (const [index, src] of images.entries()) {
+        if (controller.signal.aborted) return;
+        const image = new Image();
+        image.src = src;
+        // A broken image shows as broken in the chapter too; it shouldn't stop the preload.
+        await image.decode().catch(() => undefined);
+        decoded.push(image);
+        setEagerProgress({ loaded: chapterCount + index + 1, total });
+      }
+      if (controller.signal.aborted) return;
+      eagerImagesRef.current = decoded;
+      setEagerProgress(null);
+    })();
+    return () => {
+      controller.abort();
+      setEagerProgress(null);
+    };
+  }, [currentBook, chapterVariant, getChapterCache]);
 
-        const linkNodes = Array.from(doc.querySelectorAll("link[rel='stylesheet']"));
-        linkNodes.forEach(node => node.remove());
+  const inlineStyles = preparedChapter?.inlineStyles ?? [];
+  const externalStyles = preparedChapter?.externalStyles ?? [];
+  const bodyClassName = preparedChapter?.bodyClassName ?? "";
+  const bodyInlineStyle = preparedChapter?.bodyInlineStyle ?? "";
+  const paginationSession = preparedChapter?.session ?? null;
 
-        const docBody = doc.body;
-        if (docBody) {
-          bodyClassName = docBody.className ?? "";
-          bodyInlineStyle = docBody.getAttribute("style") ?? "";
-          bodyContent = docBody.innerHTML;
-        } else {
-          bodyContent = doc.documentElement?.innerHTML ?? sanitized;
-        }
-      } catch {
-        bodyContent = sanitized.replace(/<style[^>]*>[\s\S]*?<\/style>/gi, "");
-      }
-    } else {
-      bodyContent = sanitized.replace(/<style[^>]*>([\s\S]*?)<\/style>/gi, (_match, css) => {
-        inlineStyleBlocks.push(css);
-        return "";
-      });
-    }
+  const currentPageView: PageView | undefined = paginationSession?.page(currentPageIndex);
+  // Two pages need two readable columns; the reader area is measured as the window resizes.
+  const [contentWidth, setContentWidth] = useState(0);
+  const contentObserverRef = useRef<ResizeObserver | null>(null);
+  const contentAreaRef = useCallback((node: HTMLDivElement | null) => {
+    contentObserverRef.current?.disconnect();
+    contentObserverRef.current = null;
+    if (!node || typeof ResizeObserver === "undefined") return;
+    const observer = new ResizeObserver(([entry]) => setContentWidth(entry?.contentRect.width ?? 0));
+    observer.observe(node);
+    contentObserverRef.current = observer;
+  }, []);
+  const pageLayout = resolvePageLayout(prefs.pageLayout, contentWidth, prefs.fontSize);
+  const visiblePages = useMemo(
+    () => (paginationSession ? spreadPages(currentPageIndex, paginationSession.totalPages, pageLayout) : []),
+    [paginationSession, currentPageIndex, pageLayout],
+  );
+  const pageMarkup = useMemo(() => {
+    if (!paginationSession) return "";
+    const pages = visiblePages.map(
+      index => paginationSession.page(index)?.fragments.map(fragment => fragment.html).join("\n") ?? "",
+    );
+    return pageLayout === "twoPage" ? spreadMarkup(pages) : pages.join("\n");
+  }, [paginationSession, visiblePages, pageLayout]);
+  const totalPages = paginationSession?.totalPages ?? 0;
+  const previousPageIndex = paginationSession ? adjacentSpreadPage(currentPageIndex, -1, totalPages, pageLayout) : null;
+  const nextPageIndex = paginationSession ? adjacentSpreadPage(currentPageIndex, 1, totalPages, pageLayout) : null;
+  // Book-wide "page X of Y", estimated from text length rather than laid out.
+  const syntheticPages = useMemo(
+    () =>
+      currentBook
+        ? buildSyntheticPages(
+            currentBook.chapters.map(item => htmlToPlainText(item.content).length),
+            prefs.charsPerPage,
+          )
+        : null,
+    [currentBook, prefs.charsPerPage],
+  );
+  const chapterFraction = useMemo(() => {
+    const pages = paginationSession?.all() ?? [];
+    const total = pages.reduce((sum, page) => sum + page.textLength, 0);
+    const before = pages.slice(0, currentPageIndex).reduce((sum, page) => sum + page.textLength, 0);
+    return total > 0 ? before / total : 0;
+  }, [paginationSession, currentPageIndex]);
+  const bookPage = syntheticPages ? syntheticPageAt(syntheticPages, currentChapterIndex, chapterFraction) : 0;
+  const printPage = useMemo(() => {
+    const pageMap = currentBook?.pageMap ?? [];
+    if (!currentBook || pageMap.length === 0) return null;
+    // Only anchors in the open chapter need a laid-out page; other chapters are simply before or after it.
+    return printPageAt(pageMap, { chapter: currentChapterIndex, page: currentPageIndex }, href =>
+      resolveHrefTarget(currentBook.chapters, href, (index, anchor) =>
+        index === currentChapterIndex ? paginationSession?.findAnchor(anchor)?.pageIndex : undefined,
+      ),
+    );
+  }, [currentBook, currentChapterIndex, currentPageIndex, paginationSession]);
+  const chapterWords = useMemo(() => (chapter ? countWords(chapter.content) : 0), [chapter]);
+  const chapterTimeLeft = estimateReadingMinutes(Math.round(chapterWords * (1 - chapterFraction)), {
+    wordsPerMinute: prefs.readingPaceWpm,
+    samples: prefs.readingPaceSamples,
+  });
+  const chapterVisitRef = useRef<{ bookId: string; chapter: number; words: number; startedAt: number } | null>(null);
 
-    return {
-      inlineStyles: inlineStyleBlocks,
-      contentMarkup: bodyContent,
-      externalStyles: chapter.styles ?? [],
-      bodyClassName,
-      bodyInlineStyle,
+  useEffect(() => {
+    if (!currentBook) return;
+    const previous = chapterVisitRef.current;
+    const now = Date.now();
+    // Only chapters read through to a later one say anything about reading speed.
+    if (previous && previous.bookId === currentBook.id && currentChapterIndex > previous.chapter) {
+      setPrefs(prev => {
+        const pace = recordReadingSample(
+          { wordsPerMinute: prev.readingPaceWpm, samples: prev.readingPaceSamples },
+          previous.words,
+          now - previous.startedAt,
+        );
+        return pace.samples === prev.readingPaceSamples
+          ? prev
+          : { ...prev, readingPaceWpm: pace.wordsPerMinute, readingPaceSamples: pace.samples };
+      });
+    }
+    chapterVisitRef.current = {
+      bookId: currentBook.id,
+      chapter: currentChapterIndex,
+      words: chapterWords,
+      startedAt: now,
     };
-  }, [chapter]);
+  }, [currentBook?.id, currentChapterIndex]);
+  const [pageHeading, setPageHeading] = useState<string | null>(null);
+  // Headings from earlier pages of the chapter still apply until the page introduces its own.
+  const carriedHeading = useMemo(() => {
+    if (!paginationSession) return null;
+    const earlier = paginationSession
+      .all()
+      .slice(0, currentPageIndex)
+      .flatMap(page => page.fragments.flatMap(fragment => extractHeadings(fragment.html)));
+    return earlier[earlier.length - 1]?.text ?? null;
+  }, [paginationSession, currentPageIndex]);
+  const chapterMarkers = useMemo(() => {
+    if (!paginationSession || !chapter) return [];
+    const pages = paginationSession.all();
+    const total = pages.reduce((sum, page) => sum + page.textLength, 0);
+    const bookmarkMarkers = bookmarks
+      .filter(bookmark => bookmark.spineIndex === chapter.index && bookmark.offset < pages.length)
+      .map(bookmark => ({
+        kind: "bookmark" as const,
+        page: bookmark.offset,
+        fraction: total > 0 ? pages.slice(0, bookmark.offset).reduce((sum, page) => sum + page.textLength, 0) / total : 0,
+      }));
+    const searchMarkers = (searchHits ?? [])
+      .filter(hit => hit.chapterIndex === currentChapterIndex && hit.text.length > 0)
+      .flatMap(hit =>
+        hit.ranges.map(([start]) => {
+          const fraction = start / hit.text.length;
+          return { kind: "search" as const, page: pageAtFraction(pages, fraction), fraction };
+        }),
+      );
+    return clusterMarkers([...bookmarkMarkers, ...searchMarkers]);
+  }, [paginationSession, chapter, bookmarks, searchHits, currentChapterIndex]);
+  const availableThemes = useMemo(() => [...BUILT_IN_THEMES, ...customThemes], [customThemes]);
+  const theme = findTheme(availableThemes, prefs.themeId);
+  const motionContext = {
+    eInk: theme.eInk,
+    systemReducedMotion: window.matchMedia?.("(prefers-reduced-motion: reduce)").matches,
+  };
+  const themeCss = useMemo(
+    () =>
+      buildThemeCss(theme, {
+        // Book colours would come out as greys on e-paper.
+        respectSourceColors: prefs.respectSourceColors && !theme.eInk,
+        emphasisColors: prefs.emphasisColors,
+        texture: prefs.paperTexture,
+      }),
+    [theme, prefs.respectSourceColors, prefs.emphasisColors, prefs.paperTexture],
+  );
+  const chapterLanguage = chapter?.language ?? currentBook?.metadata.language;
+  const pageDirection = resolveTextDirection(currentBook?.pageProgression, chapterLanguage);
+  const rightsParagraphs = useMemo(
+    () =>
+      (currentBook?.metadata.rights ?? "")
+        .split(/\n\s*\n/)
+        .map(paragraph => paragraph.replace(/\s+/g, " ").trim())
+        .filter(Boolean),
+    [currentBook?.metadata.rights],
+  );
 
-  const paginationSession = useMemo(() => {
-    if (!currentBook || !chapter || !contentMarkup) {
-      return null;
-    }
-    return getPaginationSession(currentBook.id, chapter, contentMarkup);
-  }, [currentBook, chapter, contentMarkup, getPaginationSession]);
+  useEffect(() => {
+    setRightsExpanded(false);
+    setAutoScrolling(false);
+  }, [currentBook?.id]);
 
-  const currentPageView: PageView | undefined = paginationSession?.page(currentPageIndex);
-  const pageMarkup = useMemo(
-    () => (currentPageView ? currentPageView.fragments.map(fragment => fragment.html).join("\n") : ""),
-    [currentPageView],
+  useEffect(() => {
+    prefsStoreFor(profile).save(prefs);
+  }, [prefs, profile]);
+
+  const handleSwitchProfile = useCallback((name: string) => {
+    const requested = name === NEW_PROFILE_OPTION ? window.prompt("新档案名称") : name;
+    if (!requested?.trim()) return;
+    const list = profileStore.activate(requested);
+    setProfiles(list.profiles);
+    setProfile(list.active);
+    setPrefs(prefsStoreFor(list.active).load());
+  }, []);
+
+  const typographyCss = useMemo(
+    () => buildTypographyCss(prefs, { language: chapterLanguage }),
+    [prefs, chapterLanguage],
   );
-  const totalPages = paginationSession?.totalPages ?? 0;
+  const debugOutlineCss = useMemo(() => (debugOutlines ? buildDebugOutlineCss() : ""), [debugOutlines]);
 
   const handleLoginClick = useCallback(() => {
     if (typeof window !== "undefined") {
@@ -397,38 +1877,13 @@ export function App() {
     setAuthState({ status: "anonymous" });
   }, []);
 
-  const handleFileChange: React.ChangeEventHandler<HTMLInputElement> = async event => {
-    const fileList = event.target.files;
-    if (!fileList || fileList.length === 0) return;
+  const openFiles = async (files: File[]) => {
+    if (files.length === 0) return;
 
     setUploading(true);
     setError(null);
 
-    const files = Array.from(fileList);
-    const results = await Promise.allSettled(
-      files.map(async file => {
-        const formData = new FormData();
-        formData.append("file", file);
-
-        const response = await fetch("/api/epub", {
-          method: "POST",
-          body: formData,
-        });
-
-        if (!response.ok) {
-          const data = await response.json().catch(() => ({}));
-          throw new Error(data.error ?? `上传 ${file.name} 失败`);
-        }
-
-        const payload = (await response.json()) as BookPayload;
-        const loaded: LoadedBook = {
-          ...payload,
-          sourceName: file.name,
-          lastUpdated: Date.now(),
-        };
-        return loaded;
-      }),
-    );
+    const results = await Promise.allSettled(files.map(uploadEpub));
 
     const fulfilled = results.filter(
       (result): result is PromiseFulfilledResult<LoadedBook> => result.status === "fulfilled",
@@ -468,9 +1923,303 @@ export function App() {
     }
 
     setUploading(false);
+  };
+
+  const handleFileChange: React.ChangeEventHandler<HTMLInputElement> = async event => {
+    const fileList = event.target.files;
+    if (!fileList || fileList.length === 0) return;
+
+    await openFiles(Array.from(fileList));
     event.target.value = "";
   };
 
+  // Browsers cannot read a local path, so a pasted path only gets a hint to pick the file.
+  const handlePasteOpen = async () => {
+    let text: string;
+    try {
+      text = await navigator.clipboard.readText();
+    } catch {
+      setError("无法读取剪贴板，请检查浏览器权限。");
+      return;
+    }
+    const location = classifyClipboardText(text);
+    if (location.kind === "none") {
+      setError("剪贴板中没有 EPUB 文件路径或链接。");
+      return;
+    }
+    if (location.kind === "path") {
+      setError(`浏览器无法直接读取本地文件 ${location.path}，请通过“选择 EPUB 文件”打开它。`);
+      return;
+    }
+    try {
+      const response = await fetch(location.url);
+      if (!response.ok) throw new Error(`HTTP ${response.status}`);
+      const name = new URL(location.url).pathname.split("/").pop() || "book.epub";
+      await openFiles([new File([await response.blob()], name, { type: "application/epub+zip" })]);
+    } catch {
+      setError(`无法下载 ${location.url}，请确认链接可以访问。`);
+    }
+  };
+
+  const clippingsStore = useMemo(() => new ClippingsStore({ profile }), [profile]);
+  const selectionStore = useMemo(() => new SelectionStore({ profile }), [profile]);
+  const openBookId = currentBook?.id;
+
+  // Read once per page visit, so selecting on the page does not re-apply the saved selection.
+  const restoreSelection = useMemo(() => {
+    if (!prefs.rememberSelection || !openBookId) return null;
+    const saved = selectionStore.load(openBookId, currentChapterIndex);
+    return saved && saved.page === currentPageIndex ? saved : null;
+  }, [prefs.rememberSelection, openBookId, currentChapterIndex, currentPageIndex, selectionStore]);
+
+  useEffect(() => {
+    if (!notice) return;
+    const timer = window.setTimeout(() => setNotice(null), NOTICE_DURATION_MS);
+    return () => window.clearTimeout(timer);
+  }, [notice]);
+
+  const handleClipSelection = () => {
+    if (!currentBook) return;
+    const text = window.getSelection()?.toString().trim() ?? "";
+    if (!text) {
+      setError("请先在正文中选中要摘录的文字。");
+      return;
+    }
+    try {
+      clippingsStore.append(currentBook.id, {
+        text,
+        citation: formatAttribution(currentBook.metadata, prefs.citationStyle, chapterLabel ?? undefined),
+        clippedAt: new Date(),
+      });
+      setNotice("已加入本书摘录");
+    } catch (err) {
+      setError(isAppError(err) && err.userMessage ? err.userMessage : "摘录保存失败。");
+    }
+  };
+
+  const handleDownloadClippings = () => {
+    if (!currentBook) return;
+    const markdown = clippingsStore.read(currentBook.id);
+    if (!markdown) {
+      setError("本书还没有摘录。");
+      return;
+    }
+    const url = URL.createObjectURL(new Blob([markdown], { type: "text/markdown" }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = `${currentBook.metadata.title ?? currentBook.sourceName} 摘录.md`;
+    link.click();
+    URL.revokeObjectURL(url);
+  };
+
+  const handleExportSync = () => {
+    if (!store) return;
+    const syncFile = createSyncFile(store.snapshot());
+    const blob = new Blob([JSON.stringify(syncFile, null, 2)], { type: "application/json" });
+    const url = URL.createObjectURL(blob);
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = "bkai-sync.json";
+    link.click();
+    URL.revokeObjectURL(url);
+  };
+
+  const handleCopyBibtex = async () => {
+    if (!currentBook) return;
+    try {
+      await navigator.clipboard.writeText(toBibtex(currentBook.metadata));
+    } catch {
+      setError("无法写入剪贴板，请检查浏览器权限。");
+    }
+  };
+
+  // Copies the selection, or the whole chapter when nothing is selected, followed by its source.
+  const handleCopyQuote = async () => {
+    if (!currentBook || !chapter) return;
+    // Text selected inside the reader's shadow root still shows up in the document selection.
+    const selected = window.getSelection()?.toString().trim() ?? "";
+    const text = selected || htmlToPlainText(chapter.content);
+    try {
+      await navigator.clipboard.writeText(
+        composeQuote(text, currentBook.metadata, {
+          style: prefs.citationStyle,
+          chapterTitle: chapterLabel ?? undefined,
+        }),
+      );
+    } catch {
+      setError("无法写入剪贴板，请检查浏览器权限。");
+    }
+  };
+
+  const handleExportEpub = () => {
+    if (!currentBook) return;
+    try {
+      const data = writeEpub({
+        metadata: currentBook.metadata,
+        chapters: currentBook.chapters.map((item, index) => ({
+          title: deriveChapterTitle(
+            {
+              id: item.idref,
+              html: item.content,
+              tocLabel: flatToc.find(entry => findChapterIndex(currentBook.chapters, entry.href) === index)?.label,
+            },
+            { preferHeading: prefs.preferHeadingTitle },
+          ),
+          html: toSemanticHtml(item.content),
+        })),
+      });
+      const url = URL.createObjectURL(new Blob([data], { type: "application/epub+zip" }));
+      const link = document.createElement("a");
+      link.href = url;
+      link.download = `${currentBook.metadata.title ?? currentBook.sourceName ?? "book"}.epub`;
+      link.click();
+      URL.revokeObjectURL(url);
+    } catch (err) {
+      setError(isAppError(err) && err.userMessage ? err.userMessage : "导出 EPUB 失败。");
+    }
+  };
+
+  const handleCopyChapterHtml = async () => {
+    if (!chapter) return;
+    try {
+      await navigator.clipboard.writeText(toSemanticHtml(chapter.content));
+    } catch {
+      setError("无法写入剪贴板，请检查浏览器权限。");
+    }
+  };
+
+  const stopSpeech = useCallback(() => {
+    readAloudRef.current?.stop();
+    readAloudRef.current = null;
+  }, []);
+
+  const handleToggleSpeech = () => {
+    if (readAloudRef.current) {
+      stopSpeech();
+      return;
+    }
+    if (!currentBook) return;
+
+    const engine = createWebSpeechEngine({ lang: chapterLanguage });
+    if (!engine) {
+      setError("当前浏览器不支持朗读。");
+      return;
+    }
+
+    const book = currentBook;
+    const controller = new ReadAloudController(
+      engine,
+      {
+        chapterCount: book.chapters.length,
+        paragraphs: index =>
+          htmlToPlainText(book.chapters[index]?.content ?? "")
+            .split(/\n\s*\n/)
+            .map(paragraph => paragraph.replace(/\s+/g, " ").trim())
+            .filter(Boolean),
+      },
+      {
+        onStateChange: setSpeechState,
+        onPosition: position => {
+          setSpeechPosition(position);
+          if (position) {
+            updatePosition(book.id, current =>
+              current.chapter === position.chapterIndex ? current : { chapter: position.chapterIndex, page: 0 },
+            );
+          }
+        },
+      },
+    );
+    readAloudRef.current = controller;
+    void controller.start(currentChapterIndex).finally(() => {
+      if (readAloudRef.current === controller && controller.state === "idle") {
+        readAloudRef.current = null;
+      }
+    });
+  };
+
+  const handleExportTheme = () => {
+    const blob = new Blob([themeToJson(theme)], { type: "application/json" });
+    const url = URL.createObjectURL(blob);
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = `bkai-theme-${theme.id}.json`;
+    link.click();
+    URL.revokeObjectURL(url);
+  };
+
+  const handleImportTheme: React.ChangeEventHandler<HTMLInputElement> = async event => {
+    const file = event.target.files?.[0];
+    event.target.value = "";
+    if (!file) return;
+
+    try {
+      const imported = parseThemeJson(await file.text());
+      // Built-in ids are reserved so a shared file cannot shadow them.
+      const themeToAdd = BUILT_IN_THEMES.some(item => item.id === imported.id)
+        ? { ...imported, id: `custom-${imported.id}` }
+        : imported;
+      setCustomThemes(prev => {
+        const next = [...prev.filter(item => item.id !== themeToAdd.id), themeToAdd];
+        window.localStorage.setItem(CUSTOM_THEMES_KEY, JSON.stringify(next));
+        return next;
+      });
+      setPrefs(prev => ({ ...prev, themeId: themeToAdd.id }));
+    } catch (err) {
+      setError(isAppError(err) && err.userMessage ? err.userMessage : "主题文件读取失败。");
+    }
+  };
+
+  const handleImportSync: React.ChangeEventHandler<HTMLInputElement> = async event => {
+    const file = event.target.files?.[0];
+    event.target.value = "";
+    if (!file || !store) return;
+
+    try {
+      const syncFile = parseSyncFile(JSON.parse(await file.text()));
+      if (!syncFile) {
+        setError("无法识别的同步文件版本，已忽略。");
+        return;
+      }
+
+      const adopted = await store.mergeSync(syncFile);
+      setReadingPositions(prev => {
+        const next = { ...prev };
+        for (const bookId of adopted) {
+          const entry = syncFile.books[bookId];
+          const bookEntry = readerBooks.find(item => item.id === bookId);
+          if (!entry || !bookEntry) continue;
+          const chapterIndex = bookEntry.chapters.findIndex(ch => ch.index === entry.spineIndex);
+          if (chapterIndex >= 0) {
+            next[bookId] = { chapter: chapterIndex, page: entry.offset };
+          }
+        }
+        return next;
+      });
+    } catch (error) {
+      console.warn("Failed to import sync file", error);
+      setError("同步文件格式错误。");
+    }
+  };
+
+  const handleShelfOpen = async (file: File, folder: File[]) => {
+    setShelfFolder(folder);
+    setViewMode("reader");
+    await openFiles([file]);
+  };
+
+  const siblingBook = (delta: number): File | null => {
+    const source = currentBook?.sourceFile;
+    if (!source) return null;
+    const path = siblingBookPath(shelfFolder.map(relativePath), relativePath(source), delta);
+    return shelfFolder.find(file => relativePath(file) === path) ?? null;
+  };
+  const nextBook = siblingBook(1);
+  const previousBook = siblingBook(-1);
+  // At the first or last book of the folder there is nothing to open.
+  const openSiblingBook = (file: File | null) => {
+    if (file) void openFiles([file]);
+  };
+
   const updatePosition = useCallback(
     (bookId: string, updater: (position: { chapter: number; page: number }) => { chapter: number; page: number }) => {
       setReadingPositions(prev => {
@@ -488,55 +2237,290 @@ export function App() {
     [],
   );
 
+  const reloadSource = currentBook?.sourceFile;
+  const canReloadBook = Boolean(reloadSource);
+  // Re-reads the open book from its file, e.g. after editing it in Calibre. On failure the old version stays up.
+  const handleReloadBook = useCallback(async () => {
+    if (!currentBook || !reloadSource) return;
+    const bookId = currentBook.id;
+    setError(null);
+    let reloaded: LoadedBook;
+    try {
+      reloaded = await uploadEpub(reloadSource);
+    } catch (err) {
+      // fetch reports a file that can no longer be read as a TypeError.
+      const reason =
+        err instanceof TypeError
+          ? "无法读取原文件，它可能已被移动或删除"
+          : err instanceof Error
+            ? err.message
+            : "未知错误";
+      setError(`重新载入失败，仍显示之前的版本：${reason}`);
+      return;
+    }
+
+    // The book keeps its id so progress, bookmarks and notes stay attached to it.
+    setLibrary(prev => prev.map(item => (item.id === bookId ? { ...reloaded, id: bookId } : item)));
+    const chapterCount =
+      prefs.mergeShortChapters > 0
+        ? mergeShortChapters(reloaded.chapters, prefs.mergeShortChapters).length
+        : reloaded.chapters.length;
+    updatePosition(bookId, position => positionAfterReload(position, chapterCount));
+  }, [currentBook, reloadSource, prefs.mergeShortChapters, updatePosition]);
+
+  const settleResumePrompt = useCallback(
+    (choice: ResumeChoice) => {
+      if (!resumePrompt) return;
+      const decision = decideResume(resumePrompt.saved, choice);
+      const book = readerBooks.find(item => item.id === resumePrompt.bookId);
+      setResumePrompt(null);
+      // The saved position was already restored on load; only starting over moves the reader.
+      if (!book || !decision.clearSaved) return;
+      void store?.clearProgress(book.id);
+      const chapterIndex = book.chapters.findIndex(ch => ch.index === decision.location.spineIndex);
+      updatePosition(book.id, () => ({ chapter: Math.max(0, chapterIndex), page: decision.location.offset }));
+    },
+    [resumePrompt, readerBooks, store, updatePosition],
+  );
+
+  useEffect(() => {
+    if (!resumePrompt) return;
+    const timer = window.setTimeout(() => settleResumePrompt("timeout"), RESUME_PROMPT_TIMEOUT_MS);
+    return () => window.clearTimeout(timer);
+  }, [resumePrompt, settleResumePrompt]);
+
+  const flatToc = useMemo(() => (currentBook ? flattenToc(currentBook.toc) : []), [currentBook]);
+  const tocOrder = useMemo(
+    () =>
+      currentBook
+        ? tocChapterOrder(
+            flatToc.map(item => item.href),
+            href => findChapterIndex(currentBook.chapters, href),
+          )
+        : [],
+    [currentBook, flatToc],
+  );
+  // Parallel to flatToc; repeated labels such as "Exercises" get their context appended for display only.
+  const tocDisplayLabels = useMemo(
+    () =>
+      currentBook ? disambiguateTocLabels(currentBook.toc, href => findChapterIndex(currentBook.chapters, href)) : [],
+    [currentBook],
+  );
+  // Blank separator documents stay in the book, but navigation and the TOC step over them when asked to.
+  const hiddenChapters = useMemo(() => {
+    if (!prefs.hideEmptyChapters || !currentBook) return new Set<number>();
+    return new Set(
+      currentBook.chapters.flatMap((item, index) => (!item.missing && isBlankChapter(item.content) ? [index] : [])),
+    );
+  }, [currentBook, prefs.hideEmptyChapters]);
+  const isTocEntryHidden = (href: string) =>
+    hiddenChapters.size > 0 && !!currentBook && hiddenChapters.has(findChapterIndex(currentBook.chapters, href));
+  // Non-linear documents (`linear="no"`) are only reached through links, so Next/Previous and the count leave them out.
+  const skippedChapters = useMemo(
+    () => new Set([...hiddenChapters, ...nonLinearChapters(currentBook?.chapters ?? [])]),
+    [currentBook, hiddenChapters],
+  );
+  const previousChapterIndex = currentBook
+    ? adjacentChapter(currentChapterIndex, -1, currentBook.chapters.length, prefs.navOrder, tocOrder, skippedChapters)
+    : null;
+  const nextChapterIndex = currentBook
+    ? adjacentChapter(currentChapterIndex, 1, currentBook.chapters.length, prefs.navOrder, tocOrder, skippedChapters)
+    : null;
+  const skippedBeforeCurrent = [...skippedChapters].filter(index => index < currentChapterIndex).length;
+
+  const linkedNonLinearNote = (href: string): string | null => {
+    const target = currentBook && chapter ? resolveBookHref(chapter.href, href) : null;
+    const linked = target ? currentBook?.chapters[findChapterIndex(currentBook.chapters, target)] : undefined;
+    if (!target || !linked || linked.linear !== false) return null;
+    const hash = target.indexOf("#");
+    return linkedDocumentMarkup(linked.content, decodeFragment(hash === -1 ? "" : target.slice(hash + 1)));
+  };
+
   const handlePrev = () => {
     if (!currentBook || !chapter) return;
 
-    if (paginationSession && currentPageIndex > 0) {
+    if (previousPageIndex !== null) {
       updatePosition(currentBook.id, position => ({
         chapter: position.chapter,
-        page: position.page - 1,
+        page: previousPageIndex,
       }));
       return;
     }
 
-    updatePosition(currentBook.id, position => ({
-      chapter: Math.max(0, position.chapter - 1),
-      page: 0,
-    }));
+    if (previousChapterIndex === null) return;
+    updatePosition(currentBook.id, () => ({ chapter: previousChapterIndex, page: 0 }));
   };
 
   const handleNext = () => {
     if (!currentBook || !chapter) return;
 
-    if (paginationSession && currentPageIndex < totalPages - 1) {
+    if (nextPageIndex !== null) {
       updatePosition(currentBook.id, position => ({
         chapter: position.chapter,
-        page: position.page + 1,
+        page: nextPageIndex,
       }));
       return;
     }
 
-    updatePosition(currentBook.id, position => ({
-      chapter: Math.min(
-        currentBook.chapters.length - 1,
-        position.chapter + 1,
-      ),
-      page: 0,
+    if (nextChapterIndex === null) return;
+    updatePosition(currentBook.id, () => ({ chapter: nextChapterIndex, page: 0 }));
+  };
+
+  const isAtBookEnd = !!currentBook && nextChapterIndex === null && nextPageIndex === null;
+
+  const handleAutoScrollEnd = () => {
+    if (isAtBookEnd) {
+      setAutoScrolling(false);
+      return;
+    }
+    handleNext();
+  };
+
+  const adjustAutoScrollSpeed = (direction: 1 | -1) => {
+    setAutoScrollSpeed(speed =>
+      Math.min(AUTO_SCROLL_MAX_SPEED, Math.max(AUTO_SCROLL_MIN_SPEED, speed + direction * AUTO_SCROLL_SPEED_STEP)),
+    );
+  };
+
+  const handleHeadingRollover = (direction: 1 | -1) => {
+    if (!currentBook || !paginationSession) return;
+    const heading = direction === 1 ? "first" : "last";
+    const page = findPageWithHeading(paginationSession.all(), currentPageIndex, direction);
+    if (page !== -1) {
+      updatePosition(currentBook.id, position => ({ chapter: position.chapter, page }));
+      setScrollTarget({ heading });
+      return;
+    }
+
+    const chapterIndex = direction === 1 ? nextChapterIndex : previousChapterIndex;
+    if (chapterIndex === null) return;
+    const pages = getChapterCache(currentBook, chapterVariant).get(chapterIndex).session.all();
+    const target = findPageWithHeading(pages, direction === 1 ? -1 : pages.length, direction);
+    updatePosition(currentBook.id, () => ({
+      chapter: chapterIndex,
+      page: target !== -1 ? target : direction === 1 ? 0 : pages.length - 1,
     }));
+    setScrollTarget({ heading });
+  };
+
+  // Mirrors the heading rollover: the next page with a link, else the adjacent chapter.
+  const handleLinkRollover = (direction: 1 | -1) => {
+    if (!currentBook || !paginationSession) return;
+    const link = direction === 1 ? "first" : "last";
+    const page = findPageWithLink(paginationSession.all(), currentPageIndex, direction);
+    if (page !== -1) {
+      updatePosition(currentBook.id, position => ({ chapter: position.chapter, page }));
+      setScrollTarget({ link });
+      return;
+    }
+
+    const chapterIndex = direction === 1 ? nextChapterIndex : previousChapterIndex;
+    if (chapterIndex === null) return;
+    const pages = getChapterCache(currentBook, chapterVariant).get(chapterIndex).session.all();
+    const target = findPageWithLink(pages, direction === 1 ? -1 : pages.length, direction);
+    updatePosition(currentBook.id, () => ({
+      chapter: chapterIndex,
+      page: target !== -1 ? target : direction === 1 ? 0 : pages.length - 1,
+    }));
+    setScrollTarget({ link });
   };
 
   const handleTocSelect = (href: string) => {
     if (!currentBook) return;
-    const index = findChapterIndex(currentBook.chapters, href);
-    if (index >= 0) {
-      updatePosition(currentBook.id, () => ({
-        chapter: index,
-        page: 0,
-      }));
+    const target = resolveHrefTarget(
+      currentBook.chapters,
+      href,
+      (index, anchor) => getChapterCache(currentBook, chapterVariant).get(index).session?.findAnchor(anchor)?.pageIndex,
+    );
+    if (target) {
+      updatePosition(currentBook.id, () => ({ chapter: target.chapter, page: target.page }));
+      setScrollTarget(target.anchor ? { anchor: target.anchor } : null);
     }
   };
 
-  const flatToc = useMemo(() => (currentBook ? flattenToc(currentBook.toc) : []), [currentBook]);
+  const debouncedSearch = useMemo(
+    () =>
+      currentBook
+        ? new DebouncedSearch(query => searchChapters(currentBook.chapters, query), {
+            onResult: (query, hits) => {
+              setActiveSearchQuery(hits ? query : "");
+              setSearchHits(hits);
+            },
+          })
+        : null,
+    [currentBook],
+  );
+  useEffect(() => {
+    setSearchHits(null);
+    setActiveSearchQuery("");
+    setExpandedBookmark(null);
+    return () => debouncedSearch?.cancel();
+  }, [debouncedSearch]);
+  // Recomputed from the bookmarks so edited notes show up without searching again.
+  const noteHits = useMemo(
+    () => (activeSearchQuery.trim() ? searchNotes(bookmarks, activeSearchQuery) : null),
+    [bookmarks, activeSearchQuery],
+  );
+  const searchMatchCount = useMemo(
+    () =>
+      (searchHits ?? []).reduce((sum, hit) => sum + hit.ranges.length, 0) +
+      (noteHits ?? []).reduce((sum, hit) => sum + hit.ranges.length, 0),
+    [searchHits, noteHits],
+  );
+
+  useEffect(() => {
+    window.addEventListener("pagehide", stopSpeech);
+    return () => {
+      window.removeEventListener("pagehide", stopSpeech);
+      stopSpeech();
+    };
+  }, [currentBook?.id, stopSpeech]);
+
+  const handleGoToPrintPage: React.FormEventHandler<HTMLFormElement> = event => {
+    event.preventDefault();
+    const target = currentBook ? findPrintPage(currentBook.pageMap ?? [], printPageQuery) : undefined;
+    if (!target) {
+      setError(`书中没有标注第 ${printPageQuery.trim()} 页。`);
+      return;
+    }
+    setError(null);
+    handleTocSelect(target.href);
+  };
+
+  const handleSearchSubmit: React.FormEventHandler<HTMLFormElement> = event => {
+    event.preventDefault();
+    debouncedSearch?.flush(searchQuery);
+  };
+
+  const figures = useMemo(() => (currentBook ? collectFigures(currentBook.chapters) : []), [currentBook]);
+  const coverSrc = useMemo(
+    () =>
+      currentBook
+        ? currentBook.cover ??
+          generateCoverDataUrl({ ...currentBook.metadata, title: currentBook.metadata.title ?? currentBook.sourceName })
+        : null,
+    [currentBook],
+  );
+  const chapterTitleAt = useCallback(
+    (index: number) => {
+      const item = currentBook?.chapters[index];
+      if (!currentBook || !item) return "";
+      const tocLabel = flatToc.find(entry => findChapterIndex(currentBook.chapters, entry.href) === index)?.label;
+      return deriveChapterTitle(
+        { id: item.idref, html: item.content, tocLabel },
+        { preferHeading: prefs.preferHeadingTitle },
+      );
+    },
+    [currentBook, flatToc, prefs.preferHeadingTitle],
+  );
+  const chapterLabel = useMemo(
+    () => (chapter ? chapterTitleAt(currentChapterIndex) : null),
+    [chapter, chapterTitleAt, currentChapterIndex],
+  );
+  const activeHeading = pageHeading ?? carriedHeading;
+  const breadcrumb = [chapterLabel, activeHeading !== chapterLabel ? activeHeading : null].filter(
+    (part): part is string => Boolean(part),
+  );
 
   useEffect(() => {
     if (library.length > 0 && !currentBookId) {
@@ -546,12 +2530,15 @@ export function App() {
 
   useEffect(() => {
     if (!store) return;
-    library.forEach(bookEntry => {
+    readerBooks.forEach(bookEntry => {
       if (loadedProgressRef.current.has(bookEntry.id)) return;
       loadedProgressRef.current.add(bookEntry.id);
       void (async () => {
         const progress = await store.loadProgress(bookEntry.id);
         if (!progress) return;
+        if (shouldOfferResume(progress)) {
+          setResumePrompt({ bookId: bookEntry.id, saved: progress });
+        }
         setReadingPositions(prev => {
           const chapterIndex = bookEntry.chapters.findIndex(ch => ch.index === progress.spineIndex);
           if (chapterIndex < 0) return prev;
@@ -565,7 +2552,7 @@ export function App() {
         });
       })();
     });
-  }, [library, store]);
+  }, [readerBooks, store]);
 
   useEffect(() => {
     if (!store || !currentBook) return;
@@ -580,6 +2567,174 @@ export function App() {
     });
   }, [currentBook, readingPositions, store]);
 
+  const activeBookId = currentBook?.id;
+
+  useEffect(() => {
+    if (!store || !storeReady || !activeBookId) {
+      setBookmarks([]);
+      return;
+    }
+    let cancelled = false;
+    void store.listBookmarks(activeBookId).then(list => {
+      if (!cancelled) setBookmarks(list);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [store, storeReady, activeBookId]);
+
+  const handleAddBookmark = useCallback(async () => {
+    if (!store || !currentBook || !chapter) return;
+    const location = { spineIndex: chapter.index, offset: currentPageIndex };
+    const exists = bookmarks.some(
+      bookmark => bookmark.spineIndex === location.spineIndex && bookmark.offset === location.offset,
+    );
+    if (!exists) {
+      await store.addBookmark(currentBook.id, location);
+    }
+    setBookmarks(await store.listBookmarks(currentBook.id));
+  }, [store, currentBook, chapter, currentPageIndex, bookmarks]);
+
+  const handleBookmarkNoteSave = async (bookmark: Bookmark, note: string) => {
+    if (!store || !currentBook || note.trim() === (bookmark.note ?? "")) return;
+    await store.updateBookmarkNote(currentBook.id, bookmark, note);
+    setBookmarks(await store.listBookmarks(currentBook.id));
+  };
+
+  const handleBookmarkSelect = (bookmark: PageLocator) => {
+    if (!currentBook) return;
+    const chapterIndex = currentBook.chapters.findIndex(item => item.index === bookmark.spineIndex);
+    if (chapterIndex < 0) return;
+    updatePosition(currentBook.id, () => ({ chapter: chapterIndex, page: bookmark.offset }));
+  };
+
+  const bookmarkLabel = (bookmark: PageLocator) => {
+    const chapterIndex = currentBook?.chapters.findIndex(item => item.index === bookmark.spineIndex) ?? -1;
+    return `章节 ${chapterIndex + 1} · 第 ${bookmark.offset + 1} 页`;
+  };
+
+  // Registered on every render so each closure sees the current book and position.
+  useEffect(() => {
+    if (!currentBook) return;
+    return commandRegistry.register(
+      { id: "search", title: "搜索全文", keywords: ["search", "find"], run: () => searchInputRef.current?.focus() },
+      { id: "previousChapter", title: "上一章", keywords: ["previous chapter"], run: handlePrev },
+      { id: "nextChapter", title: "下一章", keywords: ["next chapter"], run: handleNext },
+      { id: "addBookmark", title: "添加书签", keywords: ["add bookmark"], run: handleAddBookmark },
+      { id: "copyBibtex", title: "复制 BibTeX", keywords: ["copy bibtex", "cite"], run: handleCopyBibtex },
+      { id: "copyQuote", title: "复制引文（附出处）", keywords: ["copy quote", "cite"], run: handleCopyQuote },
+      { id: "clipSelection", title: "摘录选中文字", keywords: ["clip selection", "clippings"], run: handleClipSelection },
+      {
+        id: "downloadClippings",
+        title: "下载本书摘录（Markdown）",
+        keywords: ["download clippings", "markdown"],
+        run: handleDownloadClippings,
+      },
+      { id: "copyChapterHtml", title: "复制章节 HTML", keywords: ["copy html"], run: handleCopyChapterHtml },
+      { id: "exportEpub", title: "导出精简 EPUB", keywords: ["export epub"], run: handleExportEpub },
+      { id: "reloadBook", title: "重新载入本书", keywords: ["reload book"], run: handleReloadBook },
+      {
+        id: "pasteOpen",
+        title: "打开剪贴板中的书",
+        keywords: ["paste open", "clipboard"],
+        run: handlePasteOpen,
+      },
+      ...(nextBook
+        ? [{ id: "nextBook", title: `下一本：${nextBook.name}`, keywords: ["next book"], run: () => openSiblingBook(nextBook) }]
+        : []),
+      ...(previousBook
+        ? [
+            {
+              id: "previousBook",
+              title: `上一本：${previousBook.name}`,
+              keywords: ["previous book"],
+              run: () => openSiblingBook(previousBook),
+            },
+          ]
+        : []),
+      {
+        id: "toggleSpeech",
+        title: "朗读 / 停止朗读",
+        keywords: ["toggle speech", "read aloud"],
+        run: handleToggleSpeech,
+      },
+      {
+        id: "toggleAutoScroll",
+        title: "开始 / 停止自动滚动",
+        keywords: ["toggle auto scroll"],
+        run: () => setAutoScrolling(value => !value),
+      },
+      {
+        id: "togglePlainText",
+        title: "切换纯文本模式",
+        keywords: ["toggle plain text"],
+        run: () => togglePlainText(currentBook.id),
+      },
+      ...flatToc.flatMap((item, position) =>
+        isTocEntryHidden(item.href)
+          ? []
+          : [
+              {
+                id: `toc:${item.id}`,
+                title: `跳到章节：${tocDisplayLabels[position] ?? item.label}`,
+                keywords: [`go to chapter ${item.label}`],
+                run: () => handleTocSelect(item.href),
+              },
+            ],
+      ),
+    );
+  });
+
+  useEffect(
+    () =>
+      commandRegistry.register(
+        ...availableThemes.map(option => ({
+          id: `theme:${option.id}`,
+          title: `切换主题：${option.label}`,
+          keywords: [`toggle theme ${option.id}`],
+          run: () => setPrefs(prev => ({ ...prev, themeId: option.id })),
+        })),
+      ),
+    [commandRegistry, availableThemes],
+  );
+
+  useEffect(() => {
+    const handleKeyDown = (event: KeyboardEvent) => {
+      // The reader page handles its own keys and marks them as handled.
+      if (event.defaultPrevented) return;
+      const action = matchKeyAction(keyMap, event);
+      if (action === "openCommandPalette") {
+        event.preventDefault();
+        setCommandPaletteOpen(true);
+      } else if (action === "reloadBook" && canReloadBook) {
+        // Only taken over while a book can be re-read; otherwise the browser reloads the page as usual.
+        event.preventDefault();
+        void handleReloadBook();
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [keyMap, canReloadBook, handleReloadBook]);
+
+  useEffect(() => {
+    if (!store || !storeReady || !activeBookId) return;
+    let cancelled = false;
+    void store.markRead(activeBookId).then(() => {
+      if (!cancelled) setLastReadTimes(toLastReadTimes(store));
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [store, storeReady, activeBookId, currentChapterIndex]);
+
+  const recentLibrary = useMemo(
+    () =>
+      [...library].sort(
+        (a, b) => (lastReadTimes[b.id] ?? Number.NEGATIVE_INFINITY) - (lastReadTimes[a.id] ?? Number.NEGATIVE_INFINITY),
+      ),
+    [library, lastReadTimes],
+  );
+
   useEffect(() => {
     if (!paginationSession || !currentBook) {
       return;
@@ -603,7 +2758,13 @@ export function App() {
   }
 
   return (
-    <div className="min-h-screen bg-slate-950 text-slate-100">
+    <div
+      className="min-h-screen bg-slate-950 text-slate-100"
+      data-theme={theme.id}
+      data-eink={theme.eInk || undefined}
+      data-reduce-motion={!motionAllowed(prefs, motionContext) || undefined}
+    >
+      <style>{theme.eInk ? REDUCED_MOTION_CSS + E_INK_CHROME_CSS : REDUCED_MOTION_CSS}</style>
       <header className="border-b border-slate-800 bg-slate-900/80 backdrop-blur py-6">
         <div className="mx-auto flex max-w-5xl flex-col gap-4 px-6 sm:flex-row sm:items-center sm:justify-between">
           <div>
@@ -674,11 +2835,29 @@ export function App() {
               />
               {uploading ? "解析中..." : "选择 EPUB 文件"}
             </label>
+
+            <button
+              type="button"
+              onClick={() => setViewMode(mode => (mode === "library" ? "reader" : "library"))}
+              className="rounded-lg border border-slate-700 bg-slate-800 px-4 py-2 text-sm font-medium text-slate-200 hover:bg-slate-700"
+            >
+              {viewMode === "library" ? "返回阅读" : "书架"}
+            </button>
           </div>
         </div>
       </header>
 
-      <main className="mx-auto flex min-h-[calc(100vh-5rem)] max-w-5xl flex-col gap-6 px-6 py-6 lg:flex-row">
+      <main className={`mx-auto max-w-5xl px-6 py-6 ${viewMode === "library" ? "flex" : "hidden"}`}>
+        <LibraryView onOpen={(file, folder) => void handleShelfOpen(file, folder)} />
+      </main>
+
+      <main
+        className={`mx-auto min-h-[calc(100vh-5rem)] flex-col gap-6 px-6 py-6 lg:flex-row ${
+          prefs.pageLayout === "twoPage" ? "max-w-screen-2xl" : "max-w-5xl"
+        } ${
+          viewMode === "reader" ? "flex" : "hidden"
+        }`}
+      >
         <aside className="w-full shrink-0 space-y-4 rounded-xl border border-slate-800 bg-slate-900/60 p-4 lg:w-72">
           <div className="space-y-3">
             <div>
@@ -690,13 +2869,15 @@ export function App() {
                 {library.length === 0 && (
                   <p className="text-slate-500">尚未加载电子书，请先选择 `.epub` 文件。</p>
                 )}
-                {library.map(entry => {
+                {recentLibrary.map(entry => {
                   const isActive = currentBook?.id === entry.id;
+                  const lastReadAt = lastReadTimes[entry.id];
                   return (
                     <button
                       key={entry.id}
                       type="button"
                       onClick={() => setCurrentBookId(entry.id)}
+                      aria-current={isActive ? "true" : undefined}
                       className={`flex w-full flex-col rounded-md border px-2 py-2 text-left transition ${
                         isActive
                           ? "border-sky-500 bg-sky-500/10 text-sky-100"
@@ -706,8 +2887,9 @@ export function App() {
                       <span className="line-clamp-1 text-sm font-medium">
                         {entry.metadata.title ?? entry.sourceName ?? "未命名书籍"}
                       </span>
-                      <span className="text-xs text-slate-400">
-                        {entry.metadata.creator ?? "未知作者"}
+                      <span className="flex justify-between gap-2 text-xs text-slate-400">
+                        <span className="line-clamp-1">{formatAuthors(entry.metadata)}</span>
+                        {lastReadAt !== undefined && <span className="shrink-0">{formatRelativeTime(lastReadAt)}</span>}
                       </span>
                     </button>
                   );
@@ -718,22 +2900,118 @@ export function App() {
             {currentBook ? (
               <div className="space-y-4">
                 <div>
+                  {coverSrc && (
+                    <img
+                      src={coverSrc}
+                      alt={currentBook.cover ? "封面" : ""}
+                      className="mb-2 aspect-[2/3] w-20 rounded border border-slate-800 object-cover"
+                    />
+                  )}
                   <h2 className="text-lg font-semibold">
                     {currentBook.metadata.title ?? currentBook.sourceName ?? "未命名书籍"}
+                    {currentBook.metadata.subtitle ? ` \u2014 ${currentBook.metadata.subtitle}` : ""}
                   </h2>
+                  {!!currentBook.metadata.alternateTitles?.length && (
+                    <p className="text-sm text-slate-400">{currentBook.metadata.alternateTitles.join(" / ")}</p>
+                  )}
                   <p className="text-sm text-slate-400">
-                    {currentBook.metadata.creator ?? "未知作者"}
+                    {formatAuthors(currentBook.metadata)}
                     {currentBook.metadata.publisher ? ` · ${currentBook.metadata.publisher}` : ""}
+                    {currentBook.metadata.date && (
+                      <span title={currentBook.metadata.date}>
+                        {` · ${formatPublicationDate(currentBook.metadata.date)}`}
+                      </span>
+                    )}
                   </p>
+                  {currentBook.missingChapters && currentBook.missingChapters.length > 0 && (
+                    <p className="mt-1 text-xs text-amber-300" title={currentBook.missingChapters.join(", ")}>
+                      有 {currentBook.missingChapters.length} 个章节在文件中缺失，已用占位页代替。
+                    </p>
+                  )}
+                  {currentBook.metadata.series && (
+                    <p className="text-xs text-slate-500">
+                      {currentBook.metadata.series}
+                      {currentBook.metadata.seriesIndex
+                        ? ` · 第 ${formatSeriesIndex(currentBook.metadata.seriesIndex)} 册`
+                        : ""}
+                    </p>
+                  )}
+                  {rightsParagraphs.length > 0 && (
+                    <div className="mt-1 space-y-1 text-xs text-slate-500">
+                      {(rightsExpanded ? rightsParagraphs : rightsParagraphs.slice(0, 1)).map((paragraph, index) => (
+                        <p key={index} className={rightsExpanded ? undefined : "line-clamp-2"}>
+                          {paragraph}
+                        </p>
+                      ))}
+                      {(rightsParagraphs.length > 1 || rightsParagraphs[0]!.length > 120) && (
+                        <button
+                          type="button"
+                          onClick={() => setRightsExpanded(value => !value)}
+                          className="text-slate-400 hover:text-slate-200"
+                        >
+                          {rightsExpanded ? "收起" : "展开版权信息"}
+                        </button>
+                      )}
+                    </div>
+                  )}
+                  <button
+                    type="button"
+                    onClick={() => void handleCopyBibtex()}
+                    title="Ctrl+Shift+B"
+                    className="mt-2 rounded border border-slate-700 bg-slate-800 px-2 py-0.5 text-xs text-slate-300 hover:bg-slate-700"
+                  >
+                    复制 BibTeX
+                  </button>
+                  {/* Mouse-down is swallowed so the click doesn't clear the selection being quoted. */}
+                  <button
+                    type="button"
+                    onMouseDown={event => event.preventDefault()}
+                    onClick={() => void handleCopyQuote()}
+                    title="复制选中的文字（未选中时复制整章）并附上出处；Ctrl+Shift+X"
+                    className="ml-2 mt-2 rounded border border-slate-700 bg-slate-800 px-2 py-0.5 text-xs text-slate-300 hover:bg-slate-700"
+                  >
+                    复制引文
+                  </button>
+                  <button
+                    type="button"
+                    onClick={handleExportEpub}
+                    title="以精简排版导出为新的 EPUB，便于发送到电子阅读器"
+                    className="ml-2 mt-2 rounded border border-slate-700 bg-slate-800 px-2 py-0.5 text-xs text-slate-300 hover:bg-slate-700"
+                  >
+                    导出精简 EPUB
+                  </button>
+                  <button
+                    type="button"
+                    // Keeps the reader's selection, which a click would otherwise clear before the handler runs.
+                    onMouseDown={event => event.preventDefault()}
+                    onClick={handleClipSelection}
+                    title="将选中的文字连同出处追加到本书摘录；Ctrl+Shift+K"
+                    className="ml-2 mt-2 rounded border border-slate-700 bg-slate-800 px-2 py-0.5 text-xs text-slate-300 hover:bg-slate-700"
+                  >
+                    摘录选中文字
+                  </button>
+                  <button
+                    type="button"
+                    onClick={handleDownloadClippings}
+                    title="将本书的摘录下载为 Markdown 文件"
+                    className="ml-2 mt-2 rounded border border-slate-700 bg-slate-800 px-2 py-0.5 text-xs text-slate-300 hover:bg-slate-700"
+                  >
+                    下载摘录
+                  </button>
                 </div>
 
                 <div className="space-y-2">
                   <h3 className="text-sm font-semibold text-slate-300">章节目录</h3>
-                  <nav className="max-h-[40vh] space-y-1 overflow-auto pr-1 text-sm">
+                  {/* As tall as the window allows, so long TOCs use tall screens yet never outgrow short ones. */}
+                  <nav
+                    aria-label="章节目录"
+                    className="max-h-[max(10rem,calc(100dvh-10rem))] space-y-1 overflow-auto overscroll-contain pr-1 text-sm"
+                  >
                     {flatToc.length === 0 && (
                       <p className="text-slate-500">目录缺失，使用 spine 顺序。</p>
                     )}
-                    {flatToc.map(item => {
+                    {flatToc.map((item, position) => {
+                      if (isTocEntryHidden(item.href)) return null;
                       const chapterIndex = findChapterIndex(currentBook.chapters, item.href);
                       const isActive = chapterIndex === currentChapterIndex;
                       return (
@@ -741,27 +3019,530 @@ export function App() {
                           key={item.id}
                           type="button"
                           onClick={() => handleTocSelect(item.href)}
+                          aria-current={isActive ? "page" : undefined}
                           className={`block w-full rounded-md px-2 py-1 text-left ${
                             isActive ? "bg-slate-800 text-sky-200" : "hover:bg-slate-800"
                           }`}
                         >
-                          {item.label}
+                          {tocDisplayLabels[position] ?? item.label}
+                          {prefs.showTocHrefs && (
+                            <span title={item.href} className="block truncate font-mono text-xs text-slate-500">
+                              {item.href}
+                            </span>
+                          )}
                         </button>
                       );
                     })}
                   </nav>
                 </div>
+
+                {bookmarks.length > 0 && (
+                  <div className="space-y-2">
+                    <h3 className="text-sm font-semibold text-slate-300">书签</h3>
+                    <ul className="max-h-[30vh] space-y-1 overflow-auto pr-1 text-sm">
+                      {bookmarks.map(bookmark => {
+                        const key = bookmarkKey(bookmark);
+                        const expanded = expandedBookmark === key;
+                        return (
+                          <li key={key} className="rounded-md px-2 py-1 hover:bg-slate-800/60">
+                            <div className="flex items-center justify-between gap-2">
+                              <button
+                                type="button"
+                                onClick={() => handleBookmarkSelect(bookmark)}
+                                className="min-w-0 flex-1 text-left"
+                              >
+                                <span className="block text-xs text-slate-400">{bookmarkLabel(bookmark)}</span>
+                                {bookmark.note && !expanded && (
+                                  <span className="line-clamp-2 text-slate-300">{bookmark.note}</span>
+                                )}
+                              </button>
+                              <button
+                                type="button"
+                                onClick={() => setExpandedBookmark(expanded ? null : key)}
+                                aria-expanded={expanded}
+                                className="shrink-0 text-xs text-slate-400 hover:text-slate-200"
+                              >
+                                {expanded ? "完成" : bookmark.note ? "编辑笔记" : "添加笔记"}
+                              </button>
+                            </div>
+                            {expanded && (
+                              <textarea
+                                autoFocus
+                                defaultValue={bookmark.note ?? ""}
+                                onBlur={event => void handleBookmarkNoteSave(bookmark, event.target.value)}
+                                rows={3}
+                                placeholder="写点什么…"
+                                aria-label="书签笔记"
+                                className="mt-1 w-full rounded border border-slate-700 bg-slate-800 px-2 py-1 text-sm text-slate-200"
+                              />
+                            )}
+                          </li>
+                        );
+                      })}
+                    </ul>
+                  </div>
+                )}
+
+                {(currentBook.pageMap?.length ?? 0) > 0 && (
+                  <div className="space-y-2">
+                    <h3 className="text-sm font-semibold text-slate-300">跳到纸书页码</h3>
+                    <form onSubmit={handleGoToPrintPage} className="flex gap-2">
+                      <input
+                        value={printPageQuery}
+                        onChange={event => setPrintPageQuery(event.target.value)}
+                        placeholder="如 12 或 xiv"
+                        aria-label="纸书页码"
+                        className="min-w-0 flex-1 rounded border border-slate-700 bg-slate-800 px-2 py-1 text-sm text-slate-200"
+                      />
+                      <button
+                        type="submit"
+                        className="rounded border border-slate-700 bg-slate-800 px-3 py-1 text-sm text-slate-200 hover:bg-slate-700"
+                      >
+                        跳转
+                      </button>
+                    </form>
+                  </div>
+                )}
+
+                <div className="space-y-2">
+                  <h3 className="text-sm font-semibold text-slate-300">全文搜索</h3>
+                  <form onSubmit={handleSearchSubmit} className="flex gap-2">
+                    <input
+                      ref={searchInputRef}
+                      type="search"
+                      value={searchQuery}
+                      onChange={event => {
+                        setSearchQuery(event.target.value);
+                        debouncedSearch?.update(event.target.value);
+                      }}
+                      placeholder='词语 "短语" -排除'
+                      aria-label="搜索本书"
+                      className="min-w-0 flex-1 rounded border border-slate-700 bg-slate-800 px-2 py-1 text-sm text-slate-200"
+                    />
+                    <button
+                      type="submit"
+                      className="rounded border border-slate-700 bg-slate-800 px-3 py-1 text-sm text-slate-200 hover:bg-slate-700"
+                    >
+                      搜索
+                    </button>
+                  </form>
+                  {searchHits && (
+                    <p className="text-xs text-slate-400" aria-live="polite">
+                      {searchMatchCount} 处匹配
+                    </p>
+                  )}
+                  {searchHits && (
+                    <div className="max-h-[30vh] space-y-1 overflow-auto pr-1 text-sm">
+                      {searchHits.length === 0 && !noteHits?.length && (
+                        <p className="text-slate-500">没有找到匹配的章节。</p>
+                      )}
+                      {noteHits?.map(hit => (
+                        <button
+                          key={bookmarkKey(hit.item)}
+                          type="button"
+                          onClick={() => handleBookmarkSelect(hit.item)}
+                          className="block w-full rounded-md px-2 py-1 text-left hover:bg-slate-800"
+                        >
+                          <span className="block text-xs text-slate-400">书签笔记 · {bookmarkLabel(hit.item)}</span>
+                          <span className="line-clamp-2 text-slate-300">{highlightSnippet(searchSnippet(hit))}</span>
+                        </button>
+                      ))}
+                      {searchHits.map(hit => {
+                        const parts = highlightSnippet(searchSnippet(hit));
+                        return (
+                          <button
+                            key={hit.chapterIndex}
+                            type="button"
+                            onClick={() => updatePosition(currentBook.id, () => ({ chapter: hit.chapterIndex, page: 0 }))}
+                            className="block w-full rounded-md px-2 py-1 text-left hover:bg-slate-800"
+                          >
+                            <span className="block text-xs text-slate-400">
+                              章节 {hit.chapterIndex + 1} · {hit.ranges.length} 处
+                            </span>
+                            <span className="line-clamp-2 text-slate-300">{parts}</span>
+                          </button>
+                        );
+                      })}
+                    </div>
+                  )}
+                </div>
+
+                {figures.length > 0 && (
+                  <div className="space-y-2">
+                    <h3 className="text-sm font-semibold text-slate-300">插图</h3>
+                    <nav className="max-h-[30vh] space-y-1 overflow-auto pr-1 text-sm">
+                      {figures.map((figure, index) => (
+                        <button
+                          key={`${figure.chapterIndex}-${index}`}
+                          type="button"
+                          onClick={() =>
+                            updatePosition(currentBook.id, () => ({ chapter: figure.chapterIndex, page: 0 }))
+                          }
+                          aria-current={figure.chapterIndex === currentChapterIndex ? "true" : undefined}
+                          className="block w-full rounded-md px-2 py-1 text-left hover:bg-slate-800"
+                        >
+                          <span className="line-clamp-2">
+                            {figure.caption || figure.alt || `图 ${index + 1}`}
+                          </span>
+                        </button>
+                      ))}
+                    </nav>
+                  </div>
+                )}
               </div>
             ) : (
               <p className="text-sm text-slate-400">选择一本电子书后，可在此浏览目录并跳转章节。</p>
             )}
           </div>
 
+          <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+            阅读档案
+            <select
+              value={profile}
+              onChange={event => handleSwitchProfile(event.target.value)}
+              className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+            >
+              {profiles.map(name => (
+                <option key={name} value={name}>
+                  {name === DEFAULT_PROFILE ? "默认" : name}
+                </option>
+              ))}
+              <option value={NEW_PROFILE_OPTION}>新建档案…</option>
+            </select>
+          </label>
+
+          <div className="space-y-2">
+            <h3 className="text-sm font-semibold text-slate-300">排版</h3>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              主题
+              <select
+                value={theme.id}
+                onChange={event => setPrefs(prev => ({ ...prev, themeId: event.target.value }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {availableThemes.map(option => (
+                  <option key={option.id} value={option.id}>
+                    {option.label}
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.textAlign === "justify"}
+                onChange={event =>
+                  setPrefs(prev => ({ ...prev, textAlign: event.target.checked ? "justify" : "start" }))
+                }
+              />
+              两端对齐
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.hyphenate}
+                disabled={prefs.textAlign !== "justify"}
+                onChange={event => setPrefs(prev => ({ ...prev, hyphenate: event.target.checked }))}
+              />
+              自动断词（按书籍语言）
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.respectSourceColors}
+                onChange={event => setPrefs(prev => ({ ...prev, respectSourceColors: event.target.checked }))}
+              />
+              保留书中文字颜色
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.emphasisColors}
+                disabled={!theme.emphasisColor}
+                onChange={event => setPrefs(prev => ({ ...prev, emphasisColors: event.target.checked }))}
+              />
+              粗体与斜体使用强调色
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.paperTexture}
+                disabled={!theme.backgroundTexture}
+                onChange={event => setPrefs(prev => ({ ...prev, paperTexture: event.target.checked }))}
+              />
+              纸张纹理背景
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.preferHeadingTitle}
+                onChange={event => setPrefs(prev => ({ ...prev, preferHeadingTitle: event.target.checked }))}
+              />
+              章节名优先取正文标题
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              合并过短章节
+              <select
+                value={prefs.mergeShortChapters}
+                onChange={event => setPrefs(prev => ({ ...prev, mergeShortChapters: Number(event.target.value) }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {MERGE_SHORT_CHAPTER_OPTIONS.map(option => (
+                  <option key={option} value={option}>
+                    {option === 0 ? "关闭" : `少于 ${option} 字`}
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              内存中保留的章节
+              <select
+                value={prefs.chapterCacheSize}
+                onChange={event => setPrefs(prev => ({ ...prev, chapterCacheSize: Number(event.target.value) }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {CHAPTER_CACHE_SIZE_OPTIONS.map(option => (
+                  <option key={option} value={option}>
+                    {option} 章
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              引文出处格式
+              <select
+                value={prefs.citationStyle}
+                onChange={event => setPrefs(prev => ({ ...prev, citationStyle: event.target.value as CitationStyle }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {CITATION_STYLES.map(style => (
+                  <option key={style} value={style}>
+                    {CITATION_STYLE_LABELS[style]}
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              全书页码每页字数
+              <select
+                value={prefs.charsPerPage}
+                onChange={event => setPrefs(prev => ({ ...prev, charsPerPage: Number(event.target.value) }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {CHARS_PER_PAGE_OPTIONS.map(option => (
+                  <option key={option} value={option}>
+                    约 {option} 字
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              纯文本模式的标点空格
+              <select
+                value={prefs.spacing}
+                onChange={event => setPrefs(prev => ({ ...prev, spacing: event.target.value as SpacingLevel }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                <option value="smart">按标点规则调整</option>
+                <option value="source">照原文保留</option>
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              翻章顺序
+              <select
+                value={prefs.navOrder}
+                onChange={event => setPrefs(prev => ({ ...prev, navOrder: event.target.value as NavOrder }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                <option value="spine">阅读顺序</option>
+                <option value="toc">目录顺序</option>
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              页面布局
+              <select
+                value={prefs.pageLayout}
+                onChange={event => setPrefs(prev => ({ ...prev, pageLayout: event.target.value as PageLayout }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                <option value="single">单页</option>
+                <option value="twoPage">双页（窗口足够宽时）</option>
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              Tab 越过最后一个链接
+              <select
+                value={prefs.linkFocusWrap}
+                onChange={event => setPrefs(prev => ({ ...prev, linkFocusWrap: event.target.value as LinkFocusWrap }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                <option value="adjacent">跳到下一页的链接</option>
+                <option value="stop">离开正文</option>
+              </select>
+            </label>
+            <label className="flex items-center justify-between gap-2 text-sm text-slate-300">
+              首行缩进
+              <select
+                value={prefs.firstLineIndent}
+                onChange={event => setPrefs(prev => ({ ...prev, firstLineIndent: Number(event.target.value) }))}
+                className="rounded border border-slate-700 bg-slate-800 px-2 py-1 text-slate-200"
+              >
+                {FIRST_LINE_INDENT_OPTIONS.map(option => (
+                  <option key={option} value={option}>
+                    {option === 0 ? "无" : `${option} 字`}
+                  </option>
+                ))}
+              </select>
+            </label>
+            {prefs.firstLineIndent > 0 && (
+              <label className="flex items-center gap-2 text-sm text-slate-300">
+                <input
+                  type="checkbox"
+                  checked={!prefs.paragraphGap}
+                  onChange={event => setPrefs(prev => ({ ...prev, paragraphGap: !event.target.checked }))}
+                />
+                取消段间距（书籍排版）
+              </label>
+            )}
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.imageLightbox}
+                onChange={event => setPrefs(prev => ({ ...prev, imageLightbox: event.target.checked }))}
+              />
+              点击图片放大查看
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.scrollEdgeNavigation}
+                onChange={event => setPrefs(prev => ({ ...prev, scrollEdgeNavigation: event.target.checked }))}
+              />
+              滚到页首或页尾后继续滚动翻页
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.invertDiagramsInDark}
+                onChange={event => setPrefs(prev => ({ ...prev, invertDiagramsInDark: event.target.checked }))}
+              />
+              深色主题下反色显示图表（照片除外）
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.numberHeadings}
+                onChange={event => setPrefs(prev => ({ ...prev, numberHeadings: event.target.checked }))}
+              />
+              自动为标题编号
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.stripRunningHeads}
+                onChange={event => setPrefs(prev => ({ ...prev, stripRunningHeads: event.target.checked }))}
+              />
+              隐藏重复的页眉页脚
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.hideEmptyChapters}
+                onChange={event => setPrefs(prev => ({ ...prev, hideEmptyChapters: event.target.checked }))}
+              />
+              跳过空白章节
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.showTocHrefs}
+                onChange={event => setPrefs(prev => ({ ...prev, showTocHrefs: event.target.checked }))}
+              />
+              目录中显示文件路径
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.mouseSideButtons}
+                onChange={event => setPrefs(prev => ({ ...prev, mouseSideButtons: event.target.checked }))}
+              />
+              鼠标侧键翻页
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.animateTransitions}
+                onChange={event => setPrefs(prev => ({ ...prev, animateTransitions: event.target.checked }))}
+              />
+              切换章节时使用过渡动画
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.reduceMotion}
+                onChange={event => setPrefs(prev => ({ ...prev, reduceMotion: event.target.checked }))}
+              />
+              减少动画（关闭所有过渡与动画效果）
+            </label>
+            <label className="flex items-center gap-2 text-sm text-slate-300">
+              <input
+                type="checkbox"
+                checked={prefs.rememberSelection}
+                onChange={event => setPrefs(prev => ({ ...prev, rememberSelection: event.target.checked }))}
+              />
+              回到章节时恢复上次选中的文字
+            </label>
+            {currentBook && (
+              <label className="flex items-center gap-2 text-sm text-slate-300" title="Ctrl+Shift+P">
+                <input type="checkbox" checked={plainTextMode} onChange={() => togglePlainText(currentBook.id)} />
+                纯文本模式（仅本书）
+              </label>
+            )}
+            <div className="flex gap-2 text-xs">
+              <button
+                type="button"
+                onClick={handleExportTheme}
+                className="rounded border border-slate-700 bg-slate-800 px-3 py-1 text-slate-200 hover:bg-slate-700"
+              >
+                导出主题
+              </button>
+              <label className="cursor-pointer rounded border border-slate-700 bg-slate-800 px-3 py-1 text-slate-200 hover:bg-slate-700">
+                <input type="file" accept="application/json,.json" onChange={handleImportTheme} className="hidden" />
+                导入主题
+              </label>
+            </div>
+          </div>
+
+          <div className="space-y-2">
+            <h3 className="text-sm font-semibold text-slate-300">同步</h3>
+            <div className="flex gap-2 text-sm">
+              <button
+                type="button"
+                onClick={handleExportSync}
+                className="rounded border border-slate-700 bg-slate-800 px-3 py-1 text-slate-200 hover:bg-slate-700"
+              >
+                导出进度
+              </button>
+              <label className="cursor-pointer rounded border border-slate-700 bg-slate-800 px-3 py-1 text-slate-200 hover:bg-slate-700">
+                <input type="file" accept="application/json,.json" onChange={handleImportSync} className="hidden" />
+                导入进度
+              </label>
+            </div>
+          </div>
+
           {error && (
             <p className="rounded-md border border-red-500 bg-red-500/10 p-3 text-sm text-red-200">
               {error}
             </p>
           )}
+          {notice && (
+            <p
+              role="status"
+              className="fixed bottom-4 right-4 z-50 rounded-md border border-sky-600 bg-slate-900/95 px-4 py-2 text-sm text-sky-100 shadow-lg"
+            >
+              {notice}
+            </p>
+          )}
         </aside>
 
         <section className="flex-1 overflow-hidden">
@@ -773,16 +3554,121 @@ export function App() {
             </div>
           ) : (
             <div className="flex h-full flex-col rounded-xl border border-slate-800 bg-slate-900/60">
+              {eagerProgress && (
+                <div role="status" className="flex items-center gap-2 border-b border-slate-800 px-4 py-2 text-xs text-slate-400">
+                  正在预加载全书 {eagerProgress.loaded} / {eagerProgress.total}
+                  <progress value={eagerProgress.loaded} max={eagerProgress.total} className="h-1.5 w-40" />
+                </div>
+              )}
               <div className="flex items-center justify-between border-b border-slate-800 px-4 py-3 text-sm text-slate-300">
                 <span>
-                  章节 {currentChapterIndex + 1} / {currentBook.chapters.length} · 第 {currentPageIndex + 1} 页 /{" "}
+                  <ChapterFilmstrip
+                    current={currentChapterIndex}
+                    count={currentBook.chapters.length}
+                    titleAt={chapterTitleAt}
+                    onSelect={index => updatePosition(currentBook.id, () => ({ chapter: index, page: 0 }))}
+                  >
+                    章节 {currentChapterIndex + 1 - skippedBeforeCurrent} /{" "}
+                    {currentBook.chapters.length - skippedChapters.size}
+                  </ChapterFilmstrip>{" "}
+                  · 第 {spreadLabel(visiblePages) || currentPageIndex + 1} 页 /{" "}
                   {totalPages}
+                  {printPage && (
+                    <span title="书中标注的纸质版页码" className="text-slate-500">
+                      {" "}
+                      · 纸书第 {printPage} 页
+                    </span>
+                  )}
+                  {syntheticPages && syntheticPages.totalPages > 0 && (
+                    <span title={`按每页约 ${prefs.charsPerPage} 字估算，并非实际排版页`} className="text-slate-500">
+                      {" "}
+                      · 全书第 {bookPage} / {syntheticPages.totalPages} 页
+                    </span>
+                  )}
+                  {chapterTimeLeft.minutes > 0 && (
+                    <span
+                      title={
+                        chapterTimeLeft.personalized
+                          ? `按你最近的阅读速度（每分钟约 ${Math.round(prefs.readingPaceWpm)} 字）估算`
+                          : `按每分钟 ${DEFAULT_WORDS_PER_MINUTE} 字估算；读完几章后改用你的速度`
+                      }
+                      className="text-slate-500"
+                    >
+                      {" "}
+                      · 本章约剩 {chapterTimeLeft.minutes} 分钟{chapterTimeLeft.personalized && "（你的速度）"}
+                    </span>
+                  )}
                 </span>
                 <div className="space-x-2">
+                  {speechState !== "idle" && (
+                    <button
+                      type="button"
+                      onClick={() =>
+                        speechState === "paused" ? readAloudRef.current?.resume() : readAloudRef.current?.pause()
+                      }
+                      className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700"
+                    >
+                      {speechState === "paused" ? "继续" : "暂停"}
+                    </button>
+                  )}
+                  <button
+                    type="button"
+                    onClick={handleToggleSpeech}
+                    aria-pressed={speechState !== "idle"}
+                    title="Ctrl+Shift+S"
+                    className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700"
+                  >
+                    {speechState === "idle" ? "朗读" : "停止朗读"}
+                  </button>
+                  <button
+                    type="button"
+                    onClick={() => void handleCopyChapterHtml()}
+                    title="Ctrl+Shift+H"
+                    className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700"
+                  >
+                    复制 HTML
+                  </button>
+                  <button
+                    type="button"
+                    onClick={() => void handleAddBookmark()}
+                    className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700"
+                  >
+                    添加书签
+                  </button>
+                  {autoScrolling && (
+                    <span className="inline-flex items-center gap-1">
+                      <button
+                        type="button"
+                        onClick={() => adjustAutoScrollSpeed(-1)}
+                        aria-label="减慢滚动"
+                        className="rounded border border-slate-700 bg-slate-800 px-2 py-1 hover:bg-slate-700"
+                      >
+                        −
+                      </button>
+                      <span className="tabular-nums">{autoScrollSpeed} px/s</span>
+                      <button
+                        type="button"
+                        onClick={() => adjustAutoScrollSpeed(1)}
+                        aria-label="加快滚动"
+                        className="rounded border border-slate-700 bg-slate-800 px-2 py-1 hover:bg-slate-700"
+                      >
+                        +
+                      </button>
+                    </span>
+                  )}
+                  <button
+                    type="button"
+                    onClick={() => setAutoScrolling(value => !value)}
+                    aria-pressed={autoScrolling}
+                    title="Ctrl+Shift+A"
+                    className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700"
+                  >
+                    {autoScrolling ? "停止滚动" : "自动滚动"}
+                  </button>
                   <button
                     type="button"
                     onClick={handlePrev}
-                    disabled={currentChapterIndex === 0 && currentPageIndex === 0}
+                    disabled={previousChapterIndex === null && currentPageIndex === 0}
                     className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700 disabled:cursor-not-allowed disabled:opacity-40"
                   >
                     上一页
@@ -790,10 +3676,7 @@ export function App() {
                   <button
                     type="button"
                     onClick={handleNext}
-                    disabled={
-                      currentChapterIndex >= currentBook.chapters.length - 1 &&
-                      currentPageIndex >= totalPages - 1
-                    }
+                    disabled={isAtBookEnd}
                     className="rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700 disabled:cursor-not-allowed disabled:opacity-40"
                   >
                     下一页
@@ -801,16 +3684,160 @@ export function App() {
                 </div>
               </div>
 
+              {resumePrompt?.bookId === currentBook.id && (
+                <div
+                  role="status"
+                  className="flex items-center justify-between gap-3 border-b border-slate-800 bg-slate-800/60 px-4 py-2 text-sm text-slate-200"
+                >
+                  <span>
+                    上次读到第{" "}
+                    {Math.max(0, currentBook.chapters.findIndex(ch => ch.index === resumePrompt.saved.spineIndex)) + 1}{" "}
+                    章，是否继续？
+                  </span>
+                  <span className="space-x-2">
+                    <button
+                      type="button"
+                      onClick={() => settleResumePrompt("resume")}
+                      className="rounded border border-sky-600 bg-sky-600/20 px-3 py-0.5 hover:bg-sky-600/40"
+                    >
+                      继续阅读
+                    </button>
+                    <button
+                      type="button"
+                      onClick={() => settleResumePrompt("startOver")}
+                      className="rounded border border-slate-600 px-3 py-0.5 hover:bg-slate-700"
+                    >
+                      从头开始
+                    </button>
+                  </span>
+                </div>
+              )}
+
+              {speechPosition && (
+                <div
+                  className="truncate border-b border-slate-800 bg-sky-500/10 px-4 py-1 text-xs text-sky-200"
+                  aria-live="polite"
+                >
+                  正在朗读：{speechPosition.text}
+                </div>
+              )}
+
+              {breadcrumb.length > 0 && (
+                <div className="truncate border-b border-slate-800 px-4 py-1 text-xs text-slate-400">
+                  {breadcrumb.join(" › ")}
+                </div>
+              )}
+
+              <div ref={contentAreaRef} className="relative flex min-h-0 flex-1">
               <ShadowPage
                 html={pageMarkup}
-                styles={[...externalStyles, ...inlineStyles]}
+                styles={[
+                  ...externalStyles,
+                  ...inlineStyles,
+                  themeCss,
+                  typographyCss,
+                  debugOutlineCss,
+                  pageLayout === "twoPage" ? SPREAD_CSS : "",
+                ]}
                 className={bodyClassName}
                 inlineStyle={bodyInlineStyle}
+                lang={chapterLanguage}
+                dir={pageDirection}
+                sourceColorBackground={prefs.respectSourceColors ? theme.background : undefined}
+                onActiveHeadingChange={setPageHeading}
+                keyMap={keyMap}
+                chapterIndex={currentChapterIndex}
+                animateTransitions={chapterTransitionsEnabled(prefs, motionContext)}
+                autoScrollSpeed={autoScrolling ? autoScrollSpeed : null}
+                onAutoScrollEnd={handleAutoScrollEnd}
+                onAutoScrollInterrupt={() => setAutoScrolling(false)}
+                onSideButton={
+                  prefs.mouseSideButtons ? direction => (direction === 1 ? handleNext() : handlePrev()) : undefined
+                }
+                wrapLinkFocus={prefs.linkFocusWrap === "adjacent"}
+                scrollTarget={scrollTarget}
+                onScrollTargetReached={() => setScrollTarget(null)}
+                onBookLink={handleTocSelect}
+                linkedNote={linkedNonLinearNote}
+                restoreSelection={restoreSelection}
+                onSelect={
+                  prefs.rememberSelection
+                    ? selection => {
+                        const saved = { ...selection, page: currentPageIndex };
+                        selectionStore.save(currentBook.id, currentChapterIndex, saved);
+                      }
+                    : undefined
+                }
+                onImageClick={
+                  prefs.imageLightbox ? image => dispatchLightbox({ type: "imageClick", image }) : undefined
+                }
+                invertDiagrams={prefs.invertDiagramsInDark && isDarkTheme(theme)}
+                onEdgeOverscroll={
+                  prefs.scrollEdgeNavigation ? direction => (direction === 1 ? handleNext() : handlePrev()) : undefined
+                }
+                onAction={action => {
+                  // Right-to-left books advance towards the left, so the arrow keys swap.
+                  if (action === "previousChapter") (pageDirection === "rtl" ? handleNext : handlePrev)();
+                  else if (action === "nextChapter") (pageDirection === "rtl" ? handlePrev : handleNext)();
+                  else if (action === "togglePlainText") togglePlainText(currentBook.id);
+                  else if (action === "copyBibtex") void handleCopyBibtex();
+                  else if (action === "copyQuote") void handleCopyQuote();
+                  else if (action === "clipSelection") handleClipSelection();
+                  else if (action === "toggleSpeech") handleToggleSpeech();
+                  else if (action === "copyChapterHtml") void handleCopyChapterHtml();
+                  else if (action === "toggleAutoScroll") setAutoScrolling(value => !value);
+                  else if (action === "autoScrollFaster" && autoScrolling) adjustAutoScrollSpeed(1);
+                  else if (action === "autoScrollSlower" && autoScrolling) adjustAutoScrollSpeed(-1);
+                  else if (action === "nextHeading") handleHeadingRollover(1);
+                  else if (action === "previousHeading") handleHeadingRollover(-1);
+                  else if (action === "openCommandPalette") setCommandPaletteOpen(true);
+                  else if (action === "toggleDebugOutlines") setDebugOutlines(value => !value);
+                  else if (action === "reloadBook") void handleReloadBook();
+                  else if (action === "nextBook") openSiblingBook(nextBook);
+                  else if (action === "previousBook") openSiblingBook(previousBook);
+                  else if (action === "pasteOpen") void handlePasteOpen();
+                  else if (action === "focusNextLink") handleLinkRollover(1);
+                  else if (action === "focusPreviousLink") handleLinkRollover(-1);
+                }}
               />
+              {chapterMarkers.length > 0 && (
+                <div className="pointer-events-none absolute bottom-2 right-1 top-2 w-2" aria-label="书签与搜索结果位置">
+                  {chapterMarkers.map(cluster => {
+                    const bookmarkCount = cluster.items.filter(item => item.kind === "bookmark").length;
+                    const searchCount = cluster.items.length - bookmarkCount;
+                    const label = [bookmarkCount > 0 && `书签 ${bookmarkCount}`, searchCount > 0 && `匹配 ${searchCount}`]
+                      .filter(Boolean)
+                      .join(" · ");
+                    return (
+                      <button
+                        key={cluster.fraction}
+                        type="button"
+                        title={label}
+                        aria-label={label}
+                        onClick={() =>
+                          updatePosition(currentBook.id, position => ({
+                            chapter: position.chapter,
+                            page: cluster.items[0]!.page,
+                          }))
+                        }
+                        style={{ top: `${cluster.fraction * 100}%` }}
+                        className={`pointer-events-auto absolute left-0 w-2 -translate-y-1/2 rounded-sm ${
+                          cluster.items.length > 1 ? "h-2" : "h-1"
+                        } ${bookmarkCount > 0 ? "bg-sky-400" : "bg-amber-400"} hover:scale-150`}
+                      />
+                    );
+                  })}
+                </div>
+              )}
+              </div>
             </div>
           )}
         </section>
       </main>
+      {commandPaletteOpen && (
+        <CommandPalette commands={commandRegistry.list()} onClose={() => setCommandPaletteOpen(false)} />
+      )}
+      {lightbox.open && <ImageLightbox state={lightbox} dispatch={dispatchLightbox} />}
     </div>
   );
 }
@@ -0,0 +1,139 @@
+import { useEffect, useRef, useState } from "react";
+import { comparePaths } from "@core-platform";
+import { generateCoverDataUrl } from "@render-engine";
+
+interface ShelfEntry {
+  key: string;
+  file: File;
+  status: "pending" | "ready" | "error";
+  title?: string;
+  creator?: string;
+  cover?: string | null;
+}
+
+interface LibraryViewProps {
+  /** Called with the chosen book and every book found in the scanned folder. */
+  onOpen: (file: File, folder: File[]) => void;
+}
+
+const SCAN_CONCURRENCY = 4;
+
+export function relativePath(file: File) {
+  return (file as File & { webkitRelativePath?: string }).webkitRelativePath || file.name;
+}
+
+export function LibraryView({ onOpen }: LibraryViewProps) {
+  const folderInputRef = useRef<HTMLInputElement | null>(null);
+  const scanIdRef = useRef(0);
+  const [entries, setEntries] = useState<ShelfEntry[]>([]);
+
+  useEffect(() => {
+    // React does not type the non-standard directory picker attribute.
+    folderInputRef.current?.setAttribute("webkitdirectory", "");
+  }, []);
+
+  const updateEntry = (key: string, patch: Partial<ShelfEntry>) => {
+    setEntries(prev => prev.map(entry => (entry.key === key ? { ...entry, ...patch } : entry)));
+  };
+
+  const scanFolder = async (files: File[]) => {
+    const scanId = ++scanIdRef.current;
+    const epubs = files
+      .filter(file => file.name.toLowerCase().endsWith(".epub"))
+      .sort((a, b) => comparePaths(relativePath(a), relativePath(b)));
+
+    setEntries(epubs.map((file): ShelfEntry => ({ key: relativePath(file), file, status: "pending" })));
+
+    // Metadata requests run a few at a time so large folders stream into the grid
+    // instead of blocking on one giant batch.
+    let cursor = 0;
+    const worker = async () => {
+      while (cursor < epubs.length && scanId === scanIdRef.current) {
+        const file = epubs[cursor++]!;
+        const key = relativePath(file);
+        try {
+          const formData = new FormData();
+          formData.append("file", file);
+          const response = await fetch("/api/epub/metadata", { method: "POST", body: formData });
+          if (!response.ok) {
+            throw new Error(`status ${response.status}`);
+          }
+          const data = (await response.json()) as {
+            metadata: { title?: string; creator?: string; authors?: string[] };
+            cover: string | null;
+          };
+          if (scanId !== scanIdRef.current) return;
+          updateEntry(key, {
+            status: "ready",
+            title: data.metadata.title,
+            creator: data.metadata.creator,
+            cover: data.cover ?? generateCoverDataUrl({ ...data.metadata, title: data.metadata.title ?? file.name }),
+          });
+        } catch (error) {
+          console.warn("Failed to read shelf entry", key, error);
+          if (scanId !== scanIdRef.current) return;
+          updateEntry(key, { status: "error" });
+        }
+      }
+    };
+
+    await Promise.all(Array.from({ length: SCAN_CONCURRENCY }, worker));
+  };
+
+  const handleFolderChange: React.ChangeEventHandler<HTMLInputElement> = event => {
+    const fileList = event.target.files;
+    if (!fileList || fileList.length === 0) return;
+    void scanFolder(Array.from(fileList));
+    event.target.value = "";
+  };
+
+  const pendingCount = entries.filter(entry => entry.status === "pending").length;
+
+  return (
+    <section className="flex-1 space-y-4">
+      <div className="flex items-center justify-between rounded-xl border border-slate-800 bg-slate-900/60 px-4 py-3 text-sm text-slate-300">
+        <span>
+          书架 · {entries.length} 本
+          {pendingCount > 0 ? `（正在读取 ${pendingCount} 本）` : ""}
+        </span>
+        <label className="inline-flex cursor-pointer items-center rounded border border-slate-700 bg-slate-800 px-3 py-1 hover:bg-slate-700">
+          <input ref={folderInputRef} type="file" multiple onChange={handleFolderChange} className="hidden" />
+          选择文件夹
+        </label>
+      </div>
+
+      {entries.length === 0 ? (
+        <div className="flex h-64 items-center justify-center rounded-xl border border-dashed border-slate-800 bg-slate-900/40">
+          <p className="text-sm text-slate-400">选择一个包含 `.epub` 文件的文件夹，生成封面书架。</p>
+        </div>
+      ) : (
+        <div className="grid grid-cols-2 gap-4 sm:grid-cols-3 lg:grid-cols-5">
+          {entries.map(entry => (
+            <button
+              key={entry.key}
+              type="button"
+              onClick={() => onOpen(entry.file, entries.map(item => item.file))}
+              className="group flex flex-col gap-2 text-left"
+            >
+              <div className="flex aspect-[2/3] w-full items-center justify-center overflow-hidden rounded-md border border-slate-800 bg-slate-800 group-hover:border-sky-500">
+                {entry.cover ? (
+                  <img src={entry.cover} alt={entry.title ?? entry.file.name} className="h-full w-full object-cover" />
+                ) : (
+                  <span className="px-2 text-center text-xs text-slate-400">
+                    {entry.status === "pending" ? "读取中..." : entry.title ?? entry.file.name}
+                  </span>
+                )}
+              </div>
+              <span className="line-clamp-2 text-sm font-medium text-slate-200">{entry.title ?? entry.file.name}</span>
+              <span className="line-clamp-1 text-xs text-slate-400">
+                {entry.status === "error" ? "无法读取" : entry.creator ?? "未知作者"}
+              </span>
+            </button>
+          ))}
+        </div>
+      )}
+    </section>
+  );
+}
+
+export default LibraryView;
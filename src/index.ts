@@ -1,6 +1,19 @@
 import { serve } from "bun";
 import index from "./index.html";
-import { openEpub } from "@epub-parser";
+import {
+  listImageSources,
+  loadSpineChapters,
+  openEpub,
+  openEpubMetadata,
+  readChapterLanguage,
+  replaceImageSources,
+  replaceImagesWithAlt,
+  resolveImageMediaType,
+  DISPLAYABLE_IMAGE_TYPES,
+  type BookModel,
+} from "@epub-parser";
+import { isAppError } from "@core-platform";
+import { decodeFragment } from "@render-engine/navigation";
 import {
   buildSetCookie,
   createAuthToken,
@@ -168,7 +181,8 @@ async function handleGetReadingState(req: Request) {
   const { payload, response } = requireAuth(req);
   if (!payload) return response!;
 
-  const snapshot = (await getUserSnapshot(payload.userId)) ?? {};
+  const profile = new URL(req.url).searchParams.get("profile") ?? "";
+  const snapshot = (await getUserSnapshot(payload.userId, profile)) ?? {};
   return json(snapshot);
 }
 
@@ -186,7 +200,7 @@ async function handlePutReadingState(req: Request) {
     return badRequest("Unable to parse snapshot");
   }
 
-  await setUserSnapshot(payload.userId, snapshot);
+  await setUserSnapshot(payload.userId, snapshot, new URL(req.url).searchParams.get("profile") ?? "");
   return json({ ok: true });
 }
 
@@ -196,6 +210,27 @@ async function handleAuthMe(req: Request) {
   return json({ login: payload.login, name: payload.name, avatarUrl: payload.avatarUrl });
 }
 
+async function handleEpubMetadata(req: Request) {
+  try {
+    const formData = await req.formData();
+    const file = formData.get("file");
+
+    if (!file || !(file instanceof Blob)) {
+      return badRequest("缺少电子书文件");
+    }
+
+    const summary = await openEpubMetadata(await file.arrayBuffer(), { normalizeAuthorOrder: true });
+    const cover = summary.cover
+      ? `data:${summary.cover.mediaType};base64,${Buffer.from(summary.cover.data).toString("base64")}`
+      : null;
+
+    return json({ metadata: summary.metadata, cover });
+  } catch (error) {
+    console.error("Failed to read EPUB metadata:", error);
+    return serverError("读取书籍信息失败。");
+  }
+}
+
 async function handleEpubUpload(req: Request) {
   try {
     const formData = await req.formData();
@@ -206,7 +241,7 @@ async function handleEpubUpload(req: Request) {
     }
 
     const buffer = await file.arrayBuffer();
-    const book = await openEpub(buffer);
+    const book = await openEpub(buffer, { normalizeAuthorOrder: true });
 
     const cssResources = await Promise.all(
       Object.values(book.manifest)
@@ -225,31 +260,17 @@ async function handleEpubUpload(req: Request) {
 
     const cssList = cssResources.filter((entry): entry is { id: string; href: string; css: string } => entry !== null);
 
+    const { chapters: spineChapters, missingIdrefs } = await loadSpineChapters(book);
+    if (missingIdrefs.length > 0) {
+      console.warn(`EPUB ${book.id} is missing spine documents: ${missingIdrefs.join(", ")}`);
+    }
     const chapters = await Promise.all(
-      book.spine.map(async (item, index) => {
-        const manifestItem = book.manifest[item.idref];
-        if (!manifestItem) return null;
-
-        if (
-          !manifestItem.mediaType.includes("html") &&
-          !manifestItem.mediaType.includes("xml") &&
-          !manifestItem.mediaType.startsWith("text/")
-        ) {
-          return null;
-        }
-
-        const content = await book.resources.getContent(manifestItem.href);
-        if (content == null) return null;
-
-        const text =
-          typeof content === "string" ? content : new TextDecoder("utf-8").decode(content);
-
+      spineChapters.map(async chapter => {
+        const text = chapter.missing ? chapter.content : await embedChapterImages(book, chapter.href, chapter.content);
         return {
-          index,
-          idref: item.idref,
-          href: manifestItem.href,
-          mediaType: manifestItem.mediaType,
+          ...chapter,
           content: text,
+          language: readChapterLanguage(text),
           styles: cssList,
         };
       }),
@@ -258,24 +279,73 @@ async function handleEpubUpload(req: Request) {
     const payload = {
       id: book.id,
       metadata: book.metadata,
+      cover: await readCoverDataUrl(book),
       toc: book.toc,
+      pageMap: book.pageMap ?? [],
       spine: book.spine,
       manifest: book.manifest,
-      chapters: chapters
-        .filter((chapter): chapter is NonNullable<typeof chapter> => chapter !== null)
-        .map(chapter => ({
-          ...chapter,
-          styles: chapter.styles.map(style => style.css),
-        })),
+      pageProgression: book.pageProgression,
+      missingChapters: missingIdrefs,
+      chapters: chapters.map(chapter => ({
+        ...chapter,
+        styles: chapter.styles.map(style => style.css),
+      })),
     };
 
     return json(payload);
   } catch (error) {
+    if (isAppError(error) && error.code === "EPUB_DRM_PROTECTED") {
+      return json({ error: error.userMessage }, 422);
+    }
     console.error("Failed to parse EPUB:", error);
     return serverError("解析失败，请确认文件是否正确。");
   }
 }
 
+async function readCoverDataUrl(book: BookModel): Promise<string | null> {
+  const item = Object.values(book.manifest).find(entry => entry.href === book.coverHref);
+  const data = item ? await book.resources.getContent(item.href) : null;
+  if (!item || data == null) return null;
+  const bytes = typeof data === "string" ? Buffer.from(data, "utf-8") : Buffer.from(data);
+  const mediaType = resolveImageMediaType(bytes, item.mediaType);
+  return DISPLAYABLE_IMAGE_TYPES.has(mediaType) ? `data:${mediaType};base64,${bytes.toString("base64")}` : null;
+}
+
+/**
+ * Inlines chapter images as data URLs so the browser can render them without a
+ * resource endpoint. Images missing from the archive are dropped from the markup,
+ * and those in a format the browser cannot show give way to their alt text.
+ */
+async function embedChapterImages(book: BookModel, chapterHref: string, html: string) {
+  const sources = listImageSources(html).filter(src => !/^(data|https?):/i.test(src));
+  if (sources.length === 0) return html;
+
+  const replacements = new Map<string, string | null>();
+  const unsupported = new Set<string>();
+  await Promise.all(
+    sources.map(async src => {
+      // A path with a stray "%" is looked up as written and dropped like any missing image.
+      const resolved = decodeFragment(new URL(src, `epub:/${chapterHref}`).pathname.replace(/^\//, ""));
+      const item = Object.values(book.manifest).find(entry => entry.href === resolved);
+      const data = item ? await book.resources.getContent(item.href) : null;
+      if (!item || data == null) {
+        replacements.set(src, null);
+        return;
+      }
+      // SVG is served as text by the resource store.
+      const bytes = typeof data === "string" ? Buffer.from(data, "utf-8") : Buffer.from(data);
+      // The format comes from the bytes, since manifests often mislabel WebP and AVIF images.
+      const mediaType = resolveImageMediaType(bytes, item.mediaType);
+      if (DISPLAYABLE_IMAGE_TYPES.has(mediaType)) {
+        replacements.set(src, `data:${mediaType};base64,${bytes.toString("base64")}`);
+      } else {
+        unsupported.add(src);
+      }
+    }),
+  );
+  return replaceImagesWithAlt(replaceImageSources(html, replacements), unsupported);
+}
+
 const server = serve({
   routes: {
     "/auth/github/login": handleGitHubLogin,
@@ -314,6 +384,10 @@ const server = serve({
       POST: handleEpubUpload,
     },
 
+    "/api/epub/metadata": {
+      POST: handleEpubMetadata,
+    },
+
     // Serve index.html for all unmatched routes.
     "/*": index,
   },
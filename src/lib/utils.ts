@@ -4,3 +4,23 @@ import { twMerge } from "tailwind-merge";
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
+
+const RELATIVE_TIME_UNITS: Array<[limit: number, size: number, label: string]> = [
+  [3_600_000, 60_000, "分钟"],
+  [86_400_000, 3_600_000, "小时"],
+  [2_592_000_000, 86_400_000, "天"],
+  [31_536_000_000, 2_592_000_000, "个月"],
+  [Infinity, 31_536_000_000, "年"],
+];
+
+/** Formats a past timestamp as "3 小时前"; future timestamps (clock skew) read as "刚刚". */
+export function formatRelativeTime(timestamp: number, now = Date.now()) {
+  const elapsed = now - timestamp;
+  if (elapsed < 60_000) return "刚刚";
+  for (const [limit, size, label] of RELATIVE_TIME_UNITS) {
+    if (elapsed < limit) {
+      return `${Math.floor(elapsed / size)} ${label}前`;
+    }
+  }
+  return "";
+}
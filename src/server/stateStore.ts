@@ -1,6 +1,8 @@
-import { mkdir, writeFile } from "fs/promises";
+import { mkdir, rename, writeFile } from "fs/promises";
 import { dirname } from "path";
 import type { StateSnapshot } from "@state-store";
+import { profileKey } from "@state-store/profiles";
+import { mergeSnapshots } from "@state-store/sync";
 
 const STATE_FILE = Bun.env.READING_STATE_FILE ?? "./data/reading_state.json";
 
@@ -35,7 +37,10 @@ async function persist() {
   writePromise = (async () => {
     try {
       await mkdir(dir, { recursive: true });
-      await writeFile(STATE_FILE, JSON.stringify(cache, null, 2), "utf8");
+      // Write beside the target and rename so readers never observe a half-written file.
+      const tempFile = `${STATE_FILE}.${process.pid}.tmp`;
+      await writeFile(tempFile, JSON.stringify(cache, null, 2), "utf8");
+      await rename(tempFile, STATE_FILE);
     } catch (error) {
       console.warn("[server] Failed to persist reading state", error);
     } finally {
@@ -46,18 +51,20 @@ async function persist() {
   await writePromise;
 }
 
-export async function getUserSnapshot(userId: string): Promise<StateSnapshot | undefined> {
+/** Each reader profile of a user has its own document; the default profile keeps the bare user id. */
+export async function getUserSnapshot(userId: string, profile = ""): Promise<StateSnapshot | undefined> {
   await ensureLoaded();
   if (!cache) return undefined;
-  return cache[userId];
+  return cache[profileKey(userId, profile)];
 }
 
-export async function setUserSnapshot(userId: string, snapshot: StateSnapshot) {
+export async function setUserSnapshot(userId: string, snapshot: StateSnapshot, profile = "") {
   await ensureLoaded();
+  const key = profileKey(userId, profile);
   if (!cache) {
-    cache = { [userId]: snapshot };
+    cache = { [key]: snapshot };
   } else {
-    cache[userId] = snapshot;
+    cache[key] = mergeSnapshots(cache[key] ?? {}, snapshot);
   }
   await persist();
 }